@@ -0,0 +1,87 @@
+use crate::coords::latlon::LatLon;
+
+/// A latitude/longitude bounding box in degrees.
+///
+/// The box crosses the antimeridian if the west longitude is greater than the east longitude.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BoundingBox
+{
+    /// The minimum latitude.
+    pub south: f64,
+
+    /// The minimum longitude, or the western edge when crossing the antimeridian.
+    pub west: f64,
+
+    /// The maximum latitude.
+    pub north: f64,
+
+    /// The maximum longitude, or the eastern edge when crossing the antimeridian.
+    pub east: f64,
+}
+
+impl BoundingBox
+{
+    /// Create a new instance.
+    pub fn new(south: f64, west: f64, north: f64, east: f64) -> Self
+    {
+        Self { south, west, north, east }
+    }
+
+    /// Check if the box crosses the antimeridian.
+    pub fn crosses_antimeridian(&self) -> bool
+    {
+        self.west > self.east
+    }
+
+    /// Get the height of the box (degrees).
+    pub fn height(&self) -> f64
+    {
+        self.north - self.south
+    }
+
+    /// Get the width of the box (degrees).
+    pub fn width(&self) -> f64
+    {
+        if self.crosses_antimeridian() { self.east - self.west + 360.0 } else { self.east - self.west }
+    }
+
+    /// Get the centre of the box.
+    pub fn center(&self) -> LatLon
+    {
+        LatLon::new((self.south + self.north) / 2.0, self.west + self.width() / 2.0)
+    }
+
+    /// Check if the point is in the box, including the edges.
+    pub fn contains(&self, p: LatLon) -> bool
+    {
+        let (lat, lon) = (p.latitude(), p.longitude());
+        let within = |lon: f64| if self.crosses_antimeridian() { lon >= self.west || lon <= self.east } else { (self.west..=self.east).contains(&lon) };
+        (self.south..=self.north).contains(&lat) && (within(lon) || lon == -180.0 && within(180.0))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_bbox()
+    {
+        let b = BoundingBox::new(10.0, 20.0, 30.0, 60.0);
+        assert!(!b.crosses_antimeridian());
+        assert_eq!((b.height(), b.width()), (20.0, 40.0));
+        assert_eq!(b.center(), LatLon::new(20.0, 40.0));
+        assert!(b.contains(LatLon::new(10.0, 60.0)));
+        assert!(!b.contains(LatLon::new(9.9, 40.0)));
+
+        let b = BoundingBox::new(-10.0, 170.0, 10.0, -170.0);
+        assert!(b.crosses_antimeridian());
+        assert_approx_eq!(f64, b.width(), 20.0);
+        assert_eq!(b.center(), LatLon::new(0.0, -180.0));
+        assert!(b.contains(LatLon::new(0.0, 180.0)));
+        assert!(b.contains(LatLon::new(0.0, -175.0)));
+        assert!(!b.contains(LatLon::new(0.0, 0.0)));
+    }
+}
@@ -0,0 +1,794 @@
+use std::f64::consts::PI;
+use crate::coords::latlon::LatLon;
+use crate::earth::auxiliary::AuxiliaryLatitude;
+use crate::earth::ellipsoid::Ellipsoid;
+use crate::earth::models::Model;
+use crate::geodesic::Polygon;
+
+/// A point on the unit sphere.
+type Vector = [f64; 3];
+
+/// The number of the leaf cells along an edge of a face.
+const LEAF_CELLS: u64 = 1 << CellId::MAX_LEVEL;
+
+/// The offset of the points just across the edge of a face in the neighbour lookup.
+const FACE_EDGE_OFFSET: f64 = 1e-12;
+
+/// Errors of the cell index.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellError
+{
+    /// The face is not in [0, 5].
+    InvalidFace(u8),
+
+    /// The level is not in [0, 30].
+    InvalidLevel(u8),
+
+    /// The 64-bit id is not a valid cell.
+    InvalidId(u64),
+
+    /// The token is not the hexadecimal id of a valid cell.
+    InvalidToken(String),
+
+    /// The covering has more cells than the limit.
+    TooManyCells(usize),
+}
+
+impl std::fmt::Display for CellError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self
+        {
+            CellError::InvalidFace(face) => write!(f, "cell face {face} is not in [0, 5]"),
+            CellError::InvalidLevel(level) => write!(f, "cell level {level} is not in [0, 30]"),
+            CellError::InvalidId(id) => write!(f, "invalid cell id {id:#018x}"),
+            CellError::InvalidToken(token) => write!(f, "invalid cell token '{token}'"),
+            CellError::TooManyCells(max) => write!(f, "covering has more than {max} cells"),
+        }
+    }
+}
+
+impl std::error::Error for CellError {}
+
+/// A cell of the hierarchical grid of quadtrees on the 6 faces of a cube.
+///
+/// The 64-bit id holds the face in the top 3 bits, followed by 2 bits per level for the child in
+/// the quadtree and a trailing 1 bit marking the level. The children are numbered in the Z-order
+/// of the quadkeys, (i, j) = (0, 0), (0, 1), (1, 0) and (1, 1), so that the ids of the descendants
+/// of a cell are a contiguous range and sort in the order of the quadtree.
+///
+/// The layout of the id follows S2, but the ids and the tokens below the faces are not compatible
+/// with S2, which numbers the children along a Hilbert curve instead of the Z-order.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CellId(u64);
+
+impl CellId
+{
+    /// The number of the faces of the cube.
+    pub const FACES: u8 = 6;
+
+    /// The level of the leaf cells, about 1 cm across on the Earth.
+    pub const MAX_LEVEL: u8 = 30;
+
+    /// Get the cell of the whole face.
+    pub fn from_face(face: u8) -> Result<Self, CellError>
+    {
+        if face >= Self::FACES
+        {
+            return Err(CellError::InvalidFace(face));
+        }
+        Ok(Self((face as u64) << 61 | 1 << 60))
+    }
+
+    /// Get the cell of the 64-bit id.
+    pub fn from_id(id: u64) -> Result<Self, CellError>
+    {
+        let zeros = id.trailing_zeros();
+        if id >> 61 >= Self::FACES as u64 || zeros > 60 || zeros % 2 == 1
+        {
+            return Err(CellError::InvalidId(id));
+        }
+        Ok(Self(id))
+    }
+
+    /// Get the 64-bit id.
+    pub fn id(&self) -> u64
+    {
+        self.0
+    }
+
+    /// Get the face in [0, 5].
+    pub fn face(&self) -> u8
+    {
+        (self.0 >> 61) as u8
+    }
+
+    /// Get the level in [0, 30], 0 for the faces and 30 for the leaves.
+    pub fn level(&self) -> u8
+    {
+        Self::MAX_LEVEL - (self.0.trailing_zeros() / 2) as u8
+    }
+
+    /// Check if the cell is a leaf.
+    pub fn is_leaf(&self) -> bool
+    {
+        self.0 & 1 == 1
+    }
+
+    /// Get the parent cell, or `None` for a face.
+    pub fn parent(&self) -> Option<CellId>
+    {
+        self.level().checked_sub(1).and_then(|level| self.parent_at(level))
+    }
+
+    /// Get the ancestor cell at the level, or `None` if the level is below this cell.
+    pub fn parent_at(&self, level: u8) -> Option<CellId>
+    {
+        if level > self.level()
+        {
+            return None;
+        }
+        let lsb = Self::lsb_at(level);
+        Some(Self(self.0 & lsb.wrapping_neg() | lsb))
+    }
+
+    /// Get the 4 children in the Z-order, or `None` for a leaf.
+    pub fn children(&self) -> Option<[CellId; 4]>
+    {
+        if self.is_leaf()
+        {
+            return None;
+        }
+        let (lsb, child) = (self.lsb(), self.lsb() >> 2);
+        Some([0, 1, 2, 3].map(|k| Self(self.0 - lsb + child * (2 * k + 1))))
+    }
+
+    /// Check if the other cell is this cell or one of its descendants.
+    pub fn contains(&self, other: CellId) -> bool
+    {
+        let lsb = self.lsb();
+        (self.0 - (lsb - 1)..=self.0 + (lsb - 1)).contains(&other.0)
+    }
+
+    /// Get the 4 cells of the same level sharing an edge, in the order of the edges of -j, +i, +j and -i on the face.
+    ///
+    /// The neighbours across the edges of the face are on the adjacent faces.
+    pub fn edge_neighbours(&self) -> [CellId; 4]
+    {
+        let (face, i, j) = self.face_ij();
+        let size = self.size() as i64;
+        let level = self.level();
+        [(0, -1), (1, 0), (0, 1), (-1, 0)].map(|(di, dj)|
+        {
+            let (ni, nj) = (i as i64 + di * size, j as i64 + dj * size);
+            let inside = |n: i64| (0..LEAF_CELLS as i64).contains(&n);
+            if inside(ni) && inside(nj)
+            {
+                return Self::from_face_ij(face, ni as u64, nj as u64).parent_at(level).unwrap();
+            }
+
+            // Take the point just across the edge of the face in line with the centre of the cell.
+            let st = |n: i64| match n
+            {
+                n if n < 0 => -FACE_EDGE_OFFSET,
+                n if n >= LEAF_CELLS as i64 => 1.0 + FACE_EDGE_OFFSET,
+                n => (n as f64 + size as f64 / 2.0) / LEAF_CELLS as f64,
+            };
+            let p = face_uv_to_xyz(face, st_to_uv(st(ni)), st_to_uv(st(nj)));
+            Self::from_xyz(p).parent_at(level).unwrap()
+        })
+    }
+
+    /// Get the hexadecimal token of the id without the trailing zeros.
+    pub fn token(&self) -> String
+    {
+        let hex = format!("{:016x}", self.0);
+        hex.trim_end_matches('0').to_string()
+    }
+
+    fn lsb(&self) -> u64
+    {
+        self.0 & self.0.wrapping_neg()
+    }
+
+    fn lsb_at(level: u8) -> u64
+    {
+        1 << (2 * (Self::MAX_LEVEL - level))
+    }
+
+    // The number of the leaf cells along an edge of the cell.
+    fn size(&self) -> u64
+    {
+        1 << (Self::MAX_LEVEL - self.level())
+    }
+
+    // Get the leaf cell of the face and the leaf coordinates.
+    fn from_face_ij(face: u8, i: u64, j: u64) -> Self
+    {
+        let mut pos = 0;
+        for b in (0..Self::MAX_LEVEL).rev()
+        {
+            pos = pos << 2 | (i >> b & 1) << 1 | (j >> b & 1);
+        }
+        Self((face as u64) << 61 | pos << 1 | 1)
+    }
+
+    // Get the face and the leaf coordinates of the minimum corner of the cell.
+    fn face_ij(&self) -> (u8, u64, u64)
+    {
+        let pos = (self.0 - self.lsb()) >> 1;
+        let (mut i, mut j) = (0, 0);
+        for b in (0..Self::MAX_LEVEL).rev()
+        {
+            i = i << 1 | (pos >> (2 * b + 1) & 1);
+            j = j << 1 | (pos >> (2 * b) & 1);
+        }
+        (self.face(), i, j)
+    }
+
+    // Get the leaf cell containing the point on the unit sphere.
+    fn from_xyz(p: Vector) -> Self
+    {
+        let (face, u, v) = xyz_to_face_uv(p);
+        let ij = |uv: f64| ((uv_to_st(uv) * LEAF_CELLS as f64).floor() as i64).clamp(0, LEAF_CELLS as i64 - 1) as u64;
+        Self::from_face_ij(face, ij(u), ij(v))
+    }
+
+    // Get the vertices on the unit sphere in the counter-clockwise order seen from the outside.
+    fn vertices_xyz(&self) -> [Vector; 4]
+    {
+        let (face, i, j) = self.face_ij();
+        let uv = |n: u64| st_to_uv(n as f64 / LEAF_CELLS as f64);
+        let (u0, v0, u1, v1) = (uv(i), uv(j), uv(i + self.size()), uv(j + self.size()));
+        [(u0, v0), (u1, v0), (u1, v1), (u0, v1)].map(|(u, v)| normalize(face_uv_to_xyz(face, u, v)))
+    }
+
+    // Get the centre on the unit sphere.
+    fn center_xyz(&self) -> Vector
+    {
+        let (face, i, j) = self.face_ij();
+        let st = |n: u64| (n as f64 + self.size() as f64 / 2.0) / LEAF_CELLS as f64;
+        normalize(face_uv_to_xyz(face, st_to_uv(st(i)), st_to_uv(st(j))))
+    }
+}
+
+impl std::fmt::Display for CellId
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        write!(f, "{}", self.token())
+    }
+}
+
+impl std::str::FromStr for CellId
+{
+    type Err = CellError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let token = s.trim();
+        if token.is_empty() || token.len() > 16 || !token.bytes().all(|b| b.is_ascii_hexdigit())
+        {
+            return Err(CellError::InvalidToken(s.to_string()));
+        }
+        let id = u64::from_str_radix(token, 16).map_err(|_| CellError::InvalidToken(s.to_string()))?;
+        Self::from_id(id << (4 * (16 - token.len()))).map_err(|_| CellError::InvalidToken(s.to_string()))
+    }
+}
+
+/// The cell index of the points on the ellipsoid.
+///
+/// The cells are laid on the authalic sphere of the ellipsoid, i.e. a point is located on the cube
+/// with its authalic latitude and longitude. As the authalic sphere preserves the areas, the area
+/// of a cell is its fraction of the sphere times the surface area of the ellipsoid. The edges of
+/// the cells are great circles on the authalic sphere.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CellGrid
+{
+    pub elps: Ellipsoid,
+}
+
+impl CellGrid
+{
+    pub fn model<T>() -> Self where T: Model
+    {
+        Self::with(T::elps())
+    }
+
+    pub fn with(elps: Ellipsoid) -> Self
+    {
+        Self { elps }
+    }
+
+    /// Get the cell of the level containing the point.
+    pub fn cell(&self, p: LatLon, level: u8) -> Result<CellId, CellError>
+    {
+        if level > CellId::MAX_LEVEL
+        {
+            return Err(CellError::InvalidLevel(level));
+        }
+        Ok(CellId::from_xyz(self.vector(p)).parent_at(level).unwrap())
+    }
+
+    /// Get the centre of the cell.
+    pub fn center(&self, cell: CellId) -> LatLon
+    {
+        self.point(cell.center_xyz())
+    }
+
+    /// Get the vertices of the cell in the counter-clockwise order.
+    pub fn vertices(&self, cell: CellId) -> [LatLon; 4]
+    {
+        cell.vertices_xyz().map(|v| self.point(v))
+    }
+
+    /// Get the area of the cell (square meters).
+    pub fn area(&self, cell: CellId) -> f64
+    {
+        let [a, b, c, d] = cell.vertices_xyz();
+        let excess = triangle_excess(a, b, c) + triangle_excess(a, c, d);
+        excess / (4.0 * PI) * self.elps.surface_area()
+    }
+
+    /// Get the cells of the level intersecting the cap of the radius (meters) around the centre.
+    ///
+    /// The radius is measured on the authalic sphere, which differs from the geodesic distance by
+    /// less than the flattening. Fails if there are more than `max_cells` cells.
+    pub fn cover_cap(&self, center: LatLon, radius: f64, level: u8, max_cells: usize) -> Result<Vec<CellId>, CellError>
+    {
+        let region = Region::Cap(self.vector(center), radius / self.elps.authalic_radius());
+        self.cover(&region, level, max_cells)
+    }
+
+    /// Get the cells of the level intersecting the polygon, either wound clockwise or counter-clockwise.
+    ///
+    /// The edges of the polygon are taken as great circles on the authalic sphere, which are close
+    /// to the geodesics for the edges much shorter than the radius of the Earth. As the winding is
+    /// free, each ring bounds the smaller of the two regions it splits the sphere into. Fails if
+    /// there are more than `max_cells` cells.
+    pub fn cover_polygon(&self, polygon: &Polygon, level: u8, max_cells: usize) -> Result<Vec<CellId>, CellError>
+    {
+        let ring = |ring: &[LatLon]|
+        {
+            let mut ring: Vec<Vector> = ring.iter().map(|&p| self.vector(p)).collect();
+            if ring.len() > 1 && ring.first() == ring.last()
+            {
+                ring.pop();
+            }
+            Ring::new(ring)
+        };
+        let mut rings = vec![ring(polygon.outer())];
+        rings.extend(polygon.holes().iter().map(|hole| ring(hole)));
+        self.cover(&Region::Polygon(rings), level, max_cells)
+    }
+
+    fn cover(&self, region: &Region, level: u8, max_cells: usize) -> Result<Vec<CellId>, CellError>
+    {
+        if level > CellId::MAX_LEVEL
+        {
+            return Err(CellError::InvalidLevel(level));
+        }
+        let mut cells = Vec::new();
+        let mut stack: Vec<CellId> = (0..CellId::FACES).rev().map(|face| CellId::from_face(face).unwrap()).collect();
+        while let Some(cell) = stack.pop()
+        {
+            if !region.intersects(cell)
+            {
+                continue;
+            }
+            match cell.children()
+            {
+                Some(children) if cell.level() < level => stack.extend(children.iter().rev()),
+                _ => cells.push(cell),
+            }
+            if cells.len() > max_cells
+            {
+                return Err(CellError::TooManyCells(max_cells));
+            }
+        }
+        Ok(cells)
+    }
+
+    fn vector(&self, p: LatLon) -> Vector
+    {
+        let xi = self.elps.convert_latitude(p.latitude(), AuxiliaryLatitude::Geographic, AuxiliaryLatitude::Authalic);
+        let (sxi, cxi) = xi.to_radians().sin_cos();
+        let (slon, clon) = p.longitude().to_radians().sin_cos();
+        [cxi * clon, cxi * slon, sxi]
+    }
+
+    fn point(&self, p: Vector) -> LatLon
+    {
+        let xi = p[2].atan2(p[0].hypot(p[1])).to_degrees();
+        let lat = self.elps.convert_latitude(xi, AuxiliaryLatitude::Authalic, AuxiliaryLatitude::Geographic);
+        LatLon::new(lat, p[1].atan2(p[0]).to_degrees())
+    }
+}
+
+/// The regions on the unit sphere for the coverings.
+enum Region
+{
+    /// The centre and the angular radius (radians).
+    Cap(Vector, f64),
+
+    /// The outer ring and the holes.
+    Polygon(Vec<Ring>),
+}
+
+impl Region
+{
+    fn intersects(&self, cell: CellId) -> bool
+    {
+        let vertices = cell.vertices_xyz();
+        let edges = [0, 1, 2, 3].map(|k| (vertices[k], vertices[(k + 1) % 4]));
+        let inside_cell = |p: Vector| edges.iter().all(|&(a, b)| dot(p, cross(a, b)) >= 0.0);
+        match self
+        {
+            Region::Cap(center, radius) =>
+            {
+                inside_cell(*center) || edges.iter().any(|&(a, b)| arc_distance(*center, a, b) <= *radius)
+            },
+            Region::Polygon(rings) =>
+            {
+                let inside = |p: Vector| rings[0].contains(p) && !rings[1..].iter().any(|hole| hole.contains(p));
+                rings.iter().flat_map(|ring| ring.vertices.iter()).any(|&p| inside_cell(p))
+                    || inside(cell.center_xyz())
+                    || rings.iter().map(|ring| &ring.vertices).any(|ring| (0..ring.len()).any(|k|
+                    {
+                        let (c, d) = (ring[k], ring[(k + 1) % ring.len()]);
+                        edges.iter().any(|&(a, b)| arcs_cross(a, b, c, d))
+                    }))
+            },
+        }
+    }
+}
+
+fn st_to_uv(s: f64) -> f64
+{
+    if s >= 0.5 { (4.0 * s * s - 1.0) / 3.0 } else { (1.0 - 4.0 * (1.0 - s) * (1.0 - s)) / 3.0 }
+}
+
+fn uv_to_st(u: f64) -> f64
+{
+    if u >= 0.0 { 0.5 * (1.0 + 3.0 * u).sqrt() } else { 1.0 - 0.5 * (1.0 - 3.0 * u).sqrt() }
+}
+
+// The (u, v) axes of each face are right-handed with the outward normal.
+fn face_uv_to_xyz(face: u8, u: f64, v: f64) -> Vector
+{
+    match face
+    {
+        0 => [1.0, u, v],
+        1 => [-u, 1.0, v],
+        2 => [-u, -v, 1.0],
+        3 => [-1.0, -v, -u],
+        4 => [v, -1.0, -u],
+        _ => [v, u, -1.0],
+    }
+}
+
+fn xyz_to_face_uv(p: Vector) -> (u8, f64, f64)
+{
+    let [x, y, z] = p;
+    let axis = if x.abs() >= y.abs() && x.abs() >= z.abs() { 0 } else if y.abs() >= z.abs() { 1 } else { 2 };
+    let face = if p[axis] < 0.0 { axis + 3 } else { axis };
+    match face
+    {
+        0 => (0, y / x, z / x),
+        1 => (1, -x / y, z / y),
+        2 => (2, -x / z, -y / z),
+        3 => (3, z / x, y / x),
+        4 => (4, z / y, -x / y),
+        _ => (5, -y / z, -x / z),
+    }
+}
+
+fn dot(a: Vector, b: Vector) -> f64
+{
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: Vector, b: Vector) -> Vector
+{
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+fn normalize(a: Vector) -> Vector
+{
+    let n = dot(a, a).sqrt();
+    a.map(|x| x / n)
+}
+
+fn angle(a: Vector, b: Vector) -> f64
+{
+    let c = cross(a, b);
+    dot(c, c).sqrt().atan2(dot(a, b))
+}
+
+// The spherical excess of the triangle on the unit sphere.
+fn triangle_excess(a: Vector, b: Vector, c: Vector) -> f64
+{
+    2.0 * dot(a, cross(b, c)).abs().atan2(1.0 + dot(a, b) + dot(b, c) + dot(c, a))
+}
+
+// The angular distance from the point to the arc of the great circle from a to b.
+fn arc_distance(p: Vector, a: Vector, b: Vector) -> f64
+{
+    let n = normalize(cross(a, b));
+    let q = [0, 1, 2].map(|k| p[k] - dot(p, n) * n[k]);
+    if dot(cross(a, q), n) >= 0.0 && dot(cross(q, b), n) >= 0.0
+    {
+        dot(p, n).abs().min(1.0).asin()
+    }
+    else
+    {
+        angle(p, a).min(angle(p, b))
+    }
+}
+
+// Check if the arcs of the great circles from a to b and from c to d cross each other.
+fn arcs_cross(a: Vector, b: Vector, c: Vector, d: Vector) -> bool
+{
+    let (n1, n2) = (cross(a, b), cross(c, d));
+    let x = cross(n1, n2);
+    let within = |x: Vector, a: Vector, b: Vector, n: Vector| dot(cross(a, x), n) >= 0.0 && dot(cross(x, b), n) >= 0.0;
+    [x, x.map(|v| -v)].iter().any(|&x| within(x, a, b, n1) && within(x, c, d, n2))
+}
+
+// The signed spherical excess of the triangle, positive if it is counter-clockwise seen from the outside.
+fn signed_excess(a: Vector, b: Vector, c: Vector) -> f64
+{
+    2.0 * dot(a, cross(b, c)).atan2(1.0 + dot(a, b) + dot(b, c) + dot(c, a))
+}
+
+/// A ring on the unit sphere wound counter-clockwise around the smaller region it bounds.
+struct Ring
+{
+    /// The vertices without the closing one.
+    vertices: Vec<Vector>,
+
+    /// The area of the region on the left of the edges (steradians).
+    area: f64,
+}
+
+impl Ring
+{
+    fn new(mut vertices: Vec<Vector>) -> Self
+    {
+        // The fan of triangles from the first vertex gives the signed area modulo 4π.
+        let n = vertices.len();
+        let area: f64 = (1..n.saturating_sub(1)).map(|k| signed_excess(vertices[0], vertices[k], vertices[k + 1])).sum();
+        let area = area - 4.0 * PI * (area / (4.0 * PI)).round();
+        if area < 0.0
+        {
+            vertices.reverse();
+        }
+        Self { vertices, area: area.abs() }
+    }
+
+    // Check whether the point is inside the ring.
+    //
+    // The winding number w of the ring around the axis through P is 1 if P is inside and -P is
+    // not, -1 for the opposite and 0 if they are on the same side. The fan of the triangles
+    // (P, Vk, Vk+1) differs from the area of the ring by 4π if -P is inside and P is not, or both
+    // are inside, so the sum of w and that multiple k of 4π is 1 for P inside and 0 otherwise.
+    fn contains(&self, p: Vector) -> bool
+    {
+        let n = self.vertices.len();
+        if n < 3
+        {
+            return false;
+        }
+        let (mut sweep, mut fan) = (0.0, 0.0);
+        for k in 0..n
+        {
+            let (a, b) = (self.vertices[k], self.vertices[(k + 1) % n]);
+            sweep += dot(p, cross(a, b)).atan2(dot(a, b) - dot(a, p) * dot(b, p));
+            fan += signed_excess(p, a, b);
+        }
+        let w = (sweep / (2.0 * PI)).round() as i64;
+        let k = ((self.area - fan) / (4.0 * PI)).round() as i64;
+        w + k == 1
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use rstest::*;
+    use float_cmp::assert_approx_eq;
+    use crate::earth::models::{WGS84, Sphere};
+
+    #[test]
+    fn test_hierarchy()
+    {
+        let face = CellId::from_face(3).unwrap();
+        assert_eq!((face.face(), face.level(), face.parent()), (3, 0, None));
+        assert_eq!(face.token(), "7");
+        let children = face.children().unwrap();
+        for (k, child) in children.iter().enumerate()
+        {
+            assert_eq!((child.face(), child.level()), (3, 1));
+            assert_eq!(child.parent(), Some(face));
+            assert!(face.contains(*child));
+            assert!(!child.contains(face));
+            assert_eq!(child.face_ij(), (3, (k as u64 >> 1) << 29, (k as u64 & 1) << 29));
+        }
+        assert!(children.windows(2).all(|w| w[0] < w[1]));
+
+        let leaf = CellId::from_face_ij(5, 123456789, 987654321);
+        assert!(leaf.is_leaf());
+        assert_eq!((leaf.level(), leaf.children()), (30, None));
+        assert_eq!(leaf.face_ij(), (5, 123456789, 987654321));
+        let cell = leaf.parent_at(12).unwrap();
+        assert_eq!(cell.level(), 12);
+        assert!(cell.contains(leaf));
+        assert_eq!(cell.face_ij(), (5, 123456789 >> 18 << 18, 987654321 >> 18 << 18));
+        assert_eq!(cell.parent_at(13), None);
+        assert_eq!(cell.parent_at(12), Some(cell));
+        assert_eq!(CellId::from_id(cell.id()), Ok(cell));
+        assert_eq!(cell.to_string().parse::<CellId>(), Ok(cell));
+    }
+
+    #[test]
+    fn test_errors()
+    {
+        assert_eq!(CellId::from_face(6), Err(CellError::InvalidFace(6)));
+        assert_eq!(CellId::from_id(0), Err(CellError::InvalidId(0)));
+        assert_eq!(CellId::from_id(2), Err(CellError::InvalidId(2)));
+        assert_eq!(CellId::from_id(0xd000000000000000), Err(CellError::InvalidId(0xd000000000000000)));
+        assert_eq!("".parse::<CellId>(), Err(CellError::InvalidToken("".to_string())));
+        assert_eq!("x".parse::<CellId>(), Err(CellError::InvalidToken("x".to_string())));
+        assert_eq!("d".parse::<CellId>(), Err(CellError::InvalidToken("d".to_string())));
+        assert_eq!("+7".parse::<CellId>(), Err(CellError::InvalidToken("+7".to_string())));
+        assert_eq!("-7".parse::<CellId>(), Err(CellError::InvalidToken("-7".to_string())));
+        assert_eq!("7".parse::<CellId>(), CellId::from_face(3));
+        let grid = CellGrid::model::<WGS84>();
+        assert_eq!(grid.cell(LatLon::new(0.0, 0.0), 31), Err(CellError::InvalidLevel(31)));
+        assert_eq!(grid.cover_cap(LatLon::new(0.0, 0.0), 1.0, 31, 100), Err(CellError::InvalidLevel(31)));
+    }
+
+    #[rstest]
+    #[case(0.0, 0.0, 0)]
+    #[case(0.0, 90.0, 1)]
+    #[case(90.0, 0.0, 2)]
+    #[case(0.0, -180.0, 3)]
+    #[case(0.0, -90.0, 4)]
+    #[case(-90.0, 0.0, 5)]
+    #[case(51.4778, -0.0015, 2)]
+    #[case(-33.8568, 151.2153, 3)]
+    fn test_cell(#[case] lat: f64, #[case] lon: f64, #[case] face: u8)
+    {
+        let grid = CellGrid::model::<WGS84>();
+        let p = LatLon::new(lat, lon);
+        let leaf = grid.cell(p, CellId::MAX_LEVEL).unwrap();
+        assert_eq!(leaf.face(), face);
+        let c = grid.center(leaf);
+        assert_approx_eq!(f64, c.latitude(), lat, epsilon = 1e-6);
+        if lat.abs() < 90.0
+        {
+            assert_approx_eq!(f64, c.longitude().abs(), lon.abs(), epsilon = 1e-6);
+        }
+        for level in [0, 1, 10, 20]
+        {
+            let cell = grid.cell(p, level).unwrap();
+            assert_eq!(Some(cell), leaf.parent_at(level));
+            assert_eq!(grid.cell(grid.center(cell), level), Ok(cell));
+        }
+    }
+
+    #[test]
+    fn test_area()
+    {
+        let grid = CellGrid::model::<WGS84>();
+        let total: f64 = (0..6).map(|face| grid.area(CellId::from_face(face).unwrap())).sum();
+        assert_approx_eq!(f64, total, grid.elps.surface_area(), epsilon = 1.0);
+        assert_approx_eq!(f64, grid.area(CellId::from_face(0).unwrap()), grid.elps.surface_area() / 6.0, epsilon = 1.0);
+
+        let cell = grid.cell(LatLon::new(48.85, 2.35), 10).unwrap();
+        let sum: f64 = cell.children().unwrap().iter().map(|&c| grid.area(c)).sum();
+        assert_approx_eq!(f64, sum, grid.area(cell), epsilon = 1e-3);
+        assert!((grid.area(cell) / 8.1e7 - 1.0).abs() < 0.5);
+
+        // The cells of the same position on the sphere have the area in proportion to the surface.
+        let sphere = CellGrid::model::<Sphere>();
+        let ratio = grid.elps.surface_area() / sphere.elps.surface_area();
+        assert_approx_eq!(f64, grid.area(cell) / sphere.area(cell), ratio, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_neighbours()
+    {
+        let grid = CellGrid::model::<WGS84>();
+        let faces = CellId::from_face(0).unwrap().edge_neighbours().map(|c| c.face());
+        assert_eq!(faces, [5, 1, 2, 4]);
+
+        let cells = [
+            grid.cell(LatLon::new(48.85, 2.35), 10).unwrap(),
+            CellId::from_face_ij(0, LEAF_CELLS - 1, 0).parent_at(8).unwrap(),
+            CellId::from_face_ij(2, 0, LEAF_CELLS / 2).parent_at(4).unwrap(),
+            CellId::from_face_ij(4, LEAF_CELLS - 1, LEAF_CELLS - 1).parent_at(1).unwrap(),
+        ];
+        for cell in cells
+        {
+            for n in cell.edge_neighbours()
+            {
+                assert_eq!(n.level(), cell.level());
+                assert_ne!(n, cell);
+                assert!(n.edge_neighbours().contains(&cell));
+            }
+        }
+    }
+
+    #[test]
+    fn test_cover_cap()
+    {
+        let grid = CellGrid::model::<WGS84>();
+        let center = LatLon::new(48.85, 2.35);
+        let cells = grid.cover_cap(center, 5000.0, 12, 100).unwrap();
+        assert!(cells.contains(&grid.cell(center, 12).unwrap()));
+        assert!(cells.iter().all(|c| c.level() == 12));
+        assert!(cells.len() > 4 && cells.len() < 40);
+        for (lat, lon) in [(48.88, 2.35), (48.85, 2.40), (48.82, 2.31)]
+        {
+            assert!(cells.contains(&grid.cell(LatLon::new(lat, lon), 12).unwrap()));
+        }
+        assert!(!cells.contains(&grid.cell(LatLon::new(48.95, 2.35), 12).unwrap()));
+
+        let area: f64 = cells.iter().map(|&c| grid.area(c)).sum();
+        assert!(area > PI * 5000.0 * 5000.0);
+
+        // The cap around the pole covers all the 4 faces around the equator.
+        let cells = grid.cover_cap(LatLon::new(90.0, 0.0), 6000e3, 0, 6).unwrap();
+        assert_eq!(cells.iter().map(|c| c.face()).collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+
+        // The covering stops as soon as the limit is exceeded.
+        assert_eq!(grid.cover_cap(center, 5000.0, 12, 4), Err(CellError::TooManyCells(4)));
+        assert_eq!(grid.cover_cap(center, 6000e3, 30, 1000), Err(CellError::TooManyCells(1000)));
+    }
+
+    #[test]
+    fn test_cover_polygon()
+    {
+        let grid = CellGrid::model::<WGS84>();
+        let square = |lat: f64, lon: f64, d: f64| vec![
+            LatLon::new(lat - d, lon - d), LatLon::new(lat - d, lon + d), LatLon::new(lat + d, lon + d), LatLon::new(lat + d, lon - d),
+        ];
+        let polygon = Polygon::new(square(10.0, 20.0, 1.0), vec![square(10.0, 20.0, 0.5)]);
+        let cells = grid.cover_polygon(&polygon, 8, 1000).unwrap();
+        let covered = |lat: f64, lon: f64| cells.contains(&grid.cell(LatLon::new(lat, lon), 8).unwrap());
+        assert!(covered(9.2, 19.2) && covered(10.8, 20.0) && covered(10.0, 19.1));
+        assert!(!covered(10.0, 20.0) && !covered(12.0, 20.0) && !covered(10.0, 17.0));
+
+        // The same cells are covered in the reverse winding, and the polygon inside a cell covers it.
+        let reversed = Polygon::new(square(10.0, 20.0, 1.0).into_iter().rev().collect(), vec![square(10.0, 20.0, 0.5)]);
+        assert_eq!(grid.cover_polygon(&reversed, 8, 1000).unwrap(), cells);
+        let tiny = Polygon::new(square(10.0, 20.0, 1e-4), vec![]);
+        assert_eq!(grid.cover_polygon(&tiny, 5, 1).unwrap(), vec![grid.cell(LatLon::new(10.0, 20.0), 5).unwrap()]);
+    }
+
+    #[test]
+    fn test_cover_polygon_large()
+    {
+        // The cells of a large polygon are all on its face, none around the antipode on face 3.
+        let grid = CellGrid::model::<WGS84>();
+        let square = vec![LatLon::new(-30.0, -30.0), LatLon::new(-30.0, 30.0), LatLon::new(30.0, 30.0), LatLon::new(30.0, -30.0)];
+        for ring in [square.clone(), square.into_iter().rev().collect()]
+        {
+            let polygon = Polygon::new(ring, vec![]);
+            assert_eq!(grid.cover_polygon(&polygon, 0, 6).unwrap(), vec![CellId::from_face(0).unwrap()]);
+            let cells = grid.cover_polygon(&polygon, 3, 64).unwrap();
+            assert!(cells.iter().all(|c| c.face() == 0));
+            assert!(cells.contains(&grid.cell(LatLon::new(0.0, 0.0), 3).unwrap()));
+            assert!(!cells.contains(&grid.cell(LatLon::new(0.0, 180.0), 3).unwrap()));
+        }
+
+        // A polygon larger than a hemisphere is taken as its complement.
+        let band = vec![LatLon::new(-10.0, 0.0), LatLon::new(-10.0, 120.0), LatLon::new(-10.0, -120.0)];
+        let cells = grid.cover_polygon(&Polygon::new(band, vec![]), 1, 24).unwrap();
+        assert!(cells.contains(&grid.cell(LatLon::new(-80.0, 0.0), 1).unwrap()));
+        assert!(!cells.contains(&grid.cell(LatLon::new(80.0, 0.0), 1).unwrap()));
+    }
+}
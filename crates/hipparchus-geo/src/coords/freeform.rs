@@ -0,0 +1,458 @@
+use crate::coords::coord::Coord;
+use crate::coords::latlon::LatLon;
+use crate::coords::orientation::Orientation;
+use hipparchus_az::{DegreeMinuteSecond, Sign};
+
+/// The parts of an angle in degrees, minutes and seconds.
+#[repr(u8)]
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone)]
+pub enum DmsPart
+{
+    Degree = 0,
+    Minute = 1,
+    Second = 2,
+}
+
+impl DmsPart
+{
+    fn with(index: usize) -> Option<Self>
+    {
+        match index
+        {
+            0 => Some(Self::Degree),
+            1 => Some(Self::Minute),
+            2 => Some(Self::Second),
+            _ => None,
+        }
+    }
+}
+
+/// Errors of parsing a free-form angle.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DmsParseError
+{
+    /// There is no number in the text.
+    Empty,
+
+    /// The character is not a number, a sign, a hemisphere letter or a unit symbol.
+    UnexpectedCharacter(char),
+
+    /// The part is not a valid number.
+    InvalidNumber { part: DmsPart, text: String },
+
+    /// There are more than the degrees, minutes and seconds.
+    TooManyParts,
+
+    /// The minutes or seconds are not less than 60.
+    PartOutOfRange { part: DmsPart, value: f64 },
+
+    /// The sign prefix and the hemisphere letter disagree, or either of them is repeated.
+    ConflictingSign,
+
+    /// The hemisphere letter does not belong to the coordinate, e.g. 'E' for the latitude.
+    WrongHemisphere(Orientation),
+
+    /// The angle is beyond 90° for the latitude or 180° for the longitude.
+    OutOfRange(f64),
+}
+
+impl std::fmt::Display for DmsParseError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self
+        {
+            DmsParseError::Empty => write!(f, "no angle is found"),
+            DmsParseError::UnexpectedCharacter(c) => write!(f, "unexpected character '{c}'"),
+            DmsParseError::InvalidNumber { part, text } => write!(f, "invalid {part:?} '{text}'"),
+            DmsParseError::TooManyParts => write!(f, "too many parts of the angle"),
+            DmsParseError::PartOutOfRange { part, value } => write!(f, "{part:?} {value} is not less than 60"),
+            DmsParseError::ConflictingSign => write!(f, "sign and hemisphere are conflicting"),
+            DmsParseError::WrongHemisphere(o) => write!(f, "hemisphere '{o}' does not match the coordinate"),
+            DmsParseError::OutOfRange(value) => write!(f, "angle {value} is out of range"),
+        }
+    }
+}
+
+impl std::error::Error for DmsParseError {}
+
+/// Errors of parsing a free-form latitude/longitude pair.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LatLonParseError
+{
+    /// The text is empty.
+    Empty,
+
+    /// The character is not a number, a sign, a hemisphere letter, a unit symbol or a separator.
+    UnexpectedCharacter(char),
+
+    /// The latitude cannot be parsed.
+    Latitude(DmsParseError),
+
+    /// The longitude cannot be parsed.
+    Longitude(DmsParseError),
+
+    /// Only one angle is found.
+    MissingLongitude,
+
+    /// More than two angles are found.
+    TooManyAngles(usize),
+}
+
+impl std::fmt::Display for LatLonParseError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self
+        {
+            LatLonParseError::Empty => write!(f, "text is empty"),
+            LatLonParseError::UnexpectedCharacter(c) => write!(f, "unexpected character '{c}'"),
+            LatLonParseError::Latitude(e) => write!(f, "latitude: {e}"),
+            LatLonParseError::Longitude(e) => write!(f, "longitude: {e}"),
+            LatLonParseError::MissingLongitude => write!(f, "longitude is missing"),
+            LatLonParseError::TooManyAngles(n) => write!(f, "{n} angles are found instead of 2"),
+        }
+    }
+}
+
+impl std::error::Error for LatLonParseError {}
+
+/// Parse the angles written by humans, such as `40°26'46"N`, `N 40 26.767` or `-79,982`.
+///
+/// The parts of an angle are the numbers with the optional unit symbols (° º ˚, ' ′ ’, " ″ ” ''),
+/// the numbers without symbols are taken as the degrees, minutes and seconds in order, and a
+/// fraction with a decimal point or a decimal comma is only allowed in the last part. The sign
+/// is given by a `Sign` prefix or a hemisphere letter of `Orientation` before or after the
+/// numbers.
+pub trait FreeForm: Sized
+{
+    /// Parse the angle of the coordinate.
+    fn parse_free(text: &str, coord: Coord) -> Result<Self, DmsParseError>;
+}
+
+impl FreeForm for DegreeMinuteSecond
+{
+    fn parse_free(text: &str, coord: Coord) -> Result<Self, DmsParseError>
+    {
+        let tokens = tokenize(text).map_err(DmsParseError::UnexpectedCharacter)?;
+        let mut angles = group(tokens).map_err(DmsParseError::UnexpectedCharacter)?;
+        match angles.len()
+        {
+            0 => Err(DmsParseError::Empty),
+            1 => Ok(DegreeMinuteSecond::with(angles.remove(0).value(Some(coord))?.0)),
+            _ => Err(DmsParseError::TooManyParts),
+        }
+    }
+}
+
+impl LatLon
+{
+    /// Parse the latitude/longitude written by humans, e.g. `40°26'46"N 79°58'56"W`, `N 40 26.767 W 79 58.933` or `40.446, -79.982`.
+    ///
+    /// The angles are separated by the whitespaces, ';', '/' or ',' which is not a decimal comma, and
+    /// a new angle starts at a sign or a hemisphere letter after the numbers. The longitude may come
+    /// first if the hemisphere letters tell so. See [`FreeForm`] for the format of the angles.
+    pub fn parse_free(text: &str) -> Result<Self, LatLonParseError>
+    {
+        let tokens = tokenize(text).map_err(LatLonParseError::UnexpectedCharacter)?;
+        if tokens.is_empty()
+        {
+            return Err(LatLonParseError::Empty);
+        }
+        let angles = group(tokens).map_err(LatLonParseError::UnexpectedCharacter)?;
+        match angles.len()
+        {
+            0 => Err(LatLonParseError::Empty),
+            1 => Err(LatLonParseError::MissingLongitude),
+            2 =>
+            {
+                let (mut first, mut second) = (&angles[0], &angles[1]);
+                if first.coord() == Some(Coord::Longitude) || second.coord() == Some(Coord::Latitude) && first.coord().is_none()
+                {
+                    (first, second) = (second, first);
+                }
+                let (lat, _) = first.value(Some(Coord::Latitude)).map_err(LatLonParseError::Latitude)?;
+                let (lon, _) = second.value(Some(Coord::Longitude)).map_err(LatLonParseError::Longitude)?;
+                Ok(LatLon::new(lat, lon))
+            },
+            n => Err(LatLonParseError::TooManyAngles(n)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token
+{
+    Number(String),
+    Unit(DmsPart, char),
+    Sign(Sign),
+    Hemisphere(Orientation),
+    Separator,
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, char>
+{
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next()
+    {
+        let token = match c
+        {
+            '0'..='9' | '.' =>
+            {
+                let mut number = c.to_string();
+                while let Some(&next) = chars.peek()
+                {
+                    let decimal_comma = next == ',' && !number.contains(['.', ','])
+                        && chars.clone().nth(1).is_some_and(|c| c.is_ascii_digit());
+                    if !(next.is_ascii_digit() || next == '.' || decimal_comma)
+                    {
+                        break;
+                    }
+                    number.push(next);
+                    chars.next();
+                }
+                Token::Number(number)
+            },
+            '+' => Token::Sign(Sign::Positive),
+            '-' | '\u{2212}' => Token::Sign(Sign::Negative),
+            'N' | 'n' => Token::Hemisphere(Orientation::North),
+            'S' | 's' => Token::Hemisphere(Orientation::South),
+            'E' | 'e' => Token::Hemisphere(Orientation::East),
+            'W' | 'w' => Token::Hemisphere(Orientation::West),
+            '°' | 'º' | '˚' => Token::Unit(DmsPart::Degree, c),
+            '\'' | '′' | '’' if chars.peek() == Some(&c) =>
+            {
+                chars.next();
+                Token::Unit(DmsPart::Second, '"')
+            },
+            '\'' | '′' | '’' => Token::Unit(DmsPart::Minute, c),
+            '"' | '″' | '”' => Token::Unit(DmsPart::Second, c),
+            ',' | ';' | '/' => Token::Separator,
+            c if c.is_whitespace() => continue,
+            c => return Err(c),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// An angle collected from the tokens.
+#[derive(Debug, Default)]
+struct Angle
+{
+    sign: Option<Sign>,
+    hemisphere: Option<Orientation>,
+    repeated: bool,
+
+    // the numbers with the index of their parts, explicit if followed by a unit symbol;
+    // the indices always increase as a smaller index or a fraction starts a new angle
+    parts: Vec<(usize, String, bool)>,
+
+    // closed by a hemisphere suffix
+    closed: bool,
+}
+
+impl Angle
+{
+    fn is_empty(&self) -> bool
+    {
+        self.sign.is_none() && self.hemisphere.is_none() && self.parts.is_empty()
+    }
+
+    fn coord(&self) -> Option<Coord>
+    {
+        self.hemisphere.map(|h| h.coord())
+    }
+
+    /// Check if the number starts a new angle after the parts of this one.
+    fn is_full(&self) -> bool
+    {
+        self.closed || self.parts.last().is_some_and(|(i, text, _)| *i >= 2 || text.contains(['.', ',']))
+    }
+
+    fn value(&self, coord: Option<Coord>) -> Result<(f64, Option<Coord>), DmsParseError>
+    {
+        if self.parts.is_empty()
+        {
+            return Err(DmsParseError::Empty);
+        }
+        if self.repeated
+        {
+            return Err(DmsParseError::ConflictingSign);
+        }
+        let mut value = 0.0;
+        for (i, text, _) in &self.parts
+        {
+            let part = DmsPart::with(*i).ok_or(DmsParseError::TooManyParts)?;
+            let v: f64 = text.replace(',', ".").parse().map_err(|_| DmsParseError::InvalidNumber { part, text: text.clone() })?;
+            if part != DmsPart::Degree && v >= 60.0
+            {
+                return Err(DmsParseError::PartOutOfRange { part, value: v });
+            }
+            value += v / 60f64.powi(*i as i32);
+        }
+
+        let sign = match (self.sign, self.hemisphere)
+        {
+            (Some(s), Some(h)) if s != h.sign() => return Err(DmsParseError::ConflictingSign),
+            (_, Some(h)) => h.sign(),
+            (Some(s), None) => s,
+            (None, None) => Sign::Positive,
+        };
+        let coord = match (coord, self.coord())
+        {
+            (Some(c), Some(h)) if c != h => return Err(DmsParseError::WrongHemisphere(self.hemisphere.unwrap())),
+            (c, h) => c.or(h),
+        };
+        let max = coord.map_or(360.0, |c| c.max() as f64);
+        if value > max
+        {
+            return Err(DmsParseError::OutOfRange(if sign == Sign::Negative { -value } else { value }));
+        }
+        Ok((if sign == Sign::Negative { -value } else { value }, coord))
+    }
+}
+
+/// Group the tokens into the angles, or get the unit symbol which does not follow a number.
+fn group(tokens: Vec<Token>) -> Result<Vec<Angle>, char>
+{
+    let mut angles = Vec::new();
+    let mut current = Angle::default();
+    fn next(current: &mut Angle, angles: &mut Vec<Angle>)
+    {
+        let angle = std::mem::take(current);
+        if !angle.is_empty()
+        {
+            angles.push(angle);
+        }
+    }
+
+    for token in tokens
+    {
+        match token
+        {
+            Token::Separator => next(&mut current, &mut angles),
+            Token::Sign(s) =>
+            {
+                if !current.parts.is_empty() || current.closed
+                {
+                    next(&mut current, &mut angles);
+                }
+                current.repeated |= current.sign.is_some();
+                current.sign = Some(s);
+            },
+            Token::Hemisphere(h) =>
+            {
+                if current.hemisphere.is_some() && !current.parts.is_empty() || current.closed
+                {
+                    next(&mut current, &mut angles);
+                }
+                current.repeated |= current.hemisphere.is_some();
+                current.closed = !current.parts.is_empty();
+                current.hemisphere = Some(h);
+            },
+            Token::Number(text) =>
+            {
+                if current.is_full()
+                {
+                    next(&mut current, &mut angles);
+                }
+                let index = current.parts.last().map_or(0, |(i, _, _)| i + 1);
+                current.parts.push((index, text, false));
+            },
+            Token::Unit(part, symbol) =>
+            {
+                let Some((_, text, false)) = current.parts.pop()
+                else
+                {
+                    return Err(symbol);
+                };
+                if current.parts.last().is_some_and(|(i, _, _)| *i >= part as usize)
+                {
+                    // The unit goes back to a larger part, which starts a new angle, e.g. 79° after 26'.
+                    next(&mut current, &mut angles);
+                }
+                current.parts.push((part as usize, text, true));
+            },
+        }
+    }
+    next(&mut current, &mut angles);
+    Ok(angles)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use rstest::*;
+    use float_cmp::assert_approx_eq;
+
+    #[rstest]
+    #[case("40°26'46\"N", Coord::Latitude, 40.446111111111)]
+    #[case("40° 26′ 46″ S", Coord::Latitude, -40.446111111111)]
+    #[case("N 40 26.767", Coord::Latitude, 40.446116666667)]
+    #[case("-79,982", Coord::Longitude, -79.982)]
+    #[case("w79º58'56''", Coord::Longitude, -79.982222222222)]
+    #[case("+ 120 30", Coord::Longitude, 120.5)]
+    #[case("30'", Coord::Latitude, 0.5)]
+    #[case("−0.5", Coord::Latitude, -0.5)]
+    fn test_parse_dms(#[case] text: &str, #[case] coord: Coord, #[case] expected: f64)
+    {
+        let dms = DegreeMinuteSecond::parse_free(text, coord).unwrap();
+        assert_approx_eq!(f64, dms.value(), expected, epsilon = 1e-6);
+    }
+
+    #[rstest]
+    #[case("", DmsParseError::Empty)]
+    #[case("N", DmsParseError::Empty)]
+    #[case("40°26'46\"X", DmsParseError::UnexpectedCharacter('X'))]
+    #[case("40.5.1", DmsParseError::InvalidNumber { part: DmsPart::Degree, text: "40.5.1".to_string() })]
+    #[case("40 26 46 10", DmsParseError::TooManyParts)]
+    #[case("26' 40°", DmsParseError::TooManyParts)]
+    #[case("40 61", DmsParseError::PartOutOfRange { part: DmsPart::Minute, value: 61.0 })]
+    #[case("40 26 60", DmsParseError::PartOutOfRange { part: DmsPart::Second, value: 60.0 })]
+    #[case("-40 N", DmsParseError::ConflictingSign)]
+    #[case("N N 40", DmsParseError::ConflictingSign)]
+    #[case("40 E", DmsParseError::WrongHemisphere(Orientation::East))]
+    #[case("91", DmsParseError::OutOfRange(91.0))]
+    #[case("°", DmsParseError::UnexpectedCharacter('°'))]
+    fn test_parse_dms_error(#[case] text: &str, #[case] expected: DmsParseError)
+    {
+        assert_eq!(DegreeMinuteSecond::parse_free(text, Coord::Latitude).err(), Some(expected));
+    }
+
+    #[rstest]
+    #[case("40°26'46\"N 79°58'56\"W", 40.446111111111, -79.982222222222)]
+    #[case("N 40 26.767 W 79 58.933", 40.446116666667, -79.982216666667)]
+    #[case("40.446, -79.982", 40.446, -79.982)]
+    #[case("40,446; -79,982", 40.446, -79.982)]
+    #[case("40.446 -79.982", 40.446, -79.982)]
+    #[case("40.446,-79.982", 40.446, -79.982)]
+    #[case("40 26 46 79 58 56", 40.446111111111, 79.982222222222)]
+    #[case("40°26' 79°58'", 40.433333333333, 79.966666666667)]
+    #[case("79°58'56\"W 40°26'46\"N", 40.446111111111, -79.982222222222)]
+    #[case("S33.9/E151.2", -33.9, 151.2)]
+    #[case("40.446 N, 79.982 W", 40.446, -79.982)]
+    fn test_parse_latlon(#[case] text: &str, #[case] lat: f64, #[case] lon: f64)
+    {
+        let p = LatLon::parse_free(text).unwrap();
+        assert_approx_eq!(f64, p.latitude(), lat, epsilon = 1e-9);
+        assert_approx_eq!(f64, p.longitude(), lon, epsilon = 1e-9);
+    }
+
+    #[rstest]
+    #[case("  ", LatLonParseError::Empty)]
+    #[case("40.446 # -79.982", LatLonParseError::UnexpectedCharacter('#'))]
+    #[case("40.446", LatLonParseError::MissingLongitude)]
+    #[case("1, 2, 3", LatLonParseError::TooManyAngles(3))]
+    #[case("40 61, -79.982", LatLonParseError::Latitude(DmsParseError::PartOutOfRange { part: DmsPart::Minute, value: 61.0 }))]
+    #[case("40.446, 79 58 60", LatLonParseError::Longitude(DmsParseError::PartOutOfRange { part: DmsPart::Second, value: 60.0 }))]
+    #[case("40.446, 181", LatLonParseError::Longitude(DmsParseError::OutOfRange(181.0)))]
+    #[case("N 40 N 79", LatLonParseError::Longitude(DmsParseError::WrongHemisphere(Orientation::North)))]
+    #[case("40.5 30', 79", LatLonParseError::TooManyAngles(3))]
+    fn test_parse_latlon_error(#[case] text: &str, #[case] expected: LatLonParseError)
+    {
+        assert_eq!(LatLon::parse_free(text), Err(expected));
+    }
+}
@@ -0,0 +1,221 @@
+use std::f64::consts::PI;
+use crate::coords::latlon::LatLon;
+
+/// The semi-major axis of Krassovsky (1940) used by GCJ-02 (meters).
+const GCJ_A: f64 = 6378245.0;
+
+/// The square of the eccentricity of Krassovsky (1940) used by GCJ-02.
+const GCJ_EE: f64 = 0.006693421622965943;
+
+/// The scale of the longitude/latitude in the angle of the BD-09 rotation.
+const BD_X_PI: f64 = PI * 3000.0 / 180.0;
+
+/// The tolerance of the iterative inverse transforms (degrees), about 0.01 mm on the ground.
+const INVERSE_TOLERANCE: f64 = 1e-10;
+
+/// The maximum number of iterations of the inverse transforms.
+const INVERSE_ITERATIONS: usize = 30;
+
+/// Offset conversions among WGS-84, GCJ-02 (the "Mars" coordinates) and BD-09 (Baidu) in mainland China.
+///
+/// The forward transforms follow the public reverse-engineered algorithms, and the points out of
+/// the bounding box of China are kept unchanged as the offset is only applied inside China. The
+/// inverse transforms are solved by iteration so that the forward transform of the result
+/// reproduces the input within 1e-10° in both latitude and longitude. They fail near the edges of
+/// the bounding box, where the offset jumps and some points have no inverse.
+impl LatLon
+{
+    /// The minimum latitude of the bounding box of China (degrees).
+    pub const CHINA_MIN_LATITUDE: f64 = 0.8293;
+
+    /// The maximum latitude of the bounding box of China (degrees).
+    pub const CHINA_MAX_LATITUDE: f64 = 55.8271;
+
+    /// The minimum longitude of the bounding box of China (degrees).
+    pub const CHINA_MIN_LONGITUDE: f64 = 72.004;
+
+    /// The maximum longitude of the bounding box of China (degrees).
+    pub const CHINA_MAX_LONGITUDE: f64 = 137.8347;
+
+    /// Check if the point is out of the bounding box of China, where no offset is applied.
+    pub fn is_out_of_china(&self) -> bool
+    {
+        !(Self::CHINA_MIN_LATITUDE..=Self::CHINA_MAX_LATITUDE).contains(&self.latitude())
+            || !(Self::CHINA_MIN_LONGITUDE..=Self::CHINA_MAX_LONGITUDE).contains(&self.longitude())
+    }
+
+    /// Convert the point from WGS-84 to GCJ-02.
+    pub fn wgs84_to_gcj02(&self) -> LatLon
+    {
+        if self.is_out_of_china()
+        {
+            return *self;
+        }
+        let (lat, lon) = (self.latitude(), self.longitude());
+        let (x, y) = (lon - 105.0, lat - 35.0);
+        let (slat, clat) = lat.to_radians().sin_cos();
+        let magic = 1.0 - GCJ_EE * slat * slat;
+        let sqrt_magic = magic.sqrt();
+        let dlat = gcj_offset_lat(x, y) * 180.0 / (GCJ_A * (1.0 - GCJ_EE) / (magic * sqrt_magic) * PI);
+        let dlon = gcj_offset_lon(x, y) * 180.0 / (GCJ_A / sqrt_magic * clat * PI);
+        LatLon::new(lat + dlat, lon + dlon)
+    }
+
+    /// Convert the point from GCJ-02 to WGS-84 by iteration, or `None` if the iteration does not converge.
+    pub fn gcj02_to_wgs84(&self) -> Option<LatLon>
+    {
+        if self.is_out_of_china()
+        {
+            return Some(*self);
+        }
+        invert(*self, LatLon::wgs84_to_gcj02)
+    }
+
+    /// Convert the point from GCJ-02 to BD-09.
+    pub fn gcj02_to_bd09(&self) -> LatLon
+    {
+        let (x, y) = (self.longitude(), self.latitude());
+        let z = x.hypot(y) + 0.00002 * (y * BD_X_PI).sin();
+        let theta = y.atan2(x) + 0.000003 * (x * BD_X_PI).cos();
+        LatLon::new(z * theta.sin() + 0.006, z * theta.cos() + 0.0065)
+    }
+
+    /// Convert the point from BD-09 to GCJ-02 by iteration, or `None` if the iteration does not converge.
+    pub fn bd09_to_gcj02(&self) -> Option<LatLon>
+    {
+        invert(*self, LatLon::gcj02_to_bd09)
+    }
+
+    /// Convert the point from WGS-84 to BD-09.
+    pub fn wgs84_to_bd09(&self) -> LatLon
+    {
+        self.wgs84_to_gcj02().gcj02_to_bd09()
+    }
+
+    /// Convert the point from BD-09 to WGS-84 by iteration, or `None` if the iteration does not converge.
+    pub fn bd09_to_wgs84(&self) -> Option<LatLon>
+    {
+        self.bd09_to_gcj02()?.gcj02_to_wgs84()
+    }
+}
+
+/// Solve `forward(p) == target` by the fixed-point iteration, as the forward offsets change slowly with the position.
+fn invert(target: LatLon, forward: fn(&LatLon) -> LatLon) -> Option<LatLon>
+{
+    let mut p = target;
+    for _ in 0..INVERSE_ITERATIONS
+    {
+        let q = forward(&p);
+        let (dlat, dlon) = (q.latitude() - target.latitude(), q.longitude() - target.longitude());
+        if dlat.abs() < INVERSE_TOLERANCE && dlon.abs() < INVERSE_TOLERANCE
+        {
+            return Some(p);
+        }
+        p = LatLon::new(p.latitude() - dlat, p.longitude() - dlon);
+    }
+    None
+}
+
+fn gcj_offset_lat(x: f64, y: f64) -> f64
+{
+    let mut v = -100.0 + 2.0 * x + 3.0 * y + 0.2 * y * y + 0.1 * x * y + 0.2 * x.abs().sqrt();
+    v += (20.0 * (6.0 * x * PI).sin() + 20.0 * (2.0 * x * PI).sin()) * 2.0 / 3.0;
+    v += (20.0 * (y * PI).sin() + 40.0 * (y / 3.0 * PI).sin()) * 2.0 / 3.0;
+    v += (160.0 * (y / 12.0 * PI).sin() + 320.0 * (y * PI / 30.0).sin()) * 2.0 / 3.0;
+    v
+}
+
+fn gcj_offset_lon(x: f64, y: f64) -> f64
+{
+    let mut v = 300.0 + x + 2.0 * y + 0.1 * x * x + 0.1 * x * y + 0.1 * x.abs().sqrt();
+    v += (20.0 * (6.0 * x * PI).sin() + 20.0 * (2.0 * x * PI).sin()) * 2.0 / 3.0;
+    v += (20.0 * (x * PI).sin() + 40.0 * (x / 3.0 * PI).sin()) * 2.0 / 3.0;
+    v += (150.0 * (x / 12.0 * PI).sin() + 300.0 * (x / 30.0 * PI).sin()) * 2.0 / 3.0;
+    v
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use rstest::*;
+    use float_cmp::assert_approx_eq;
+
+    #[rstest]
+    #[case(39.908823, 116.397470, 39.9102264981, 116.4037135824, 39.9165658186, 116.4100864544)]
+    #[case(31.2304, 121.4737, 31.2284577376, 121.4782230593, 31.2343105937, 121.4847814685)]
+    #[case(22.5431, 114.0579, 22.5403828142, 114.0630139986, 22.5460415591, 114.0695639870)]
+    fn test_gcj02_bd09(#[case] lat: f64, #[case] lon: f64, #[case] glat: f64, #[case] glon: f64, #[case] blat: f64, #[case] blon: f64)
+    {
+        let wgs = LatLon::new(lat, lon);
+        let gcj = wgs.wgs84_to_gcj02();
+        assert_approx_eq!(f64, gcj.latitude(), glat, epsilon = 1e-10);
+        assert_approx_eq!(f64, gcj.longitude(), glon, epsilon = 1e-10);
+        let bd = gcj.gcj02_to_bd09();
+        assert_approx_eq!(f64, bd.latitude(), blat, epsilon = 1e-10);
+        assert_approx_eq!(f64, bd.longitude(), blon, epsilon = 1e-10);
+        assert_eq!(wgs.wgs84_to_bd09(), bd);
+
+        let back = gcj.gcj02_to_wgs84().unwrap();
+        assert_approx_eq!(f64, back.latitude(), lat, epsilon = 1e-10);
+        assert_approx_eq!(f64, back.longitude(), lon, epsilon = 1e-10);
+        let back = bd.bd09_to_gcj02().unwrap();
+        assert_approx_eq!(f64, back.latitude(), glat, epsilon = 1e-10);
+        assert_approx_eq!(f64, back.longitude(), glon, epsilon = 1e-10);
+        let back = bd.bd09_to_wgs84().unwrap();
+        assert_approx_eq!(f64, back.latitude(), lat, epsilon = 1e-9);
+        assert_approx_eq!(f64, back.longitude(), lon, epsilon = 1e-9);
+    }
+
+    #[rstest]
+    #[case(39.9, 116.4, false)]
+    #[case(0.8, 100.0, true)]
+    #[case(56.0, 100.0, true)]
+    #[case(30.0, 72.0, true)]
+    #[case(30.0, 138.0, true)]
+    #[case(51.5, -0.1, true)]
+    #[case(-33.9, 151.2, true)]
+    fn test_out_of_china(#[case] lat: f64, #[case] lon: f64, #[case] out: bool)
+    {
+        let p = LatLon::new(lat, lon);
+        assert_eq!(p.is_out_of_china(), out);
+        if out
+        {
+            assert_eq!(p.wgs84_to_gcj02(), p);
+            assert_eq!(p.gcj02_to_wgs84(), Some(p));
+        }
+    }
+
+    #[test]
+    fn test_inverse_accuracy()
+    {
+        // The forward transforms of the inverse results reproduce the grid of inputs over China.
+        for i in 0..=20
+        {
+            for j in 0..=20
+            {
+                let p = LatLon::new(18.0 + 2.0 * i as f64, 74.0 + 3.0 * j as f64);
+                let q = p.gcj02_to_wgs84().unwrap().wgs84_to_gcj02();
+                assert_approx_eq!(f64, q.latitude(), p.latitude(), epsilon = INVERSE_TOLERANCE);
+                assert_approx_eq!(f64, q.longitude(), p.longitude(), epsilon = INVERSE_TOLERANCE);
+                let q = p.bd09_to_gcj02().unwrap().gcj02_to_bd09();
+                assert_approx_eq!(f64, q.latitude(), p.latitude(), epsilon = INVERSE_TOLERANCE);
+                assert_approx_eq!(f64, q.longitude(), p.longitude(), epsilon = INVERSE_TOLERANCE);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inverse_edge()
+    {
+        // Just inside the bounding box, the points have no pre-image as the offset moves the
+        // points nearby out of China, where no offset is applied.
+        assert_eq!(LatLon::new(30.0, LatLon::CHINA_MIN_LONGITUDE + 0.001).gcj02_to_wgs84(), None);
+        assert_eq!(LatLon::new(LatLon::CHINA_MIN_LATITUDE, 100.0).gcj02_to_wgs84(), None);
+
+        let p = LatLon::new(30.0, LatLon::CHINA_MIN_LONGITUDE + 0.01);
+        let q = p.gcj02_to_wgs84().unwrap().wgs84_to_gcj02();
+        assert_approx_eq!(f64, q.latitude(), p.latitude(), epsilon = INVERSE_TOLERANCE);
+        assert_approx_eq!(f64, q.longitude(), p.longitude(), epsilon = INVERSE_TOLERANCE);
+    }
+}
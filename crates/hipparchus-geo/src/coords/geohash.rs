@@ -0,0 +1,311 @@
+use crate::coords::bbox::BoundingBox;
+use crate::coords::latlon::LatLon;
+
+/// The base32 alphabet of geohash without 'a', 'i', 'l' and 'o'.
+const BASE32: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// Errors of the geohash conversions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeohashError
+{
+    /// The precision is not in [1, 12].
+    InvalidPrecision(usize),
+
+    /// The character is not in the geohash alphabet.
+    InvalidCharacter(char),
+
+    /// The covering has more cells than the limit.
+    TooManyCells(usize),
+}
+
+impl std::fmt::Display for GeohashError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self
+        {
+            GeohashError::InvalidPrecision(precision) => write!(f, "geohash precision {precision} is not in [1, 12]"),
+            GeohashError::InvalidCharacter(c) => write!(f, "invalid geohash character '{c}'"),
+            GeohashError::TooManyCells(max) => write!(f, "covering has more than {max} cells"),
+        }
+    }
+}
+
+impl std::error::Error for GeohashError {}
+
+/// A geohash cell, the interleaved bits of the longitude and latitude in base32.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Geohash
+{
+    hash: String,
+}
+
+impl Geohash
+{
+    /// The maximum precision in characters, about 19 mm by 37 mm.
+    pub const MAX_PRECISION: usize = 12;
+
+    /// Encode the point with the precision in characters.
+    pub fn new(p: LatLon, precision: usize) -> Result<Self, GeohashError>
+    {
+        Self::check_precision(precision)?;
+        let (mut lat, mut lon) = ((-90.0, 90.0), (-180.0, 180.0));
+        let mut hash = String::with_capacity(precision);
+        let mut even = true;
+        for _ in 0..precision
+        {
+            let mut index = 0;
+            for _ in 0..5
+            {
+                let (range, value) = if even { (&mut lon, p.longitude()) } else { (&mut lat, p.latitude()) };
+                let mid = (range.0 + range.1) / 2.0;
+                index <<= 1;
+                if value >= mid
+                {
+                    index |= 1;
+                    range.0 = mid;
+                }
+                else
+                {
+                    range.1 = mid;
+                }
+                even = !even;
+            }
+            hash.push(BASE32[index] as char);
+        }
+        Ok(Self { hash })
+    }
+
+    /// Get the geohash text.
+    pub fn as_str(&self) -> &str
+    {
+        &self.hash
+    }
+
+    /// Get the precision in characters.
+    pub fn precision(&self) -> usize
+    {
+        self.hash.len()
+    }
+
+    /// Get the size of the cells of the precision, the height and width in degrees.
+    pub fn cell_size(precision: usize) -> (f64, f64)
+    {
+        let bits = 5 * precision as i32;
+        (180.0 / 2f64.powi(bits / 2), 360.0 / 2f64.powi((bits + 1) / 2))
+    }
+
+    /// Get the bounds of the cell.
+    pub fn bounds(&self) -> BoundingBox
+    {
+        let (mut lat, mut lon) = ((-90.0, 90.0), (-180.0, 180.0));
+        let mut even = true;
+        for c in self.hash.bytes()
+        {
+            let index = BASE32.iter().position(|&b| b == c).unwrap_or(0);
+            for shift in (0..5).rev()
+            {
+                let range = if even { &mut lon } else { &mut lat };
+                let mid = (range.0 + range.1) / 2.0;
+                if index >> shift & 1 == 1 { range.0 = mid } else { range.1 = mid }
+                even = !even;
+            }
+        }
+        BoundingBox::new(lat.0, lon.0, lat.1, lon.1)
+    }
+
+    /// Get the centre of the cell.
+    pub fn center(&self) -> LatLon
+    {
+        self.bounds().center()
+    }
+
+    /// Get the maximum errors of the centre, the half height and half width of the cell in degrees.
+    pub fn error(&self) -> (f64, f64)
+    {
+        let (height, width) = Self::cell_size(self.precision());
+        (height / 2.0, width / 2.0)
+    }
+
+    /// Get the cell containing this one with one less character, or `None` at the precision of 1.
+    pub fn parent(&self) -> Option<Geohash>
+    {
+        match self.precision()
+        {
+            1 => None,
+            n => Some(Self { hash: self.hash[..n - 1].to_string() }),
+        }
+    }
+
+    /// Get the neighbour cell with the offsets of the rows to the north and the columns to the east.
+    ///
+    /// The cells wrap around the antimeridian, while there is no cell beyond the poles.
+    pub fn neighbour(&self, north: i32, east: i32) -> Option<Geohash>
+    {
+        let (height, width) = Self::cell_size(self.precision());
+        let c = self.center();
+        let lat = c.latitude() + north as f64 * height;
+        if lat.abs() > 90.0
+        {
+            return None;
+        }
+        Self::new(LatLon::new(lat, c.longitude() + east as f64 * width), self.precision()).ok()
+    }
+
+    /// Get the 8 neighbours in the order of N, NE, E, SE, S, SW, W and NW.
+    pub fn neighbours(&self) -> [Option<Geohash>; 8]
+    {
+        [(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)].map(|(n, e)| self.neighbour(n, e))
+    }
+
+    /// Get the cells of the precision covering the bounding box, row by row from the south-west.
+    ///
+    /// Fails if there are more than `max_cells` cells.
+    pub fn cover(bbox: &BoundingBox, precision: usize, max_cells: usize) -> Result<Vec<Geohash>, GeohashError>
+    {
+        Self::check_precision(precision)?;
+        let (height, width) = Self::cell_size(precision);
+        let (rows, cols) = ((180.0 / height) as i64, (360.0 / width) as i64);
+        let row = |lat: f64| (((lat.clamp(-90.0, 90.0) + 90.0) / height).floor() as i64).min(rows - 1);
+        let col = |lon: f64| (((lon + 180.0) / width).floor() as i64).clamp(0, cols - 1);
+        let (west, mut east) = (col(bbox.west), col(bbox.east));
+        if bbox.crosses_antimeridian()
+        {
+            east += cols;
+        }
+        let (south, north) = (row(bbox.south), row(bbox.north));
+        let count = (north - south + 1).max(0) as u64 * (east - west + 1).max(0) as u64;
+        if count > max_cells as u64
+        {
+            return Err(GeohashError::TooManyCells(max_cells));
+        }
+        let mut cells = Vec::with_capacity(count as usize);
+        for i in south..=north
+        {
+            for j in west..=east
+            {
+                let lat = -90.0 + (i as f64 + 0.5) * height;
+                let lon = -180.0 + ((j % cols) as f64 + 0.5) * width;
+                cells.push(Self::new(LatLon::new(lat, lon), precision)?);
+            }
+        }
+        Ok(cells)
+    }
+
+    fn check_precision(precision: usize) -> Result<(), GeohashError>
+    {
+        if (1..=Self::MAX_PRECISION).contains(&precision) { Ok(()) } else { Err(GeohashError::InvalidPrecision(precision)) }
+    }
+}
+
+impl std::fmt::Display for Geohash
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        write!(f, "{}", self.hash)
+    }
+}
+
+impl std::str::FromStr for Geohash
+{
+    type Err = GeohashError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let hash = s.trim().to_ascii_lowercase();
+        if let Some(c) = hash.chars().find(|c| !c.is_ascii() || !BASE32.contains(&(*c as u8)))
+        {
+            return Err(GeohashError::InvalidCharacter(c));
+        }
+        Self::check_precision(hash.len())?;
+        Ok(Self { hash })
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use rstest::*;
+    use float_cmp::assert_approx_eq;
+
+    #[rstest]
+    #[case(57.64911, 10.40744, 11, "u4pruydqqvj")]
+    #[case(42.605, -5.603, 5, "ezs42")]
+    #[case(0.0, 0.0, 1, "s")]
+    #[case(-90.0, -180.0, 3, "000")]
+    #[case(90.0, 179.99, 3, "zzz")]
+    fn test_geohash(#[case] lat: f64, #[case] lon: f64, #[case] precision: usize, #[case] expected: &str)
+    {
+        let hash = Geohash::new(LatLon::new(lat, lon), precision).unwrap();
+        assert_eq!(hash.to_string(), expected);
+        assert_eq!(hash.precision(), precision);
+        assert!(hash.bounds().contains(LatLon::new(lat, lon)));
+        assert_eq!(expected.to_uppercase().parse::<Geohash>(), Ok(hash));
+    }
+
+    #[test]
+    fn test_decode()
+    {
+        let hash: Geohash = "ezs42".parse().unwrap();
+        let b = hash.bounds();
+        assert_eq!((b.south, b.west, b.north, b.east), (42.5830078125, -5.625, 42.626953125, -5.5810546875));
+        let c = hash.center();
+        assert_approx_eq!(f64, c.latitude(), 42.60498046875);
+        assert_approx_eq!(f64, c.longitude(), -5.60302734375);
+        assert_eq!(hash.error(), (0.02197265625, 0.02197265625));
+
+        let hash: Geohash = "u4pruydqqvj".parse().unwrap();
+        let (dlat, dlon) = hash.error();
+        assert!((hash.center().latitude() - 57.64911).abs() <= dlat);
+        assert!((hash.center().longitude() - 10.40744).abs() <= dlon);
+        assert_eq!(hash.parent().unwrap().as_str(), "u4pruydqqv");
+        assert_eq!(Geohash::new(LatLon::new(0.0, 0.0), 1).unwrap().parent(), None);
+    }
+
+    #[test]
+    fn test_neighbours()
+    {
+        let hash: Geohash = "gbsuv".parse().unwrap();
+        let expected = ["gbsvj", "gbsvn", "gbsuy", "gbsuw", "gbsut", "gbsus", "gbsuu", "gbsvh"];
+        assert_eq!(hash.neighbours().map(|h| h.unwrap().to_string()), expected);
+
+        // The neighbours wrap around the antimeridian, but not beyond the poles.
+        let hash = Geohash::new(LatLon::new(89.9, 179.9), 2).unwrap();
+        assert_eq!(hash.to_string(), "zz");
+        let n = hash.neighbours();
+        assert_eq!(n[0], None);
+        assert_eq!(n[2].as_ref().map(|h| h.as_str()), Some("bp"));
+        assert_eq!(n[4].as_ref().map(|h| h.as_str()), Some("zy"));
+    }
+
+    #[test]
+    fn test_cover()
+    {
+        let bbox = BoundingBox::new(42.59, -5.62, 42.62, -5.59);
+        let cells = Geohash::cover(&bbox, 5, 1).unwrap();
+        assert_eq!(cells.iter().map(|h| h.as_str()).collect::<Vec<_>>(), vec!["ezs42"]);
+        let cells = Geohash::cover(&bbox, 6, 24).unwrap();
+        assert_eq!(cells.len(), 24);
+        assert!(cells.iter().all(|h| h.as_str().starts_with("ezs42")));
+        assert_eq!(Geohash::cover(&bbox, 6, 23), Err(GeohashError::TooManyCells(23)));
+        let world = BoundingBox::new(-90.0, -180.0, 90.0, 180.0);
+        assert_eq!(Geohash::cover(&world, 12, 1 << 20), Err(GeohashError::TooManyCells(1 << 20)));
+
+        // The cells cross the antimeridian from the west to the east.
+        let bbox = BoundingBox::new(0.1, 179.0, 1.0, -179.0);
+        let cells: Vec<_> = Geohash::cover(&bbox, 2, 2).unwrap().into_iter().map(|h| h.to_string()).collect();
+        assert_eq!(cells, vec!["xb", "80"]);
+    }
+
+    #[rstest]
+    #[case("", GeohashError::InvalidPrecision(0))]
+    #[case("u4pruydqqvjzz", GeohashError::InvalidPrecision(13))]
+    #[case("u4pa", GeohashError::InvalidCharacter('a'))]
+    #[case("ü", GeohashError::InvalidCharacter('ü'))]
+    fn test_errors(#[case] text: &str, #[case] expected: GeohashError)
+    {
+        assert_eq!(text.parse::<Geohash>(), Err(expected));
+        assert_eq!(Geohash::new(LatLon::new(0.0, 0.0), 13), Err(GeohashError::InvalidPrecision(13)));
+    }
+}
@@ -31,11 +31,216 @@ impl ISO6709 for DegreeMinuteSecond
     }
 }
 
+/// Errors of parsing the ISO 6709 string representation of a point.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Iso6709Error
+{
+    /// The text is empty.
+    Empty,
+
+    /// The text does not end with the terminator '/'.
+    MissingTerminator,
+
+    /// The latitude is not in the form of ±DD.D, ±DDMM.M or ±DDMMSS.S.
+    InvalidLatitude(String),
+
+    /// The longitude is not in the form of ±DDD.D, ±DDDMM.M or ±DDDMMSS.S.
+    InvalidLongitude(String),
+
+    /// The minutes of the latitude or longitude are not less than 60.
+    MinuteOutOfRange(f64),
+
+    /// The seconds of the latitude or longitude are not less than 60.
+    SecondOutOfRange(f64),
+
+    /// The latitude is beyond ±90°.
+    LatitudeOutOfRange(f64),
+
+    /// The longitude is beyond ±180°.
+    LongitudeOutOfRange(f64),
+
+    /// The altitude is not a signed number.
+    InvalidAltitude(String),
+
+    /// The altitude is required but missing.
+    MissingAltitude,
+
+    /// The identifier of the coordinate reference system is empty.
+    InvalidCrs(String),
+
+    /// The text between the coordinates and the terminator is not recognized.
+    UnexpectedText(String),
+}
+
+impl std::fmt::Display for Iso6709Error
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self
+        {
+            Iso6709Error::Empty => write!(f, "ISO 6709 text is empty"),
+            Iso6709Error::MissingTerminator => write!(f, "ISO 6709 text does not end with '/'"),
+            Iso6709Error::InvalidLatitude(text) => write!(f, "invalid latitude '{text}'"),
+            Iso6709Error::InvalidLongitude(text) => write!(f, "invalid longitude '{text}'"),
+            Iso6709Error::MinuteOutOfRange(min) => write!(f, "minutes {min} are not less than 60"),
+            Iso6709Error::SecondOutOfRange(sec) => write!(f, "seconds {sec} are not less than 60"),
+            Iso6709Error::LatitudeOutOfRange(lat) => write!(f, "latitude {lat} is out of [-90, 90]"),
+            Iso6709Error::LongitudeOutOfRange(lon) => write!(f, "longitude {lon} is out of [-180, 180]"),
+            Iso6709Error::InvalidAltitude(text) => write!(f, "invalid altitude '{text}'"),
+            Iso6709Error::MissingAltitude => write!(f, "altitude is missing"),
+            Iso6709Error::InvalidCrs(text) => write!(f, "invalid coordinate reference system '{text}'"),
+            Iso6709Error::UnexpectedText(text) => write!(f, "unexpected text '{text}'"),
+        }
+    }
+}
+
+impl std::error::Error for Iso6709Error {}
+
+/// A point in the ISO 6709 string representation, e.g. `+27.5916+086.5640+8850CRSWGS_84/`.
+///
+/// The latitude and longitude are in degrees, and the altitude in meters is measured in the
+/// coordinate reference system (CRS) if it is given, or above the ellipsoid otherwise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Iso6709Point
+{
+    /// Latitude in degrees.
+    pub lat: f64,
+
+    /// Longitude in degrees.
+    pub lon: f64,
+
+    /// Altitude in meters, if given.
+    pub alt: Option<f64>,
+
+    /// Identifier of the coordinate reference system, if given.
+    pub crs: Option<String>,
+}
+
+impl Iso6709Point
+{
+    /// Format the point in the unit of degrees, minutes or seconds.
+    pub fn format(&self, unit: Unit) -> String
+    {
+        let lat = DegreeMinuteSecond::with(Coord::Latitude.norm(self.lat));
+        let lon = DegreeMinuteSecond::with(Coord::Longitude.norm(self.lon));
+        let mut text = lat.iso6709(Coord::Latitude, unit) + &lon.iso6709(Coord::Longitude, unit);
+        if let Some(alt) = self.alt
+        {
+            text += &format!("{alt:+}");
+        }
+        if let Some(crs) = &self.crs
+        {
+            text += &format!("CRS{crs}");
+        }
+        text + "/"
+    }
+
+    /// Split the leading signed number from the text.
+    fn split_number(s: &str) -> (&str, &str)
+    {
+        if !s.starts_with(['+', '-'])
+        {
+            return ("", s);
+        }
+        let len = s[1..].find(|c: char| !c.is_ascii_digit() && c != '.').map_or(s.len(), |i| i + 1);
+        s.split_at(len)
+    }
+
+    /// Parse the angle with the given number of digits of the degrees, followed by the optional minutes and seconds.
+    fn parse_angle(text: &str, digits: usize, invalid: fn(String) -> Iso6709Error) -> Result<f64, Iso6709Error>
+    {
+        let sign = if text.starts_with('-') { -1.0 } else { 1.0 };
+        let body = text.get(1..).unwrap_or("");
+        let (int, frac) = body.split_once('.').unwrap_or((body, ""));
+        if body.ends_with('.') || !(int.bytes().chain(frac.bytes()).all(|c| c.is_ascii_digit()))
+        {
+            return Err(invalid(text.to_string()));
+        }
+        let frac = if frac.is_empty() { 0.0 } else { format!("0.{frac}").parse::<f64>().unwrap_or(0.0) };
+        let field = |range: std::ops::Range<usize>| int[range].parse::<f64>().unwrap_or(0.0);
+        let value = match int.len().checked_sub(digits)
+        {
+            Some(0) => field(0..digits) + frac,
+            Some(2) =>
+            {
+                let min = field(digits..digits + 2) + frac;
+                if min >= 60.0
+                {
+                    return Err(Iso6709Error::MinuteOutOfRange(min));
+                }
+                field(0..digits) + min / 60.0
+            },
+            Some(4) =>
+            {
+                let (min, sec) = (field(digits..digits + 2), field(digits + 2..digits + 4) + frac);
+                if min >= 60.0
+                {
+                    return Err(Iso6709Error::MinuteOutOfRange(min));
+                }
+                if sec >= 60.0
+                {
+                    return Err(Iso6709Error::SecondOutOfRange(sec));
+                }
+                field(0..digits) + min / 60.0 + sec / 3600.0
+            },
+            _ => return Err(invalid(text.to_string())),
+        };
+        Ok(sign * value)
+    }
+}
+
+impl std::str::FromStr for Iso6709Point
+{
+    type Err = Iso6709Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let s = s.trim();
+        if s.is_empty()
+        {
+            return Err(Iso6709Error::Empty);
+        }
+        let s = s.strip_suffix('/').ok_or(Iso6709Error::MissingTerminator)?;
+
+        let (text, s) = Self::split_number(s);
+        let lat = Self::parse_angle(text, 2, Iso6709Error::InvalidLatitude)?;
+        if lat.abs() > 90.0
+        {
+            return Err(Iso6709Error::LatitudeOutOfRange(lat));
+        }
+        let (text, s) = Self::split_number(s);
+        let lon = Self::parse_angle(text, 3, Iso6709Error::InvalidLongitude)?;
+        if lon.abs() > 180.0
+        {
+            return Err(Iso6709Error::LongitudeOutOfRange(lon));
+        }
+
+        let (text, s) = Self::split_number(s);
+        let alt = match text
+        {
+            "" => None,
+            _ => Some(text.parse::<f64>().map_err(|_| Iso6709Error::InvalidAltitude(text.to_string()))?),
+        };
+        let crs = match s
+        {
+            "" => None,
+            _ => match s.strip_prefix("CRS")
+            {
+                Some(crs) if !crs.is_empty() && !crs.contains('/') => Some(crs.to_string()),
+                Some(_) => return Err(Iso6709Error::InvalidCrs(s.to_string())),
+                None => return Err(Iso6709Error::UnexpectedText(s.to_string())),
+            },
+        };
+        Ok(Self { lat, lon, alt, crs })
+    }
+}
+
 #[cfg(test)]
 mod test
 {
     use super::*;
     use rstest::*;
+    use float_cmp::assert_approx_eq;
 
     #[rstest]
     #[case(90.0, "+90.0000")]
@@ -127,5 +332,55 @@ mod test
         assert_eq!(expected, text);
     }
 
+    #[rstest]
+    #[case("+40.20361-075.00417/", 40.20361, -75.00417, None, None)]
+    #[case("+4012.2166-07500.2502+350.517CRSWGS_84/", 40.20361, -75.00417, Some(350.517), Some("WGS_84"))]
+    #[case("+401213.0-0750015.0/", 40.2036111111111, -75.0041666666667, None, None)]
+    #[case("+27.5916+086.5640+8850CRSWGS_84/", 27.5916, 86.564, Some(8850.0), Some("WGS_84"))]
+    #[case(" -90+180-10.5/ ", -90.0, 180.0, Some(-10.5), None)]
+    #[case("+00-000CRSEPSG:4326/", 0.0, 0.0, None, Some("EPSG:4326"))]
+    fn test_iso6709_parse(#[case] text: &str, #[case] lat: f64, #[case] lon: f64, #[case] alt: Option<f64>, #[case] crs: Option<&str>)
+    {
+        let p: Iso6709Point = text.parse().unwrap();
+        assert_approx_eq!(f64, p.lat, lat, epsilon = 1e-9);
+        assert_approx_eq!(f64, p.lon, lon, epsilon = 1e-9);
+        assert_eq!(p.alt, alt);
+        assert_eq!(p.crs.as_deref(), crs);
+    }
+
+    #[rstest]
+    #[case("", Iso6709Error::Empty)]
+    #[case("+40.2-075.0", Iso6709Error::MissingTerminator)]
+    #[case("40.2-075.0/", Iso6709Error::InvalidLatitude("".to_string()))]
+    #[case("+402-075.0/", Iso6709Error::InvalidLatitude("+402".to_string()))]
+    #[case("+40.-075.0/", Iso6709Error::InvalidLatitude("+40.".to_string()))]
+    #[case("+4060-075.0/", Iso6709Error::MinuteOutOfRange(60.0))]
+    #[case("+40.2-75.0/", Iso6709Error::InvalidLongitude("-75.0".to_string()))]
+    #[case("+40.2-0750060/", Iso6709Error::SecondOutOfRange(60.0))]
+    #[case("+40.2-0756000/", Iso6709Error::MinuteOutOfRange(60.0))]
+    #[case("+40.2-0750.5/", Iso6709Error::InvalidLongitude("-0750.5".to_string()))]
+    #[case("+90.5-075.0/", Iso6709Error::LatitudeOutOfRange(90.5))]
+    #[case("+40.2-180.5/", Iso6709Error::LongitudeOutOfRange(-180.5))]
+    #[case("+40.2-075.0+1.2.3/", Iso6709Error::InvalidAltitude("+1.2.3".to_string()))]
+    #[case("+40.2-075.0CRS/", Iso6709Error::InvalidCrs("CRS".to_string()))]
+    #[case("+40.2-075.0m/", Iso6709Error::UnexpectedText("m".to_string()))]
+    fn test_iso6709_parse_error(#[case] text: &str, #[case] expected: Iso6709Error)
+    {
+        assert_eq!(text.parse::<Iso6709Point>(), Err(expected));
+    }
 
-}
\ No newline at end of file
+    #[rstest]
+    #[case(Unit::Degree, "+27.5916+086.5640+8850CRSWGS_84/")]
+    #[case(Unit::Minute, "+2735.496+08633.840+8850CRSWGS_84/")]
+    #[case(Unit::Second, "+273529.76+0863350.40+8850CRSWGS_84/")]
+    fn test_iso6709_format(#[case] unit: Unit, #[case] expected: &str)
+    {
+        let p = Iso6709Point { lat: 27.5916, lon: 86.564, alt: Some(8850.0), crs: Some("WGS_84".to_string()) };
+        let text = p.format(unit);
+        assert_eq!(text, expected);
+        let q: Iso6709Point = text.parse().unwrap();
+        assert_approx_eq!(f64, q.lat, p.lat, epsilon = 1e-5);
+        assert_approx_eq!(f64, q.lon, p.lon, epsilon = 1e-5);
+        assert_eq!((q.alt, q.crs), (p.alt, p.crs));
+    }
+}
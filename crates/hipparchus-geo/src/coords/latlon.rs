@@ -1,9 +1,10 @@
 use crate::coords::coord::Coord;
-use crate::coords::iso6709::ISO6709;
+use crate::coords::iso6709::{ISO6709, Iso6709Error, Iso6709Point};
 use crate::coords::nmea0183::NMEA0183;
 use hipparchus_az::{Unit, DegreeMinuteSecond};
 
 /// Latitude and longitude in degrees.
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct LatLon
 {
     /// Latitude in degrees.
@@ -45,6 +46,15 @@ impl LatLon
         format!("{lat},{lon}", lat=lat.iso6709(Coord::Latitude, unit), lon=lon.iso6709(Coord::Longitude, unit))
     }
 
+    /// Parse the ISO6709 string representation such as `+40.20361-075.00417+32.0CRSWGS_84/`.
+    ///
+    /// The latitude/longitude may be in degrees, minutes or seconds, and the optional altitude and CRS are ignored.
+    pub fn from_iso6709(s: &str) -> Result<Self, Iso6709Error>
+    {
+        let p: Iso6709Point = s.parse()?;
+        Ok(Self::new(p.lat, p.lon))
+    }
+
     /// Get the MMEA0183 format of latitude/longitude value.
     pub fn nmea0183(&self) -> String
     {
@@ -54,6 +64,16 @@ impl LatLon
     }
 }
 
+impl std::str::FromStr for LatLon
+{
+    type Err = Iso6709Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        Self::from_iso6709(s)
+    }
+}
+
 #[cfg(test)]
 mod tests 
 {
@@ -80,6 +100,36 @@ mod tests
         assert_eq!(expected, latlon.iso6709(unit));
     }
 
+    #[rstest]
+    #[case("+40.20361-075.00417+32.0CRSWGS_84/", 40.20361, -75.00417)]
+    #[case("+4012.2166-07500.2502/", 40.20361, -75.00417)]
+    #[case("+401213.0-0750015.0CRSWGS_84/", 40.2036111111, -75.0041666667)]
+    #[case("-90-180/", -90.0, -180.0)]
+    #[case("+00+180+0.5/", 0.0, -180.0)]
+    fn test_latlon_from_iso6709(#[case] text: &str, #[case] lat: f64, #[case] lon: f64)
+    {
+        let latlon = LatLon::from_iso6709(text).unwrap();
+        assert_approx_eq!(f64, lat, latlon.latitude(), epsilon = 1e-9);
+        assert_approx_eq!(f64, lon, latlon.longitude(), epsilon = 1e-9);
+        assert_eq!(text.parse::<LatLon>(), Ok(latlon));
+    }
+
+    #[rstest]
+    #[case("", Iso6709Error::Empty)]
+    #[case("+40.20361-075.00417", Iso6709Error::MissingTerminator)]
+    #[case("+40.20361/", Iso6709Error::InvalidLongitude("".to_string()))]
+    #[case("N40.20361W075.00417/", Iso6709Error::InvalidLatitude("".to_string()))]
+    #[case("+4012.2166-07560.2502/", Iso6709Error::MinuteOutOfRange(60.2502))]
+    #[case("+401260.0-0750015.0/", Iso6709Error::SecondOutOfRange(60.0))]
+    #[case("+91-075/", Iso6709Error::LatitudeOutOfRange(91.0))]
+    #[case("+40-181/", Iso6709Error::LongitudeOutOfRange(-181.0))]
+    #[case("+40-075+/", Iso6709Error::InvalidAltitude("+".to_string()))]
+    #[case("+40-075CRS/", Iso6709Error::InvalidCrs("CRS".to_string()))]
+    fn test_latlon_from_iso6709_error(#[case] text: &str, #[case] expected: Iso6709Error)
+    {
+        assert_eq!(LatLon::from_iso6709(text), Err(expected));
+    }
+
     #[rstest]
     #[case(0.0, 0.0, "0000.000,N,00000.000,E")]
     #[case(39.908823, 116.397470, "3954.529,N,11623.848,E")]
@@ -0,0 +1,212 @@
+use crate::coords::latlon::LatLon;
+use crate::coords::iso6709::{Iso6709Error, Iso6709Point};
+use crate::earth::ecef::Ecef;
+use crate::earth::ellipsoid::Ellipsoid;
+use crate::geodesic::{Caps, Geodesic, InverseSolution};
+use hipparchus_az::Unit;
+
+/// Latitude and longitude in degrees with the altitude in meters.
+///
+/// The ECEF conversions take the altitude as the height above the ellipsoid. The altitude read
+/// from ISO 6709 is measured in the coordinate reference system (CRS) given with it, if any.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LatLonAlt
+{
+    /// Latitude and longitude in degrees.
+    latlon: LatLon,
+
+    /// Altitude in meters.
+    alt: f64,
+}
+
+impl LatLonAlt
+{
+    /// Create a new instance.
+    pub fn new(lat: f64, lon: f64, alt: f64) -> Self
+    {
+        Self::with(LatLon::new(lat, lon), alt)
+    }
+
+    /// Create a new instance from the latitude/longitude and the altitude.
+    pub fn with(latlon: LatLon, alt: f64) -> Self
+    {
+        Self { latlon, alt }
+    }
+
+    /// Get the latitude.
+    pub fn latitude(&self) -> f64
+    {
+        self.latlon.latitude()
+    }
+
+    /// Get the longitude.
+    pub fn longitude(&self) -> f64
+    {
+        self.latlon.longitude()
+    }
+
+    /// Get the altitude (meters).
+    pub fn altitude(&self) -> f64
+    {
+        self.alt
+    }
+
+    /// Get the latitude/longitude without the altitude.
+    pub fn latlon(&self) -> LatLon
+    {
+        self.latlon
+    }
+
+    /// Get the ISO6709 format of latitude/longitude/altitude value, with the optional identifier of the CRS.
+    pub fn iso6709(&self, unit: Unit, crs: Option<&str>) -> String
+    {
+        let p = Iso6709Point
+        {
+            lat: self.latitude(),
+            lon: self.longitude(),
+            alt: Some(self.alt),
+            crs: crs.map(str::to_string),
+        };
+        p.format(unit)
+    }
+
+    /// Parse the ISO6709 format of latitude/longitude/altitude value, with the optional identifier
+    /// of the CRS in which the altitude is measured.
+    pub fn from_iso6709(s: &str) -> Result<(Self, Option<String>), Iso6709Error>
+    {
+        let p: Iso6709Point = s.parse()?;
+        let alt = p.alt.ok_or(Iso6709Error::MissingAltitude)?;
+        Ok((Self::new(p.lat, p.lon, alt), p.crs))
+    }
+
+    /// Convert the point to ECEF on the ellipsoid, with the altitude as the height above the ellipsoid.
+    pub fn to_ecef(&self, elps: &Ellipsoid) -> Ecef
+    {
+        elps.geodetic_to_ecef(self.latitude(), self.longitude(), self.alt)
+    }
+
+    /// Convert the ECEF coordinates to the point on the ellipsoid, with the height above the ellipsoid as the altitude.
+    pub fn from_ecef(elps: &Ellipsoid, p: Ecef) -> Self
+    {
+        let (lat, lon, alt) = elps.ecef_to_geodetic(p);
+        Self::new(lat, lon, alt)
+    }
+
+    /// Get the straight-line distance to the other point through the space (meters).
+    pub fn slant_distance(&self, elps: &Ellipsoid, other: &LatLonAlt) -> f64
+    {
+        self.to_ecef(elps).distance(&other.to_ecef(elps))
+    }
+
+    /// Solve the inverse geodesic problem to the other point on the surface of the ellipsoid, ignoring the heights.
+    pub fn inverse(&self, geod: &Geodesic, other: &LatLonAlt, caps: Caps) -> InverseSolution
+    {
+        geod.solve_inverse(self.latitude(), self.longitude(), other.latitude(), other.longitude(), caps)
+    }
+
+    /// Move the point by the distance (meters) along the geodesic with the azimuth (degrees), keeping the height.
+    pub fn direct(&self, geod: &Geodesic, azi: f64, s: f64) -> Self
+    {
+        let sol = geod.solve_direct(self.latitude(), self.longitude(), azi, s, Caps::LATITUDE | Caps::LONGITUDE);
+        Self::new(sol.lat2.unwrap_or(f64::NAN), sol.lon2.unwrap_or(f64::NAN), self.alt)
+    }
+}
+
+impl From<LatLon> for LatLonAlt
+{
+    /// The point on the surface of the ellipsoid.
+    fn from(latlon: LatLon) -> Self
+    {
+        Self::with(latlon, 0.0)
+    }
+}
+
+impl From<LatLonAlt> for LatLon
+{
+    fn from(p: LatLonAlt) -> Self
+    {
+        p.latlon()
+    }
+}
+
+impl std::str::FromStr for LatLonAlt
+{
+    type Err = Iso6709Error;
+
+    /// Parse the ISO6709 format, ignoring the CRS, see `from_iso6709` to get it.
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        Ok(Self::from_iso6709(s)?.0)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::earth::models::{Model, WGS84};
+    use rstest::*;
+    use float_cmp::assert_approx_eq;
+
+    #[rstest]
+    #[case(27.988056, 86.925278, 8848.86, Unit::Degree, "+27.9881+086.9253+8848.86CRSWGS_84/")]
+    #[case(-33.3, -44.4, -12.5, Unit::Minute, "-3318.000-04424.000-12.5CRSWGS_84/")]
+    #[case(0.0, 0.0, 0.0, Unit::Second, "+000000.00+0000000.00+0CRSWGS_84/")]
+    fn test_latlonalt_iso6709(#[case] lat: f64, #[case] lon: f64, #[case] alt: f64, #[case] unit: Unit, #[case] expected: &str)
+    {
+        let p = LatLonAlt::new(lat, lon, alt);
+        let text = p.iso6709(unit, Some("WGS_84"));
+        assert_eq!(text, expected);
+        let q: LatLonAlt = text.parse().unwrap();
+        assert_approx_eq!(f64, q.latitude(), lat, epsilon = 1e-4);
+        assert_approx_eq!(f64, q.longitude(), lon, epsilon = 1e-4);
+        assert_eq!(q.altitude(), alt);
+        assert_eq!(LatLonAlt::from_iso6709(&text), Ok((q, Some("WGS_84".to_string()))));
+        let text = p.iso6709(unit, None);
+        assert_eq!(text, expected.replace("CRSWGS_84", ""));
+        assert_eq!(LatLonAlt::from_iso6709(&text), Ok((q, None)));
+    }
+
+    #[test]
+    fn test_latlonalt_iso6709_error()
+    {
+        assert_eq!(LatLonAlt::from_iso6709("+27.9881+086.9253/"), Err(Iso6709Error::MissingAltitude));
+        assert_eq!("+27.9881+086.9253+88x/".parse::<LatLonAlt>(), Err(Iso6709Error::UnexpectedText("x".to_string())));
+    }
+
+    #[test]
+    fn test_latlonalt_ecef()
+    {
+        let elps = WGS84::elps();
+        let p = LatLonAlt::new(33.3, 44.4, 6000.0);
+        let ecef = p.to_ecef(&elps);
+        assert_approx_eq!(f64, ecef.x, 3816209.60449309, epsilon = 1e-7);
+        assert_approx_eq!(f64, ecef.y, 3737108.55025426, epsilon = 1e-7);
+        assert_approx_eq!(f64, ecef.z, 3485109.57256804, epsilon = 1e-7);
+        let q = LatLonAlt::from_ecef(&elps, ecef);
+        assert_approx_eq!(f64, q.latitude(), 33.3, epsilon = 1e-12);
+        assert_approx_eq!(f64, q.longitude(), 44.4, epsilon = 1e-12);
+        assert_approx_eq!(f64, q.altitude(), 6000.0, epsilon = 1e-8);
+
+        let ground = LatLonAlt::from(p.latlon());
+        assert_approx_eq!(f64, p.slant_distance(&elps, &ground), 6000.0, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn test_latlonalt_geodesic()
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let p = LatLonAlt::new(40.6, -73.8, 10.0);
+        let q = LatLonAlt::new(49.01666667, 2.55, 119.0);
+        let sol = p.inverse(&geod, &q, Caps::DISTANCE | Caps::AZIMUTH);
+        let (s12, azi1) = (sol.s12.unwrap(), sol.azi1.unwrap());
+        assert_approx_eq!(f64, azi1, 53.47022, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, s12, 5853226.0, epsilon = 0.5);
+
+        let r = p.direct(&geod, azi1, s12);
+        assert_approx_eq!(f64, r.latitude(), q.latitude(), epsilon = 1e-9);
+        assert_approx_eq!(f64, r.longitude(), q.longitude(), epsilon = 1e-9);
+        assert_eq!(r.altitude(), p.altitude());
+        assert_eq!(LatLon::from(r), r.latlon());
+    }
+}
@@ -0,0 +1,219 @@
+use crate::coords::bbox::BoundingBox;
+use crate::coords::latlon::LatLon;
+
+/// The number of divisions of the pairs, the field, square, subsquare, extended square and extended subsquare.
+const DIVISIONS: [i64; 5] = [18, 10, 24, 10, 24];
+
+/// The number of the smallest cells in a field, the product of the divisions after the field.
+const FIELD_CELLS: i64 = 10 * 24 * 10 * 24;
+
+/// The number of the smallest cells per degree of longitude.
+const LON_CELLS: f64 = (18 * FIELD_CELLS) as f64 / 360.0;
+
+/// The number of the smallest cells per degree of latitude.
+const LAT_CELLS: f64 = (18 * FIELD_CELLS) as f64 / 180.0;
+
+/// Errors of the Maidenhead locator conversions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MaidenheadError
+{
+    /// The length is not one of 2, 4, 6, 8 and 10.
+    InvalidLength(usize),
+
+    /// The character is not valid at its position.
+    InvalidCharacter(char),
+}
+
+impl std::fmt::Display for MaidenheadError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self
+        {
+            MaidenheadError::InvalidLength(length) => write!(f, "maidenhead locator length {length} is not 2, 4, 6, 8 or 10"),
+            MaidenheadError::InvalidCharacter(c) => write!(f, "invalid maidenhead locator character '{c}'"),
+        }
+    }
+}
+
+impl std::error::Error for MaidenheadError {}
+
+/// A Maidenhead locator of the grid squares used by radio amateurs, e.g. "JO65ha".
+///
+/// The pairs of longitude and latitude are the field (A-R, 20° by 10°), the square (0-9, 2° by 1°),
+/// the subsquare (a-x, 5' by 2.5'), the extended square (0-9) and the extended subsquare (a-x).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Maidenhead
+{
+    locator: String,
+}
+
+impl Maidenhead
+{
+    /// The maximum length in characters.
+    pub const MAX_LENGTH: usize = 10;
+
+    /// Encode the point with the length in characters, one of 2, 4, 6, 8 and 10.
+    pub fn new(p: LatLon, length: usize) -> Result<Self, MaidenheadError>
+    {
+        Self::check_length(length)?;
+        let cells = 18 * FIELD_CELLS;
+        let lon = (((p.longitude() + 180.0) * LON_CELLS).floor() as i64).clamp(0, cells - 1);
+        let lat = (((p.latitude() + 90.0) * LAT_CELLS).floor() as i64).clamp(0, cells - 1);
+        let mut locator = String::with_capacity(length);
+        let mut place = cells;
+        for (i, &division) in DIVISIONS.iter().enumerate().take(length / 2)
+        {
+            place /= division;
+            for v in [lon, lat]
+            {
+                let d = (v / place % division) as u8;
+                locator.push(match i % 2
+                {
+                    1 => (b'0' + d) as char,
+                    _ if i == 0 => (b'A' + d) as char,
+                    _ => (b'a' + d) as char,
+                });
+            }
+        }
+        Ok(Self { locator })
+    }
+
+    /// Get the locator text.
+    pub fn as_str(&self) -> &str
+    {
+        &self.locator
+    }
+
+    /// Get the length in characters.
+    pub fn length(&self) -> usize
+    {
+        self.locator.len()
+    }
+
+    /// Get the bounds of the cell.
+    pub fn bounds(&self) -> BoundingBox
+    {
+        let digits: Vec<i64> = self.locator.bytes().enumerate().map(|(i, b)| digit(i, b).unwrap_or(0)).collect();
+        let (mut lon, mut lat) = (0, 0);
+        let mut place = 18 * FIELD_CELLS;
+        for (pair, &division) in digits.chunks(2).zip(DIVISIONS.iter())
+        {
+            place /= division;
+            lon += pair[0] * place;
+            lat += pair[1] * place;
+        }
+        let (west, south) = (lon as f64 / LON_CELLS - 180.0, lat as f64 / LAT_CELLS - 90.0);
+        BoundingBox::new(south, west, south + place as f64 / LAT_CELLS, west + place as f64 / LON_CELLS)
+    }
+
+    /// Get the centre of the cell.
+    pub fn center(&self) -> LatLon
+    {
+        self.bounds().center()
+    }
+
+    fn check_length(length: usize) -> Result<(), MaidenheadError>
+    {
+        if (2..=Self::MAX_LENGTH).contains(&length) && length % 2 == 0 { Ok(()) } else { Err(MaidenheadError::InvalidLength(length)) }
+    }
+}
+
+/// Get the value of the character at the position, in either case for the letters.
+fn digit(position: usize, c: u8) -> Option<i64>
+{
+    let (first, division) = match position / 2
+    {
+        0 => (b'A', 18),
+        1 | 3 => (b'0', 10),
+        _ => (b'A', 24),
+    };
+    let v = c.to_ascii_uppercase().wrapping_sub(first) as i64;
+    (v < division).then_some(v)
+}
+
+impl std::fmt::Display for Maidenhead
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        write!(f, "{}", self.locator)
+    }
+}
+
+impl std::str::FromStr for Maidenhead
+{
+    type Err = MaidenheadError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let s = s.trim();
+        Self::check_length(s.chars().count())?;
+        if let Some(c) = s.chars().enumerate().find(|&(i, c)| !c.is_ascii() || digit(i, c as u8).is_none()).map(|(_, c)| c)
+        {
+            return Err(MaidenheadError::InvalidCharacter(c));
+        }
+        let locator = s.char_indices().map(|(i, c)| if i < 2 { c.to_ascii_uppercase() } else { c.to_ascii_lowercase() }).collect();
+        Ok(Self { locator })
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use rstest::*;
+    use float_cmp::assert_approx_eq;
+
+    #[rstest]
+    #[case(41.714775, -72.727260, 6, "FN31pr")]
+    #[case(55.0, 12.6, 6, "JO65ha")]
+    #[case(51.4778, -0.0015, 6, "IO91xl")]
+    #[case(-33.8568, 151.2153, 6, "QF56od")]
+    #[case(41.714775, -72.727260, 2, "FN")]
+    #[case(41.714775, -72.727260, 4, "FN31")]
+    #[case(41.714775, -72.727260, 10, "FN31pr21rn")]
+    #[case(-90.0, -180.0, 10, "AA00aa00aa")]
+    #[case(90.0, 179.9999, 10, "RR99xx99xx")]
+    fn test_encode(#[case] lat: f64, #[case] lon: f64, #[case] length: usize, #[case] expected: &str)
+    {
+        let p = LatLon::new(lat, lon);
+        let locator = Maidenhead::new(p, length).unwrap();
+        assert_eq!(locator.as_str(), expected);
+        assert_eq!(locator.length(), length);
+        assert!(locator.bounds().contains(p));
+        assert_eq!(expected.to_lowercase().parse::<Maidenhead>(), Ok(locator));
+    }
+
+    #[rstest]
+    #[case("JO", 50.0, 0.0, 60.0, 20.0)]
+    #[case("JO65", 55.0, 12.0, 56.0, 14.0)]
+    #[case("JO65ha", 55.0, 12.5833333333, 55.0416666667, 12.6666666667)]
+    #[case("JO65ha00", 55.0, 12.5833333333, 55.0041666667, 12.5916666667)]
+    #[case("JO65ha00aa", 55.0, 12.5833333333, 55.0001736111, 12.5836805556)]
+    fn test_decode(#[case] text: &str, #[case] south: f64, #[case] west: f64, #[case] north: f64, #[case] east: f64)
+    {
+        let locator: Maidenhead = text.parse().unwrap();
+        let b = locator.bounds();
+        assert_approx_eq!(f64, b.south, south, epsilon = 1e-10);
+        assert_approx_eq!(f64, b.west, west, epsilon = 1e-10);
+        assert_approx_eq!(f64, b.north, north, epsilon = 1e-10);
+        assert_approx_eq!(f64, b.east, east, epsilon = 1e-10);
+        assert_eq!(locator.center(), b.center());
+        assert_eq!(Maidenhead::new(locator.center(), locator.length()), Ok(locator));
+    }
+
+    #[rstest]
+    #[case("", MaidenheadError::InvalidLength(0))]
+    #[case("JO6", MaidenheadError::InvalidLength(3))]
+    #[case("JO65ha00aa00", MaidenheadError::InvalidLength(12))]
+    #[case("SO65", MaidenheadError::InvalidCharacter('S'))]
+    #[case("JOA5", MaidenheadError::InvalidCharacter('A'))]
+    #[case("JO65hz", MaidenheadError::InvalidCharacter('z'))]
+    #[case("JO65ha0a", MaidenheadError::InvalidCharacter('a'))]
+    #[case("JO65hä", MaidenheadError::InvalidCharacter('ä'))]
+    fn test_errors(#[case] text: &str, #[case] expected: MaidenheadError)
+    {
+        assert_eq!(text.parse::<Maidenhead>(), Err(expected));
+        assert_eq!(Maidenhead::new(LatLon::new(0.0, 0.0), 5), Err(MaidenheadError::InvalidLength(5)));
+    }
+}
@@ -0,0 +1,513 @@
+use std::fmt::Display;
+use std::str::FromStr;
+use crate::coords::latlon::LatLon;
+use crate::projection::{UtmUps, UtmUpsPosition, UtmError};
+
+// The side of the 100 km squares (meters).
+const TILE: f64 = 100e3;
+
+// The latitude band letters of UTM, 8° each from 80°S, the band X spans 12°.
+const UTM_BANDS: &[u8] = b"CDEFGHJKLMNPQRSTUVWX";
+
+// The column letters of the 100 km squares of UTM, cycling with the zone.
+const UTM_COLUMNS: [&[u8]; 3] = [b"ABCDEFGH", b"JKLMNPQR", b"STUVWXYZ"];
+
+// The row letters of the 100 km squares of UTM, the even zones are shifted by 5 rows.
+const UTM_ROWS: &[u8] = b"ABCDEFGHJKLMNPQRSTUV";
+const UTM_ROW_PERIOD: i64 = 20;
+const UTM_EVEN_ROW_SHIFT: i64 = 5;
+
+// The band, column and row letters of UPS, for the south-west, south-east, north-west and north-east.
+const UPS_BANDS: &[u8] = b"ABYZ";
+const UPS_COLUMNS: [&[u8]; 4] = [b"JKLPQRSTUXYZ", b"ABCFGHJKLPQR", b"RSTUXYZ", b"ABCFGHJ"];
+const UPS_ROWS: [&[u8]; 2] = [b"ABCDEFGHJKLMNPQRSTUVWXYZ", b"ABCDEFGHJKLMNP"];
+
+// The index of the first 100 km column and row of UPS in the south and the north.
+const UPS_MIN_SOUTH: i64 = 8;
+const UPS_MIN_NORTH: i64 = 13;
+
+// The index of the 100 km column of the pole in UPS.
+const UPS_POLE: i64 = 20;
+
+/// Errors of the MGRS conversions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MgrsError
+{
+    /// The precision is not in [0, 5] digits.
+    InvalidPrecision(u8),
+
+    /// The point cannot be converted to UTM or UPS.
+    Projection(UtmError),
+
+    /// The string is empty.
+    Empty,
+
+    /// The zone is not a number in [1, 60].
+    InvalidZone(String),
+
+    /// The latitude band is missing.
+    MissingBand,
+
+    /// The latitude band letter is not valid for UTM, or for UPS if there is no zone.
+    InvalidBand(char),
+
+    /// The letters of the 100 km square are missing.
+    MissingSquare,
+
+    /// The column letter of the 100 km square is not valid for the zone or the polar region.
+    InvalidColumn(char),
+
+    /// The row letter of the 100 km square is not valid for the zone or the polar region.
+    InvalidRow(char),
+
+    /// The 100 km square is not in the latitude band.
+    SquareNotInBand { square: String, band: char },
+
+    /// A character other than a digit follows the 100 km square.
+    UnexpectedCharacter(char),
+
+    /// The numbers of digits of the easting and the northing differ.
+    UnevenDigits(usize),
+
+    /// There are more than 5 digits for the easting and the northing.
+    TooManyDigits(usize),
+
+    /// The easting or the northing is not in a 100 km square of the zone or the polar region.
+    OutOfGrid,
+}
+
+impl Display for MgrsError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self
+        {
+            MgrsError::InvalidPrecision(p) => write!(f, "precision {p} is not in [0, 5] digits"),
+            MgrsError::Projection(e) => write!(f, "{e}"),
+            MgrsError::Empty => write!(f, "MGRS string is empty"),
+            MgrsError::InvalidZone(zone) => write!(f, "zone {zone} is not in [1, 60]"),
+            MgrsError::MissingBand => write!(f, "latitude band is missing"),
+            MgrsError::InvalidBand(c) => write!(f, "latitude band {c} is not valid"),
+            MgrsError::MissingSquare => write!(f, "100 km square is missing"),
+            MgrsError::InvalidColumn(c) => write!(f, "column {c} of the 100 km square is not valid in the zone"),
+            MgrsError::InvalidRow(c) => write!(f, "row {c} of the 100 km square is not valid in the zone"),
+            MgrsError::SquareNotInBand { square, band } => write!(f, "100 km square {square} is not in the latitude band {band}"),
+            MgrsError::UnexpectedCharacter(c) => write!(f, "unexpected character {c:?} in the coordinates"),
+            MgrsError::UnevenDigits(n) => write!(f, "{n} digits cannot be split evenly into easting and northing"),
+            MgrsError::TooManyDigits(n) => write!(f, "{n} digits exceed the precision of 1 m"),
+            MgrsError::OutOfGrid => write!(f, "easting and northing are not in a 100 km square of the zone"),
+        }
+    }
+}
+
+impl std::error::Error for MgrsError {}
+
+impl From<UtmError> for MgrsError
+{
+    fn from(e: UtmError) -> Self
+    {
+        MgrsError::Projection(e)
+    }
+}
+
+/// A grid reference of the Military Grid Reference System (MGRS).
+///
+/// The reference designates a square of the UTM or UPS grid, identified by the south-west corner
+/// with the precision of 0 (100 km) to 5 (1 m) digits for both the easting and the northing.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Mgrs
+{
+    zone: u8,
+    band: char,
+    easting: f64,
+    northing: f64,
+    precision: u8,
+}
+
+impl Mgrs
+{
+    /// The maximum number of digits of the easting and the northing, i.e. 1 m.
+    pub const MAX_PRECISION: u8 = 5;
+
+    /// Get the grid reference of the point with the given number of digits (0 for 100 km to 5 for 1 m).
+    pub fn new(grid: &UtmUps, p: LatLon, precision: u8) -> Result<Self, MgrsError>
+    {
+        if precision > Self::MAX_PRECISION
+        {
+            return Err(MgrsError::InvalidPrecision(precision));
+        }
+        let pos = grid.forward(p)?;
+        let band = match pos
+        {
+            UtmUpsPosition::Utm(_) =>
+            {
+                let lat = p.latitude();
+                let iband = if lat == 0.0 { 0 } else { ((lat / 8.0).floor() as i64).clamp(-10, 9) };
+                UTM_BANDS[(iband + 10) as usize] as char
+            },
+            UtmUpsPosition::Ups(ups) =>
+            {
+                let east = ups.easting >= UPS_POLE as f64 * TILE;
+                UPS_BANDS[2 * ups.north as usize + east as usize] as char
+            },
+        };
+        let res = Self::resolution_of(precision) as i64;
+        let truncate = |v: f64| ((v.floor() as i64).div_euclid(res) * res) as f64;
+        Ok(Self { zone: pos.zone(), band, easting: truncate(pos.easting()), northing: truncate(pos.northing()), precision })
+    }
+
+    /// Build the grid reference from its parts, with the easting and the northing truncated to the precision.
+    ///
+    /// The zone is in [1, 60] for UTM or 0 for UPS, and the 100 km square of the easting and the
+    /// northing must be in the latitude band.
+    pub fn from_parts(zone: u8, band: char, easting: f64, northing: f64, precision: u8) -> Result<Self, MgrsError>
+    {
+        if precision > Self::MAX_PRECISION
+        {
+            return Err(MgrsError::InvalidPrecision(precision));
+        }
+        if zone > 60
+        {
+            return Err(MgrsError::InvalidZone(zone.to_string()));
+        }
+        let iband = Self::band_index(zone, band)?;
+        if !(easting.is_finite() && northing.is_finite())
+        {
+            return Err(MgrsError::OutOfGrid);
+        }
+        let res = Self::resolution_of(precision);
+        let truncate = |v: f64| (v / res).floor() * res;
+        let mgrs = Self { zone, band, easting: truncate(easting), northing: truncate(northing), precision };
+        let (col, row) = mgrs.square().ok_or(MgrsError::OutOfGrid)?;
+        let (xh, yh) = Self::square_origin(zone, iband, band, col, row)?;
+        if (xh as f64 * TILE, yh as f64 * TILE) != ((mgrs.easting / TILE).floor() * TILE, (mgrs.northing / TILE).floor() * TILE)
+        {
+            return Err(MgrsError::SquareNotInBand { square: format!("{col}{row}"), band });
+        }
+        Ok(mgrs)
+    }
+
+    /// Get the UTM zone in [1, 60], 0 for UPS.
+    pub fn zone(&self) -> u8
+    {
+        self.zone
+    }
+
+    /// Get the latitude band letter, A, B, Y or Z for UPS.
+    pub fn band(&self) -> char
+    {
+        self.band
+    }
+
+    /// Get the easting of the south-west corner of the square (meters).
+    pub fn easting(&self) -> f64
+    {
+        self.easting
+    }
+
+    /// Get the northing of the south-west corner of the square (meters).
+    pub fn northing(&self) -> f64
+    {
+        self.northing
+    }
+
+    /// Get the number of digits of the easting and the northing in [0, 5].
+    pub fn precision(&self) -> u8
+    {
+        self.precision
+    }
+
+    /// Get the side of the square (meters).
+    pub fn resolution(&self) -> f64
+    {
+        Self::resolution_of(self.precision)
+    }
+
+    /// Check if the grid reference is in the northern hemisphere.
+    pub fn north(&self) -> bool
+    {
+        if self.zone == 0 { self.band >= 'Y' } else { self.band >= 'N' }
+    }
+
+    /// Get the latitude and longitude of the center of the square.
+    pub fn to_latlon(&self, grid: &UtmUps) -> Result<LatLon, MgrsError>
+    {
+        let half = self.resolution() / 2.0;
+        let pos = grid.position(self.zone, self.north(), self.easting + half, self.northing + half)?;
+        Ok(grid.reverse(&pos))
+    }
+
+    fn resolution_of(precision: u8) -> f64
+    {
+        10f64.powi(5 - precision as i32)
+    }
+
+    // Get the index of the row of UTM with the origin at the equator in [-90, 95) from the band in
+    // [-10, 10), the column in [0, 8) and the periodic row in [0, 20), None if they are inconsistent.
+    fn utm_row(iband: i64, icol: i64, irow: i64) -> Option<i64>
+    {
+        let c = 100.0 * (8 * iband + 4) as f64 / 90.0;
+        let northp = if iband >= 0 { 0.1 } else { 0.0 };
+        let minrow = if iband > -10 { (c - 4.3 - northp).floor() as i64 } else { -90 };
+        let maxrow = if iband < 9 { (c + 4.4 - northp).floor() as i64 } else { 94 };
+        let baserow = (minrow + maxrow) / 2 - UTM_ROW_PERIOD / 2;
+        let irow = (irow - baserow).rem_euclid(UTM_ROW_PERIOD) + baserow;
+        if (minrow..=maxrow).contains(&irow)
+        {
+            return Some(irow);
+        }
+        // The northings 7100 km and 8000 km cross the band boundaries in some columns.
+        let sband = if iband >= 0 { iband } else { -iband - 1 };
+        let srow = if irow >= 0 { irow } else { -irow - 1 };
+        let scol = if icol < 4 { icol } else { 7 - icol };
+        let allowed = (srow == 70 && sband == 8 && scol >= 2)
+            || (srow == 71 && sband == 7 && scol <= 2)
+            || (srow == 79 && sband == 9 && scol >= 1)
+            || (srow == 80 && sband == 8 && scol <= 1);
+        if allowed { Some(irow) } else { None }
+    }
+
+    // Get the index of the latitude band letter for UTM, or for UPS if the zone is 0.
+    fn band_index(zone: u8, band: char) -> Result<i64, MgrsError>
+    {
+        let bands = if zone > 0 { UTM_BANDS } else { UPS_BANDS };
+        Ok(bands.iter().position(|&b| b as char == band).ok_or(MgrsError::InvalidBand(band))? as i64)
+    }
+
+    // Get the indices of the 100 km column and row of the square letters in the latitude band.
+    fn square_origin(zone: u8, iband: i64, band: char, col: char, row: char) -> Result<(i64, i64), MgrsError>
+    {
+        if zone > 0
+        {
+            let north = iband >= 10;
+            let icol = UTM_COLUMNS[((zone - 1) % 3) as usize].iter().position(|&b| b as char == col).ok_or(MgrsError::InvalidColumn(col))? as i64;
+            let irow = UTM_ROWS.iter().position(|&b| b as char == row).ok_or(MgrsError::InvalidRow(row))? as i64;
+            let shift = if zone % 2 == 0 { UTM_EVEN_ROW_SHIFT } else { 0 };
+            let irow = Self::utm_row(iband - 10, icol, (irow - shift).rem_euclid(UTM_ROW_PERIOD))
+                .ok_or_else(|| MgrsError::SquareNotInBand { square: format!("{col}{row}"), band })?;
+            Ok((icol + 1, if north { irow } else { irow + 100 }))
+        }
+        else
+        {
+            let north = iband >= 2;
+            let east = iband % 2 == 1;
+            let min = if north { UPS_MIN_NORTH } else { UPS_MIN_SOUTH };
+            let icol = UPS_COLUMNS[iband as usize].iter().position(|&b| b as char == col).ok_or(MgrsError::InvalidColumn(col))? as i64;
+            let irow = UPS_ROWS[north as usize].iter().position(|&b| b as char == row).ok_or(MgrsError::InvalidRow(row))? as i64;
+            Ok((icol + if east { UPS_POLE } else { min }, irow + min))
+        }
+    }
+
+    // Get the column and row letters of the 100 km square, None if it is out of the grid.
+    fn square(&self) -> Option<(char, char)>
+    {
+        let letter = |letters: &[u8], i: i64| usize::try_from(i).ok().and_then(|i| letters.get(i)).map(|&b| b as char);
+        let xh = (self.easting / TILE).floor() as i64;
+        let yh = (self.northing / TILE).floor() as i64;
+        if self.zone == 0
+        {
+            let north = self.north();
+            let east = xh >= UPS_POLE;
+            let min = if north { UPS_MIN_NORTH } else { UPS_MIN_SOUTH };
+            let iband = 2 * north as usize + east as usize;
+            let icol = xh - if east { UPS_POLE } else { min };
+            Some((letter(UPS_COLUMNS[iband], icol)?, letter(UPS_ROWS[north as usize], yh - min)?))
+        }
+        else
+        {
+            let zone = self.zone as i64;
+            let shift = if zone % 2 == 0 { UTM_EVEN_ROW_SHIFT } else { 0 };
+            let icol = xh - 1;
+            let irow = (yh + shift).rem_euclid(UTM_ROW_PERIOD);
+            Some((letter(UTM_COLUMNS[((zone - 1) % 3) as usize], icol)?, letter(UTM_ROWS, irow)?))
+        }
+    }
+}
+
+impl Display for Mgrs
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        if self.zone > 0
+        {
+            write!(f, "{:02}", self.zone)?;
+        }
+        let (col, row) = self.square().ok_or(std::fmt::Error)?;
+        write!(f, "{}{}{}", self.band, col, row)?;
+        let res = self.resolution();
+        let width = self.precision as usize;
+        if width > 0
+        {
+            let x = (self.easting.rem_euclid(TILE) / res).floor() as i64;
+            let y = (self.northing.rem_euclid(TILE) / res).floor() as i64;
+            write!(f, "{x:0width$}{y:0width$}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Mgrs
+{
+    type Err = MgrsError;
+
+    /// Parse the grid reference, e.g. `33UXP0500444996`, letters are case insensitive.
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let s = s.trim().to_ascii_uppercase();
+        if s.is_empty()
+        {
+            return Err(MgrsError::Empty);
+        }
+        let chars: Vec<char> = s.chars().collect();
+
+        // zone
+        let nz = chars.iter().take_while(|c| c.is_ascii_digit()).count();
+        let zone = match nz
+        {
+            0 => 0,
+            1 | 2 =>
+            {
+                let zone: u8 = s[..nz].parse().unwrap();
+                if !(1..=60).contains(&zone)
+                {
+                    return Err(MgrsError::InvalidZone(s[..nz].to_string()));
+                }
+                zone
+            },
+            _ => return Err(MgrsError::InvalidZone(s[..nz].to_string())),
+        };
+
+        // latitude band
+        let band = *chars.get(nz).ok_or(MgrsError::MissingBand)?;
+        let iband = Self::band_index(zone, band)?;
+
+        // 100 km square
+        let (col, row) = match (chars.get(nz + 1), chars.get(nz + 2))
+        {
+            (Some(col), Some(row)) if col.is_ascii_alphabetic() && row.is_ascii_alphabetic() => (*col, *row),
+            _ => return Err(MgrsError::MissingSquare),
+        };
+        let (xh, yh) = Self::square_origin(zone, iband, band, col, row)?;
+
+        // easting and northing
+        let digits = &chars[nz + 3..];
+        if let Some(c) = digits.iter().find(|c| !c.is_ascii_digit())
+        {
+            return Err(MgrsError::UnexpectedCharacter(*c));
+        }
+        if digits.len() % 2 != 0
+        {
+            return Err(MgrsError::UnevenDigits(digits.len()));
+        }
+        if digits.len() > 2 * Self::MAX_PRECISION as usize
+        {
+            return Err(MgrsError::TooManyDigits(digits.len()));
+        }
+        let precision = (digits.len() / 2) as u8;
+        let res = Self::resolution_of(precision);
+        let value = |d: &[char]| d.iter().fold(0.0, |v, c| v * 10.0 + c.to_digit(10).unwrap() as f64) * res;
+        let (dx, dy) = digits.split_at(precision as usize);
+        Ok(Self
+        {
+            zone,
+            band,
+            easting: xh as f64 * TILE + value(dx),
+            northing: yh as f64 * TILE + value(dy),
+            precision,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::earth::models::WGS84;
+    use float_cmp::assert_approx_eq;
+    use rstest::*;
+
+    #[rstest]
+    #[case(33.3, 44.4, 5, "38SMB4414084706")]
+    #[case(33.3, 44.4, 3, "38SMB441847")]
+    #[case(33.3, 44.4, 0, "38SMB")]
+    #[case(-33.3, 44.4, 2, "38HMJ4415")]
+    #[case(90.0, 0.0, 5, "ZAH0000000000")]
+    #[case(-90.0, 0.0, 5, "BAN0000000000")]
+    #[case(85.0, 30.0, 4, "ZCC77721895")]
+    #[case(-85.0, -120.0, 1, "ATK12")]
+    #[case(0.0, 3.0, 2, "31NEA0000")]
+    #[case(-0.5, 3.0, 2, "31MEV0044")]
+    fn test_mgrs(#[case] lat: f64, #[case] lon: f64, #[case] precision: u8, #[case] expected: &str)
+    {
+        let grid = UtmUps::model::<WGS84>();
+        let mgrs = Mgrs::new(&grid, LatLon::new(lat, lon), precision).unwrap();
+        assert_eq!(mgrs.to_string(), expected);
+
+        let parsed: Mgrs = expected.parse().unwrap();
+        assert_eq!(parsed, mgrs);
+        let p = parsed.to_latlon(&grid).unwrap();
+        assert_eq!(Mgrs::new(&grid, p, precision).unwrap(), mgrs);
+        let limit = parsed.resolution() / 100e3;
+        assert_approx_eq!(f64, p.latitude(), lat, epsilon = limit);
+    }
+
+    #[test]
+    fn test_parse()
+    {
+        let grid = UtmUps::model::<WGS84>();
+        let mgrs: Mgrs = "33UXP0500444996".parse().unwrap();
+        assert_eq!((mgrs.zone(), mgrs.band(), mgrs.precision()), (33, 'U', 5));
+        assert_eq!((mgrs.easting(), mgrs.northing()), (605004.0, 5344996.0));
+        let p = mgrs.to_latlon(&grid).unwrap();
+        assert_eq!(Mgrs::new(&grid, p, 5).unwrap().to_string(), "33UXP0500444996");
+
+        let lower: Mgrs = " 4qfj1234567890 ".parse().unwrap();
+        assert_eq!(lower.to_string(), "04QFJ1234567890");
+        assert!(lower.north());
+    }
+
+    #[rstest]
+    #[case("", MgrsError::Empty)]
+    #[case("61UXP", MgrsError::InvalidZone("61".to_string()))]
+    #[case("0UXP", MgrsError::InvalidZone("0".to_string()))]
+    #[case("333UXP", MgrsError::InvalidZone("333".to_string()))]
+    #[case("33", MgrsError::MissingBand)]
+    #[case("33IXP", MgrsError::InvalidBand('I'))]
+    #[case("33YXP", MgrsError::InvalidBand('Y'))]
+    #[case("CAN", MgrsError::InvalidBand('C'))]
+    #[case("33UX", MgrsError::MissingSquare)]
+    #[case("33UX1", MgrsError::MissingSquare)]
+    #[case("33UAP", MgrsError::InvalidColumn('A'))]
+    #[case("ZMH", MgrsError::InvalidColumn('M'))]
+    #[case("33UXW", MgrsError::InvalidRow('W'))]
+    #[case("33UXF", MgrsError::SquareNotInBand { square: "XF".to_string(), band: 'U' })]
+    #[case("33UXP05004-4996", MgrsError::UnexpectedCharacter('-'))]
+    #[case("33UXP 05004 44996", MgrsError::UnexpectedCharacter(' '))]
+    #[case("33UXP050044499", MgrsError::UnevenDigits(9))]
+    #[case("33UXP050041449961", MgrsError::TooManyDigits(12))]
+    fn test_parse_errors(#[case] s: &str, #[case] expected: MgrsError)
+    {
+        assert_eq!(s.parse::<Mgrs>(), Err(expected));
+    }
+
+    #[test]
+    fn test_errors()
+    {
+        let grid = UtmUps::model::<WGS84>();
+        assert_eq!(Mgrs::new(&grid, LatLon::new(0.0, 0.0), 6), Err(MgrsError::InvalidPrecision(6)));
+        assert_eq!(MgrsError::InvalidBand('I').to_string(), "latitude band I is not valid");
+
+        let mgrs = Mgrs::from_parts(33, 'U', 605004.7, 5344996.2, 4).unwrap();
+        assert_eq!(mgrs.to_string(), "33UXP05004499");
+        assert_eq!(Mgrs::from_parts(0, 'Z', 2000000.0, 2000000.0, 5).unwrap().to_string(), "ZAH0000000000");
+        assert_eq!(Mgrs::from_parts(33, 'U', 605004.0, 5344996.0, 6), Err(MgrsError::InvalidPrecision(6)));
+        assert_eq!(Mgrs::from_parts(61, 'U', 605004.0, 5344996.0, 5), Err(MgrsError::InvalidZone("61".to_string())));
+        assert_eq!(Mgrs::from_parts(0, 'U', 605004.0, 5344996.0, 5), Err(MgrsError::InvalidBand('U')));
+        assert_eq!(Mgrs::from_parts(33, 'U', -1.0, 5344996.0, 5), Err(MgrsError::OutOfGrid));
+        assert_eq!(Mgrs::from_parts(33, 'U', 905004.0, 5344996.0, 5), Err(MgrsError::OutOfGrid));
+        assert_eq!(Mgrs::from_parts(33, 'U', f64::NAN, 5344996.0, 5), Err(MgrsError::OutOfGrid));
+        assert_eq!(Mgrs::from_parts(0, 'Z', 1e7, 2000000.0, 5), Err(MgrsError::OutOfGrid));
+        assert_eq!(Mgrs::from_parts(33, 'U', 605004.0, 1000.0, 5), Err(MgrsError::SquareNotInBand { square: "XA".to_string(), band: 'U' }));
+        assert_eq!(MgrsError::SquareNotInBand { square: "XA".to_string(), band: 'U' }.to_string(), "100 km square XA is not in the latitude band U");
+    }
+}
@@ -1,6 +1,16 @@
 pub mod coord;
+pub mod freeform;
 pub mod latlon;
+pub mod latlonalt;
+pub mod gcj02;
+pub mod bbox;
+pub mod geohash;
+pub mod olc;
+pub mod maidenhead;
+pub mod cell;
 pub mod iso6709;
+pub mod nmea;
 pub mod nmea0183;
 pub mod zone;
 pub mod orientation;
+pub mod mgrs;
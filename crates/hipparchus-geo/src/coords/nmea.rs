@@ -0,0 +1,729 @@
+use std::str::FromStr;
+use crate::coords::latlon::LatLon;
+use crate::coords::orientation::Orientation;
+
+/// Meters per second in a knot.
+const KNOT: f64 = 1852.0 / 3600.0;
+
+/// Errors of parsing the NMEA 0183 sentences.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NmeaError
+{
+    /// The sentence is empty.
+    Empty,
+
+    /// The sentence does not start with '$'.
+    MissingStart,
+
+    /// The sentence does not end with the checksum '*hh'.
+    MissingChecksum,
+
+    /// The checksum is not a 2-digit hexadecimal number.
+    InvalidChecksum(String),
+
+    /// The checksum of the sentence differs from the computed one.
+    ChecksumMismatch { expected: u8, actual: u8 },
+
+    /// The address field is not a talker followed by a sentence type.
+    InvalidAddress(String),
+
+    /// The sentence type is not one of GGA, RMC, GLL, VTG and GSA.
+    UnsupportedSentence(String),
+
+    /// The sentence has fewer fields than required by its type.
+    TooFewFields { expected: usize, actual: usize },
+
+    /// The field cannot be parsed.
+    InvalidField { field: &'static str, value: String },
+}
+
+impl std::fmt::Display for NmeaError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self
+        {
+            NmeaError::Empty => write!(f, "NMEA sentence is empty"),
+            NmeaError::MissingStart => write!(f, "NMEA sentence does not start with '$'"),
+            NmeaError::MissingChecksum => write!(f, "NMEA sentence does not end with the checksum"),
+            NmeaError::InvalidChecksum(text) => write!(f, "invalid checksum '{text}'"),
+            NmeaError::ChecksumMismatch { expected, actual } => write!(f, "checksum {actual:02X} does not match {expected:02X}"),
+            NmeaError::InvalidAddress(text) => write!(f, "invalid address '{text}'"),
+            NmeaError::UnsupportedSentence(text) => write!(f, "unsupported sentence '{text}'"),
+            NmeaError::TooFewFields { expected, actual } => write!(f, "{actual} fields are fewer than {expected}"),
+            NmeaError::InvalidField { field, value } => write!(f, "invalid {field} '{value}'"),
+        }
+    }
+}
+
+impl std::error::Error for NmeaError {}
+
+/// UTC time of day in NMEA 0183 sentences.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct NmeaTime
+{
+    pub hour: u8,
+    pub minute: u8,
+    pub second: f64,
+}
+
+/// UTC date in NMEA 0183 sentences, with the 2-digit years mapped to 1980-2079.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct NmeaDate
+{
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+/// GGA: time, position and fix data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gga
+{
+    /// UTC time of the fix.
+    pub time: Option<NmeaTime>,
+
+    /// position of the fix.
+    pub position: Option<LatLon>,
+
+    /// fix quality, 0 for invalid, 1 for GPS, 2 for DGPS, 4 for RTK fixed, 5 for RTK float, etc.
+    pub quality: u8,
+
+    /// number of the satellites in use.
+    pub satellites: Option<u8>,
+
+    /// horizontal dilution of precision.
+    pub hdop: Option<f64>,
+
+    /// altitude above the mean sea level (meters).
+    pub altitude: Option<f64>,
+
+    /// height of the geoid above the ellipsoid (meters).
+    pub geoid_separation: Option<f64>,
+
+    /// age of the differential corrections (seconds).
+    pub dgps_age: Option<f64>,
+
+    /// identifier of the differential reference station.
+    pub dgps_station: Option<u16>,
+}
+
+/// RMC: recommended minimum specific GNSS data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rmc
+{
+    /// UTC time of the fix.
+    pub time: Option<NmeaTime>,
+
+    /// true if the data is valid ('A'), false if it is a warning ('V').
+    pub valid: bool,
+
+    /// position of the fix.
+    pub position: Option<LatLon>,
+
+    /// speed over ground (knots).
+    pub speed: Option<f64>,
+
+    /// course over ground from the true north (degrees).
+    pub course: Option<f64>,
+
+    /// UTC date of the fix.
+    pub date: Option<NmeaDate>,
+
+    /// magnetic variation, positive to the east (degrees).
+    pub magnetic_variation: Option<f64>,
+
+    /// FAA mode indicator since NMEA 2.3, e.g. 'A' for autonomous and 'D' for differential.
+    pub mode: Option<char>,
+}
+
+/// GLL: geographic position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gll
+{
+    /// position of the fix.
+    pub position: Option<LatLon>,
+
+    /// UTC time of the fix.
+    pub time: Option<NmeaTime>,
+
+    /// true if the data is valid ('A'), false if it is a warning ('V').
+    pub valid: bool,
+
+    /// FAA mode indicator since NMEA 2.3.
+    pub mode: Option<char>,
+}
+
+/// VTG: course and speed over ground.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vtg
+{
+    /// course over ground from the true north (degrees).
+    pub course: Option<f64>,
+
+    /// course over ground from the magnetic north (degrees).
+    pub course_magnetic: Option<f64>,
+
+    /// speed over ground (knots).
+    pub speed: Option<f64>,
+
+    /// speed over ground (km/h).
+    pub speed_kmh: Option<f64>,
+
+    /// FAA mode indicator since NMEA 2.3.
+    pub mode: Option<char>,
+}
+
+/// GSA: dilution of precision and active satellites.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gsa
+{
+    /// true if the 2D/3D mode is selected automatically ('A'), false if manually ('M').
+    pub automatic: bool,
+
+    /// fix type, 1 for no fix, 2 for 2D and 3 for 3D.
+    pub fix: u8,
+
+    /// PRNs of the satellites used in the fix, at most 12.
+    pub satellites: Vec<u8>,
+
+    /// position dilution of precision.
+    pub pdop: Option<f64>,
+
+    /// horizontal dilution of precision.
+    pub hdop: Option<f64>,
+
+    /// vertical dilution of precision.
+    pub vdop: Option<f64>,
+}
+
+/// The data of the supported NMEA 0183 sentences.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NmeaData
+{
+    Gga(Gga),
+    Rmc(Rmc),
+    Gll(Gll),
+    Vtg(Vtg),
+    Gsa(Gsa),
+}
+
+/// A NMEA 0183 sentence such as `$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47`.
+///
+/// The sentences are parsed with `FromStr` which validates the checksum, and emitted with `Display`
+/// which appends the checksum.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NmeaSentence
+{
+    /// talker identifier, e.g. "GP" for GPS and "GN" for the combined GNSS.
+    pub talker: String,
+
+    /// data of the sentence.
+    pub data: NmeaData,
+}
+
+impl NmeaSentence
+{
+    /// Create a new instance.
+    pub fn new(talker: &str, data: NmeaData) -> Self
+    {
+        Self { talker: talker.to_string(), data }
+    }
+
+    /// Get the checksum of the sentence between '$' and '*', the XOR of all the bytes.
+    pub fn checksum(payload: &str) -> u8
+    {
+        payload.bytes().fold(0, |c, b| c ^ b)
+    }
+
+    /// Get the position of the sentence if any.
+    pub fn position(&self) -> Option<LatLon>
+    {
+        match &self.data
+        {
+            NmeaData::Gga(gga) => gga.position,
+            NmeaData::Rmc(rmc) => rmc.position,
+            NmeaData::Gll(gll) => gll.position,
+            NmeaData::Vtg(_) | NmeaData::Gsa(_) => None,
+        }
+    }
+
+    /// Get the UTC time of the sentence if any.
+    pub fn time(&self) -> Option<NmeaTime>
+    {
+        match &self.data
+        {
+            NmeaData::Gga(gga) => gga.time,
+            NmeaData::Rmc(rmc) => rmc.time,
+            NmeaData::Gll(gll) => gll.time,
+            NmeaData::Vtg(_) | NmeaData::Gsa(_) => None,
+        }
+    }
+
+    fn payload(&self) -> String
+    {
+        let (kind, fields) = match &self.data
+        {
+            NmeaData::Gga(v) => ("GGA", vec!
+            [
+                fmt_time(v.time), fmt_position(v.position), v.quality.to_string(),
+                v.satellites.map_or(String::new(), |n| format!("{n:02}")), fmt_opt(v.hdop),
+                fmt_opt(v.altitude), "M".to_string(), fmt_opt(v.geoid_separation), "M".to_string(),
+                fmt_opt(v.dgps_age), v.dgps_station.map_or(String::new(), |n| format!("{n:04}")),
+            ]),
+            NmeaData::Rmc(v) => ("RMC", vec!
+            [
+                fmt_time(v.time), fmt_status(v.valid), fmt_position(v.position), fmt_opt(v.speed), fmt_opt(v.course),
+                v.date.map_or(String::new(), |d| format!("{:02}{:02}{:02}", d.day, d.month, d.year % 100)),
+                fmt_opt(v.magnetic_variation.map(f64::abs)),
+                v.magnetic_variation.map_or(String::new(), |m| if m < 0.0 { "W" } else { "E" }.to_string()),
+            ]),
+            NmeaData::Gll(v) => ("GLL", vec![fmt_position(v.position), fmt_time(v.time), fmt_status(v.valid)]),
+            NmeaData::Vtg(v) => ("VTG", vec!
+            [
+                fmt_opt(v.course), "T".to_string(), fmt_opt(v.course_magnetic), "M".to_string(),
+                fmt_opt(v.speed), "N".to_string(), fmt_opt(v.speed_kmh), "K".to_string(),
+            ]),
+            NmeaData::Gsa(v) =>
+            {
+                let mut fields = vec![if v.automatic { "A" } else { "M" }.to_string(), v.fix.to_string()];
+                fields.extend((0..12).map(|i| v.satellites.get(i).map_or(String::new(), |n| format!("{n:02}"))));
+                fields.extend([fmt_opt(v.pdop), fmt_opt(v.hdop), fmt_opt(v.vdop)]);
+                ("GSA", fields)
+            },
+        };
+        let mode = match &self.data
+        {
+            NmeaData::Rmc(Rmc { mode, .. }) | NmeaData::Gll(Gll { mode, .. }) | NmeaData::Vtg(Vtg { mode, .. }) => *mode,
+            _ => None,
+        };
+        let mut text = format!("{}{kind},{}", self.talker, fields.join(","));
+        if let Some(mode) = mode
+        {
+            text += &format!(",{mode}");
+        }
+        text
+    }
+}
+
+impl std::fmt::Display for NmeaSentence
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        let payload = self.payload();
+        write!(f, "${payload}*{:02X}", Self::checksum(&payload))
+    }
+}
+
+impl FromStr for NmeaSentence
+{
+    type Err = NmeaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let s = s.trim();
+        if s.is_empty()
+        {
+            return Err(NmeaError::Empty);
+        }
+        let s = s.strip_prefix('$').ok_or(NmeaError::MissingStart)?;
+        let (payload, checksum) = s.rsplit_once('*').ok_or(NmeaError::MissingChecksum)?;
+        let actual = match checksum.len()
+        {
+            2 => u8::from_str_radix(checksum, 16).map_err(|_| NmeaError::InvalidChecksum(checksum.to_string()))?,
+            _ => return Err(NmeaError::InvalidChecksum(checksum.to_string())),
+        };
+        let expected = Self::checksum(payload);
+        if expected != actual
+        {
+            return Err(NmeaError::ChecksumMismatch { expected, actual });
+        }
+
+        let mut fields = payload.split(',');
+        let address = fields.next().unwrap_or("");
+        if address.len() < 5 || !address.bytes().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+        {
+            return Err(NmeaError::InvalidAddress(address.to_string()));
+        }
+        let (talker, kind) = address.split_at(address.len() - 3);
+        let f = Fields(fields.collect());
+        let data = match kind
+        {
+            "GGA" =>
+            {
+                f.require(14)?;
+                NmeaData::Gga(Gga
+                {
+                    time: f.time(0)?,
+                    position: f.position(1)?,
+                    quality: f.value(5, "fix quality")?.unwrap_or(0),
+                    satellites: f.value(6, "number of satellites")?,
+                    hdop: f.value(7, "HDOP")?,
+                    altitude: f.value(8, "altitude")?,
+                    geoid_separation: f.value(10, "geoid separation")?,
+                    dgps_age: f.value(12, "age of DGPS")?,
+                    dgps_station: f.value(13, "DGPS station")?,
+                })
+            },
+            "RMC" =>
+            {
+                f.require(11)?;
+                let variation: Option<f64> = f.value(9, "magnetic variation")?;
+                NmeaData::Rmc(Rmc
+                {
+                    time: f.time(0)?,
+                    valid: f.status(1)?,
+                    position: f.position(2)?,
+                    speed: f.value(6, "speed")?,
+                    course: f.value(7, "course")?,
+                    date: f.date(8)?,
+                    magnetic_variation: match (variation, f.get(10))
+                    {
+                        (Some(v), "W") => Some(-v),
+                        (Some(v), "E" | "") => Some(v),
+                        (Some(_), dir) => return Err(f.invalid("magnetic variation direction", dir)),
+                        (None, _) => None,
+                    },
+                    mode: f.mode(11)?,
+                })
+            },
+            "GLL" =>
+            {
+                f.require(6)?;
+                NmeaData::Gll(Gll { position: f.position(0)?, time: f.time(4)?, valid: f.status(5)?, mode: f.mode(6)? })
+            },
+            "VTG" =>
+            {
+                f.require(8)?;
+                NmeaData::Vtg(Vtg
+                {
+                    course: f.value(0, "course")?,
+                    course_magnetic: f.value(2, "magnetic course")?,
+                    speed: f.value(4, "speed")?,
+                    speed_kmh: f.value(6, "speed")?,
+                    mode: f.mode(8)?,
+                })
+            },
+            "GSA" =>
+            {
+                f.require(17)?;
+                let automatic = match f.get(0)
+                {
+                    "A" => true,
+                    "M" => false,
+                    text => return Err(f.invalid("selection mode", text)),
+                };
+                let mut satellites = Vec::new();
+                for i in 2..14
+                {
+                    if let Some(prn) = f.value(i, "satellite")?
+                    {
+                        satellites.push(prn);
+                    }
+                }
+                NmeaData::Gsa(Gsa
+                {
+                    automatic,
+                    fix: f.value(1, "fix type")?.unwrap_or(1),
+                    satellites,
+                    pdop: f.value(14, "PDOP")?,
+                    hdop: f.value(15, "HDOP")?,
+                    vdop: f.value(16, "VDOP")?,
+                })
+            },
+            _ => return Err(NmeaError::UnsupportedSentence(kind.to_string())),
+        };
+        Ok(Self::new(talker, data))
+    }
+}
+
+impl Rmc
+{
+    /// Get the speed over ground (meters per second).
+    pub fn speed_mps(&self) -> Option<f64>
+    {
+        self.speed.map(|v| v * KNOT)
+    }
+}
+
+impl Vtg
+{
+    /// Get the speed over ground (meters per second), from knots or km/h.
+    pub fn speed_mps(&self) -> Option<f64>
+    {
+        self.speed.map(|v| v * KNOT).or(self.speed_kmh.map(|v| v / 3.6))
+    }
+}
+
+/// The fields of a sentence after the address.
+struct Fields<'a>(Vec<&'a str>);
+
+impl<'a> Fields<'a>
+{
+    fn get(&self, i: usize) -> &'a str
+    {
+        self.0.get(i).copied().unwrap_or("")
+    }
+
+    fn invalid(&self, field: &'static str, value: &str) -> NmeaError
+    {
+        NmeaError::InvalidField { field, value: value.to_string() }
+    }
+
+    fn require(&self, expected: usize) -> Result<(), NmeaError>
+    {
+        if self.0.len() < expected { Err(NmeaError::TooFewFields { expected, actual: self.0.len() }) } else { Ok(()) }
+    }
+
+    fn value<T: FromStr>(&self, i: usize, field: &'static str) -> Result<Option<T>, NmeaError>
+    {
+        match self.get(i)
+        {
+            "" => Ok(None),
+            text => text.parse().map(Some).map_err(|_| self.invalid(field, text)),
+        }
+    }
+
+    fn status(&self, i: usize) -> Result<bool, NmeaError>
+    {
+        match self.get(i)
+        {
+            "A" => Ok(true),
+            "V" => Ok(false),
+            text => Err(self.invalid("status", text)),
+        }
+    }
+
+    fn mode(&self, i: usize) -> Result<Option<char>, NmeaError>
+    {
+        let text = self.get(i);
+        let mut chars = text.chars();
+        match (chars.next(), chars.next())
+        {
+            (None, _) => Ok(None),
+            (Some(c), None) if c.is_ascii_uppercase() => Ok(Some(c)),
+            _ => Err(self.invalid("mode", text)),
+        }
+    }
+
+    fn time(&self, i: usize) -> Result<Option<NmeaTime>, NmeaError>
+    {
+        let text = self.get(i);
+        if text.is_empty()
+        {
+            return Ok(None);
+        }
+        let field = |r: std::ops::Range<usize>| text.get(r).and_then(|v| v.parse::<u8>().ok());
+        match (field(0..2), field(2..4), text.get(4..).and_then(|v| v.parse::<f64>().ok()))
+        {
+            (Some(hour), Some(minute), Some(second)) if hour < 24 && minute < 60 && (0.0..61.0).contains(&second) =>
+                Ok(Some(NmeaTime { hour, minute, second })),
+            _ => Err(self.invalid("time", text)),
+        }
+    }
+
+    fn date(&self, i: usize) -> Result<Option<NmeaDate>, NmeaError>
+    {
+        let text = self.get(i);
+        if text.is_empty()
+        {
+            return Ok(None);
+        }
+        let field = |r: std::ops::Range<usize>| text.get(r).and_then(|v| v.parse::<u8>().ok());
+        match (text.len(), field(0..2), field(2..4), field(4..6))
+        {
+            (6, Some(day), Some(month), Some(yy)) if (1..=31).contains(&day) && (1..=12).contains(&month) =>
+            {
+                let year = if yy < 80 { 2000 } else { 1900 } + yy as u16;
+                Ok(Some(NmeaDate { year, month, day }))
+            },
+            _ => Err(self.invalid("date", text)),
+        }
+    }
+
+    fn position(&self, i: usize) -> Result<Option<LatLon>, NmeaError>
+    {
+        let lat = self.angle(i, "latitude", 90.0, [Orientation::North, Orientation::South])?;
+        let lon = self.angle(i + 2, "longitude", 180.0, [Orientation::East, Orientation::West])?;
+        match (lat, lon)
+        {
+            (Some(lat), Some(lon)) => Ok(Some(LatLon::new(lat, lon))),
+            (None, None) => Ok(None),
+            _ => Err(self.invalid("position", &format!("{},{},{},{}", self.get(i), self.get(i + 1), self.get(i + 2), self.get(i + 3)))),
+        }
+    }
+
+    /// Parse the angle in the format of `dddmm.mmm` and its hemisphere.
+    fn angle(&self, i: usize, field: &'static str, max: f64, hemispheres: [Orientation; 2]) -> Result<Option<f64>, NmeaError>
+    {
+        let (text, hemisphere) = (self.get(i), self.get(i + 1));
+        if text.is_empty() && hemisphere.is_empty()
+        {
+            return Ok(None);
+        }
+        let value: f64 = text.parse().map_err(|_| self.invalid(field, text))?;
+        let deg = (value / 100.0).trunc();
+        let min = value - deg * 100.0;
+        if !(0.0..60.0).contains(&min) || deg + min / 60.0 > max || text.starts_with(['+', '-'])
+        {
+            return Err(self.invalid(field, text));
+        }
+        match Orientation::from_str(hemisphere)
+        {
+            Ok(o) if o == hemispheres[0] => Ok(Some(deg + min / 60.0)),
+            Ok(o) if o == hemispheres[1] => Ok(Some(-(deg + min / 60.0))),
+            _ => Err(self.invalid(field, hemisphere)),
+        }
+    }
+}
+
+fn fmt_opt(v: Option<f64>) -> String
+{
+    v.map_or(String::new(), |v| v.to_string())
+}
+
+fn fmt_time(t: Option<NmeaTime>) -> String
+{
+    t.map_or(String::new(), |t| format!("{:02}{:02}{:05.2}", t.hour, t.minute, t.second))
+}
+
+fn fmt_status(valid: bool) -> String
+{
+    if valid { "A" } else { "V" }.to_string()
+}
+
+fn fmt_position(p: Option<LatLon>) -> String
+{
+    p.map_or(",,,".to_string(), |p|
+    {
+        let lat = fmt_angle(p.latitude(), 2, ['N', 'S']);
+        let lon = fmt_angle(p.longitude(), 3, ['E', 'W']);
+        format!("{lat},{lon}")
+    })
+}
+
+/// Format the angle in the format of `dddmm.mmmmm` and its hemisphere, with the trailing zeros
+/// of the minutes trimmed down to 3 decimal places.
+fn fmt_angle(v: f64, width: usize, hemispheres: [char; 2]) -> String
+{
+    // Round in the units of 1e-5 minute so that the carry goes into the minutes and degrees.
+    const SCALE: u64 = 100_000;
+    let units = (v.abs() * 60.0 * SCALE as f64).round() as u64;
+    let (deg, min, frac) = (units / (60 * SCALE), units / SCALE % 60, units % SCALE);
+    let text = format!("{deg:0width$}{min:02}.{frac:05}");
+    let len = text.trim_end_matches('0').len().max(width + 6);
+    let hemisphere = if v < 0.0 && units > 0 { hemispheres[1] } else { hemispheres[0] };
+    format!("{},{hemisphere}", &text[..len])
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use rstest::*;
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_gga()
+    {
+        let s: NmeaSentence = "$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47".parse().unwrap();
+        assert_eq!(s.talker, "GP");
+        let NmeaData::Gga(gga) = &s.data else { panic!("not GGA") };
+        assert_eq!(gga.time, Some(NmeaTime { hour: 12, minute: 35, second: 19.0 }));
+        let p = s.position().unwrap();
+        assert_approx_eq!(f64, p.latitude(), 48.1173, epsilon = 1e-12);
+        assert_approx_eq!(f64, p.longitude(), 11.516666666667, epsilon = 1e-12);
+        assert_eq!((gga.quality, gga.satellites, gga.hdop), (1, Some(8), Some(0.9)));
+        assert_eq!((gga.altitude, gga.geoid_separation), (Some(545.4), Some(46.9)));
+        assert_eq!((gga.dgps_age, gga.dgps_station), (None, None));
+        assert_eq!(s.to_string(), "$GPGGA,123519.00,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*69");
+    }
+
+    #[test]
+    fn test_rmc()
+    {
+        let s: NmeaSentence = "$GPRMC,123519,A,4807.038,N,01131.000,E,022.4,084.4,230394,003.1,W*6A".parse().unwrap();
+        let NmeaData::Rmc(rmc) = &s.data else { panic!("not RMC") };
+        assert!(rmc.valid);
+        assert_eq!(rmc.date, Some(NmeaDate { year: 1994, month: 3, day: 23 }));
+        assert_eq!((rmc.speed, rmc.course, rmc.magnetic_variation, rmc.mode), (Some(22.4), Some(84.4), Some(-3.1), None));
+        assert_approx_eq!(f64, rmc.speed_mps().unwrap(), 11.523555555556, epsilon = 1e-12);
+        assert_eq!(s.time(), Some(NmeaTime { hour: 12, minute: 35, second: 19.0 }));
+
+        let s: NmeaSentence = "$GNRMC,001031.00,V,,,,,,,060180,,,N*6F".parse().unwrap();
+        let NmeaData::Rmc(rmc) = &s.data else { panic!("not RMC") };
+        assert!(!rmc.valid);
+        assert_eq!((rmc.position, rmc.date.map(|d| d.year), rmc.mode), (None, Some(1980), Some('N')));
+    }
+
+    #[test]
+    fn test_gll_vtg_gsa()
+    {
+        let s: NmeaSentence = "$GPGLL,4916.45,N,12311.12,W,225444,A,*1D".parse().unwrap();
+        let NmeaData::Gll(gll) = &s.data else { panic!("not GLL") };
+        assert!(gll.valid);
+        assert_approx_eq!(f64, gll.position.unwrap().latitude(), 49.274166666667, epsilon = 1e-12);
+        assert_approx_eq!(f64, gll.position.unwrap().longitude(), -123.18533333333, epsilon = 1e-11);
+
+        let s: NmeaSentence = "$GPVTG,054.7,T,034.4,M,005.5,N,010.2,K*48".parse().unwrap();
+        let NmeaData::Vtg(vtg) = &s.data else { panic!("not VTG") };
+        assert_eq!((vtg.course, vtg.course_magnetic, vtg.speed, vtg.speed_kmh), (Some(54.7), Some(34.4), Some(5.5), Some(10.2)));
+        assert_approx_eq!(f64, vtg.speed_mps().unwrap(), 5.5 * KNOT);
+
+        let s: NmeaSentence = "$GPGSA,A,3,04,05,,09,12,,,24,,,,,2.5,1.3,2.1*39".parse().unwrap();
+        let NmeaData::Gsa(gsa) = &s.data else { panic!("not GSA") };
+        assert!(gsa.automatic);
+        assert_eq!(gsa.fix, 3);
+        assert_eq!(gsa.satellites, vec![4, 5, 9, 12, 24]);
+        assert_eq!((gsa.pdop, gsa.hdop, gsa.vdop), (Some(2.5), Some(1.3), Some(2.1)));
+    }
+
+    #[rstest]
+    #[case("$GPGGA,123519.00,4807.038,N,01131.000,E,2,12,0.8,545.4,M,46.9,M,1.5,0120")]
+    #[case("$GNRMC,235959.50,A,3354.529,S,15112.000,E,0.5,359.9,311299,1.2,E,D")]
+    #[case("$GPGLL,,,,,000000.00,V,N")]
+    #[case("$GPVTG,,T,,M,0,N,0,K,A")]
+    #[case("$GPGSA,M,2,01,02,03,04,05,06,07,08,09,10,11,12,1.1,0.9,0.7")]
+    fn test_round_trip(#[case] payload: &str)
+    {
+        let text = format!("{payload}*{:02X}", NmeaSentence::checksum(&payload[1..]));
+        let s: NmeaSentence = text.parse().unwrap();
+        assert_eq!(s.to_string(), text);
+    }
+
+    #[rstest]
+    #[case(48.0 + 59.99999 / 60.0, 123.0 + 59.999996 / 60.0, "4859.99999,N,12400.000,E")]
+    #[case(-(48.0 + 59.999999 / 60.0), -(179.0 + 59.999999 / 60.0), "4900.000,S,18000.000,W")]
+    #[case(12.0 + 34.56789 / 60.0, -(1.0 + 0.0001 / 60.0), "1234.56789,N,00100.0001,W")]
+    #[case(-0.000000001, 0.0, "0000.000,N,00000.000,E")]
+    fn test_position(#[case] lat: f64, #[case] lon: f64, #[case] expected: &str)
+    {
+        let p = LatLon::new(lat, lon);
+        assert_eq!(fmt_position(Some(p)), expected);
+        let data = NmeaData::Gll(Gll { position: Some(p), time: None, valid: true, mode: None });
+        let s = NmeaSentence { talker: "GP".to_string(), data };
+        let parsed: NmeaSentence = s.to_string().parse().unwrap();
+        let q = parsed.position().unwrap();
+        assert_approx_eq!(f64, q.latitude(), lat, epsilon = 1e-7);
+        assert_approx_eq!(f64, q.longitude(), lon, epsilon = 1e-7);
+        assert_eq!(parsed.to_string(), s.to_string());
+    }
+
+    #[rstest]
+    #[case("", NmeaError::Empty)]
+    #[case("GPGLL,4916.45,N,12311.12,W,225444,A,*1D", NmeaError::MissingStart)]
+    #[case("$GPGLL,4916.45,N,12311.12,W,225444,A,", NmeaError::MissingChecksum)]
+    #[case("$GPGLL,4916.45,N,12311.12,W,225444,A,*1G", NmeaError::InvalidChecksum("1G".to_string()))]
+    #[case("$GPGLL,4916.45,N,12311.12,W,225444,A,*1E", NmeaError::ChecksumMismatch { expected: 0x1D, actual: 0x1E })]
+    #[case("$GP*17", NmeaError::InvalidAddress("GP".to_string()))]
+    #[case("$GPGSV,1*48", NmeaError::UnsupportedSentence("GSV".to_string()))]
+    #[case("$GPGLL,4916.45,N*3B", NmeaError::TooFewFields { expected: 6, actual: 2 })]
+    #[case("$GPGLL,4966.45,N,12311.12,W,225444,A,*1A", NmeaError::InvalidField { field: "latitude", value: "4966.45".to_string() })]
+    #[case("$GPGLL,4916.45,E,12311.12,W,225444,A,*16", NmeaError::InvalidField { field: "latitude", value: "E".to_string() })]
+    #[case("$GPGLL,4916.45,N,12311.12,W,245444,A,*1B", NmeaError::InvalidField { field: "time", value: "245444".to_string() })]
+    #[case("$GPGLL,4916.45,N,12311.12,W,225444,X,*04", NmeaError::InvalidField { field: "status", value: "X".to_string() })]
+    fn test_errors(#[case] text: &str, #[case] expected: NmeaError)
+    {
+        assert_eq!(text.parse::<NmeaSentence>(), Err(expected));
+    }
+}
@@ -0,0 +1,435 @@
+use crate::coords::bbox::BoundingBox;
+use crate::coords::latlon::LatLon;
+
+/// The base20 alphabet of the Open Location Code without vowels and easily confused characters.
+const ALPHABET: &[u8; 20] = b"23456789CFGHJMPQRVWX";
+
+/// The separator after the 8th digit.
+const SEPARATOR: char = '+';
+
+/// The position of the separator in the full codes.
+const SEPARATOR_POSITION: usize = 8;
+
+/// The padding of the full codes shorter than 8 digits.
+const PADDING: char = '0';
+
+/// The number of digits of the pairs of latitude and longitude.
+const PAIR_LENGTH: usize = 10;
+
+/// The number of rows of the grid digits after the pairs.
+const GRID_ROWS: i64 = 5;
+
+/// The number of columns of the grid digits after the pairs.
+const GRID_COLUMNS: i64 = 4;
+
+/// The number of the integer steps per degree at the end of the pairs.
+const PAIR_PRECISION: i64 = 8000;
+
+/// The number of the integer steps per degree of latitude at the maximum length.
+const FINAL_LAT_PRECISION: i64 = PAIR_PRECISION * 3125;
+
+/// The number of the integer steps per degree of longitude at the maximum length.
+const FINAL_LON_PRECISION: i64 = PAIR_PRECISION * 1024;
+
+/// The sizes of the cells of the pairs (degrees).
+const PAIR_RESOLUTIONS: [f64; 5] = [20.0, 1.0, 0.05, 0.0025, 0.000125];
+
+/// Errors of the Open Location Code conversions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OlcError
+{
+    /// The length is not in [2, 15] or is odd below 10.
+    InvalidLength(usize),
+
+    /// The text is not a valid code.
+    InvalidCode(String),
+
+    /// The code is valid but not a full code.
+    NotFull(String),
+
+    /// The padded code can not be shortened.
+    Padded(String),
+}
+
+impl std::fmt::Display for OlcError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self
+        {
+            OlcError::InvalidLength(length) => write!(f, "open location code length {length} is not valid"),
+            OlcError::InvalidCode(code) => write!(f, "invalid open location code '{code}'"),
+            OlcError::NotFull(code) => write!(f, "open location code '{code}' is not a full code"),
+            OlcError::Padded(code) => write!(f, "padded open location code '{code}' can not be shortened"),
+        }
+    }
+}
+
+impl std::error::Error for OlcError {}
+
+/// A full Open Location Code (Plus Code), e.g. "849VCWC8+R9".
+///
+/// The short codes such as "CWC8+R9" are not full codes and are recovered against a reference
+/// location nearby, and the full codes are shortened the same way.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OpenLocationCode
+{
+    code: String,
+}
+
+impl OpenLocationCode
+{
+    /// The default length in digits, about 14 m by 14 m.
+    pub const DEFAULT_LENGTH: usize = PAIR_LENGTH;
+
+    /// The maximum length in digits, below 1 mm; longer lengths are clamped.
+    pub const MAX_LENGTH: usize = 15;
+
+    /// Encode the point with the length in digits, which is even below 10.
+    pub fn new(p: LatLon, length: usize) -> Result<Self, OlcError>
+    {
+        if length < 2 || length < PAIR_LENGTH && length % 2 == 1
+        {
+            return Err(OlcError::InvalidLength(length));
+        }
+        let length = length.min(Self::MAX_LENGTH);
+
+        // The integers of the final precision, clamped below the north pole and wrapped at the antimeridian.
+        let steps = |v: f64, precision: i64| ((v * precision as f64 * 1e6).round() / 1e6).floor() as i64;
+        let mut lat = (steps(p.latitude(), FINAL_LAT_PRECISION) + 90 * FINAL_LAT_PRECISION).clamp(0, 180 * FINAL_LAT_PRECISION - 1);
+        let mut lon = (steps(p.longitude(), FINAL_LON_PRECISION) + 180 * FINAL_LON_PRECISION).rem_euclid(360 * FINAL_LON_PRECISION);
+
+        let mut digits = Vec::with_capacity(Self::MAX_LENGTH);
+        for _ in PAIR_LENGTH..Self::MAX_LENGTH
+        {
+            digits.push(ALPHABET[((lat % GRID_ROWS) * GRID_COLUMNS + lon % GRID_COLUMNS) as usize]);
+            lat /= GRID_ROWS;
+            lon /= GRID_COLUMNS;
+        }
+        for _ in 0..PAIR_LENGTH / 2
+        {
+            digits.push(ALPHABET[(lon % 20) as usize]);
+            digits.push(ALPHABET[(lat % 20) as usize]);
+            lat /= 20;
+            lon /= 20;
+        }
+        digits.reverse();
+        digits.truncate(length);
+
+        let mut code: String = digits.iter().map(|&b| b as char).collect();
+        while code.len() < SEPARATOR_POSITION
+        {
+            code.push(PADDING);
+        }
+        code.insert(SEPARATOR_POSITION, SEPARATOR);
+        Ok(Self { code })
+    }
+
+    /// Check if the text is a valid full or short code.
+    pub fn is_valid(code: &str) -> bool
+    {
+        let Some(separator) = code.find(SEPARATOR) else { return false };
+        if code.matches(SEPARATOR).count() > 1 || separator > SEPARATOR_POSITION || separator % 2 == 1
+        {
+            return false;
+        }
+        if let Some(padding) = code.find(PADDING)
+        {
+            // Only the full codes are padded, with an even number of padding ending at the separator.
+            let end = code.rfind(PADDING).unwrap() + 1;
+            if separator < SEPARATOR_POSITION || padding == 0 || (end - padding) % 2 == 1
+                || !code[padding..end].chars().all(|c| c == PADDING) || !code.ends_with(SEPARATOR)
+            {
+                return false;
+            }
+        }
+        code.len() > 1 && code.len() - separator - 1 != 1
+            && code.chars().all(|c| c == SEPARATOR || c == PADDING || digit(c).is_some())
+    }
+
+    /// Check if the text is a valid short code, with the leading digits removed.
+    pub fn is_short(code: &str) -> bool
+    {
+        Self::is_valid(code) && code.find(SEPARATOR).is_some_and(|separator| separator < SEPARATOR_POSITION)
+    }
+
+    /// Check if the text is a valid full code, within the ranges of latitude and longitude.
+    pub fn is_full(code: &str) -> bool
+    {
+        if !Self::is_valid(code) || Self::is_short(code)
+        {
+            return false;
+        }
+        let mut chars = code.chars();
+        let lat = chars.next().and_then(digit).unwrap_or(0);
+        let lon = chars.next().and_then(digit).unwrap_or(0);
+        lat * 20 < 180 && lon * 20 < 360
+    }
+
+    /// Get the code text.
+    pub fn as_str(&self) -> &str
+    {
+        &self.code
+    }
+
+    /// Get the length in digits, excluding the separator and padding.
+    pub fn length(&self) -> usize
+    {
+        self.code.chars().filter(|&c| c != SEPARATOR && c != PADDING).count()
+    }
+
+    /// Get the bounds of the code area.
+    pub fn bounds(&self) -> BoundingBox
+    {
+        let digits: Vec<i64> = self.code.chars().filter_map(digit).collect();
+        let pairs = digits.len().min(PAIR_LENGTH);
+        let (mut lat, mut lon) = (-90 * PAIR_PRECISION, -180 * PAIR_PRECISION);
+        let mut place = 20i64.pow(4);
+        for i in (0..pairs).step_by(2)
+        {
+            lat += digits[i] * place;
+            lon += digits[i + 1] * place;
+            if i < pairs - 2
+            {
+                place /= 20;
+            }
+        }
+        let (mut south, mut west) = (lat as f64 / PAIR_PRECISION as f64, lon as f64 / PAIR_PRECISION as f64);
+        let (mut height, mut width) = (place as f64 / PAIR_PRECISION as f64, place as f64 / PAIR_PRECISION as f64);
+        if digits.len() > PAIR_LENGTH
+        {
+            let (mut row, mut col) = (GRID_ROWS.pow(4), GRID_COLUMNS.pow(4));
+            let (mut lat, mut lon) = (0, 0);
+            for (i, &d) in digits.iter().enumerate().skip(PAIR_LENGTH)
+            {
+                lat += d / GRID_COLUMNS * row;
+                lon += d % GRID_COLUMNS * col;
+                if i < digits.len() - 1
+                {
+                    row /= GRID_ROWS;
+                    col /= GRID_COLUMNS;
+                }
+            }
+            south += lat as f64 / FINAL_LAT_PRECISION as f64;
+            west += lon as f64 / FINAL_LON_PRECISION as f64;
+            height = row as f64 / FINAL_LAT_PRECISION as f64;
+            width = col as f64 / FINAL_LON_PRECISION as f64;
+        }
+        BoundingBox::new(south, west, south + height, west + width)
+    }
+
+    /// Get the centre of the code area.
+    pub fn center(&self) -> LatLon
+    {
+        let b = self.bounds();
+        LatLon::new(((b.south + b.north) / 2.0).min(90.0), ((b.west + b.east) / 2.0).min(180.0))
+    }
+
+    /// Remove as many leading digits as possible so that the short code is recovered to this code near the reference.
+    ///
+    /// The code is returned unchanged if the reference is too far away.
+    pub fn shorten(&self, reference: LatLon) -> Result<String, OlcError>
+    {
+        if self.code.contains(PADDING)
+        {
+            return Err(OlcError::Padded(self.code.clone()));
+        }
+        let c = self.center();
+        let range = (c.latitude() - reference.latitude()).abs().max((c.longitude() - reference.longitude()).abs());
+        for i in (1..PAIR_RESOLUTIONS.len() - 1).rev()
+        {
+            // Keep a margin of the cell size since the recovery is centred at the reference.
+            if range < PAIR_RESOLUTIONS[i] * 0.3
+            {
+                return Ok(self.code[(i + 1) * 2..].to_string());
+            }
+        }
+        Ok(self.code.clone())
+    }
+
+    /// Recover the full code nearest to the reference from the short code, or parse the full code as is.
+    pub fn recover(code: &str, reference: LatLon) -> Result<Self, OlcError>
+    {
+        if !Self::is_short(code)
+        {
+            return code.parse();
+        }
+        let code = code.to_ascii_uppercase();
+        let removed = SEPARATOR_POSITION - code.find(SEPARATOR).unwrap();
+        let resolution = 20f64.powf(2.0 - removed as f64 / 2.0);
+        let prefix = Self::new(reference, PAIR_LENGTH)?;
+        let area = format!("{}{}", &prefix.code[..removed], code).parse::<Self>()?;
+
+        // Move the area by one cell if the reference is closer to the cell next to it.
+        let (lat, lon) = (reference.latitude(), reference.longitude());
+        let c = area.center();
+        let (mut clat, mut clon) = (c.latitude(), c.longitude());
+        if lat + resolution / 2.0 < clat && clat - resolution >= -90.0
+        {
+            clat -= resolution;
+        }
+        else if lat - resolution / 2.0 > clat && clat + resolution <= 90.0
+        {
+            clat += resolution;
+        }
+        if lon + resolution / 2.0 < clon
+        {
+            clon -= resolution;
+        }
+        else if lon - resolution / 2.0 > clon
+        {
+            clon += resolution;
+        }
+        Self::new(LatLon::new(clat, clon), area.length())
+    }
+}
+
+impl std::fmt::Display for OpenLocationCode
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        write!(f, "{}", self.code)
+    }
+}
+
+impl std::str::FromStr for OpenLocationCode
+{
+    type Err = OlcError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let code = s.trim().to_ascii_uppercase();
+        if !Self::is_valid(&code)
+        {
+            return Err(OlcError::InvalidCode(s.to_string()));
+        }
+        if !Self::is_full(&code)
+        {
+            return Err(OlcError::NotFull(s.to_string()));
+        }
+        Ok(Self { code })
+    }
+}
+
+/// Get the value of the digit of the alphabet in either case.
+fn digit(c: char) -> Option<i64>
+{
+    ALPHABET.iter().position(|&b| b as char == c.to_ascii_uppercase()).map(|i| i as i64)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use rstest::*;
+    use float_cmp::assert_approx_eq;
+
+    #[rstest]
+    #[case(20.375, 2.775, 6, "7FG49Q00+")]
+    #[case(20.3700625, 2.7821875, 10, "7FG49QCJ+2V")]
+    #[case(20.3701125, 2.782234375, 11, "7FG49QCJ+2VX")]
+    #[case(47.0000625, 8.0000625, 10, "8FVC2222+22")]
+    #[case(-41.2730625, 174.7859375, 10, "4VCPPQGP+Q9")]
+    #[case(0.5, -179.5, 4, "62G20000+")]
+    #[case(-89.5, -179.5, 4, "22220000+")]
+    #[case(0.5, 179.5, 4, "6VGX0000+")]
+    #[case(1.0, 1.0, 11, "6FH32222+222")]
+    #[case(90.0, 1.0, 4, "CFX30000+")]
+    #[case(1.0, 180.0, 4, "62H20000+")]
+    #[case(37.4220625, -122.0840625, 10, "849VCWC8+R9")]
+    fn test_encode(#[case] lat: f64, #[case] lon: f64, #[case] length: usize, #[case] expected: &str)
+    {
+        let code = OpenLocationCode::new(LatLon::new(lat, lon), length).unwrap();
+        assert_eq!(code.as_str(), expected);
+        assert_eq!(code.length(), length);
+        assert!(OpenLocationCode::is_full(expected));
+        assert_eq!(expected.to_lowercase().parse::<OpenLocationCode>(), Ok(code));
+    }
+
+    #[rstest]
+    #[case("7FG49Q00+", 20.35, 2.75, 20.4, 2.8)]
+    #[case("7FG49QCJ+2VX", 20.3701, 2.78221875, 20.370125, 2.78225)]
+    #[case("849VCWC8+R9", 37.422, -122.084125, 37.422125, -122.084)]
+    fn test_decode(#[case] text: &str, #[case] south: f64, #[case] west: f64, #[case] north: f64, #[case] east: f64)
+    {
+        let code: OpenLocationCode = text.parse().unwrap();
+        let b = code.bounds();
+        assert_approx_eq!(f64, b.south, south, epsilon = 1e-10);
+        assert_approx_eq!(f64, b.west, west, epsilon = 1e-10);
+        assert_approx_eq!(f64, b.north, north, epsilon = 1e-10);
+        assert_approx_eq!(f64, b.east, east, epsilon = 1e-10);
+        assert_eq!(OpenLocationCode::new(code.center(), code.length()), Ok(code));
+    }
+
+    #[rstest]
+    #[case("9C3W9QCJ+2VX", 51.3701125, -1.217765625, "+2VX")]
+    #[case("9C3W9QCJ+2VX", 51.3708675, -1.217765625, "CJ+2VX")]
+    #[case("9C3W9QCJ+2VX", 51.38, -1.3, "9QCJ+2VX")]
+    #[case("9C3W9QCJ+2VX", 54.0, -1.2, "9C3W9QCJ+2VX")]
+    #[case("849VCWC8+R9", 37.4, -122.1, "CWC8+R9")]
+    fn test_shorten(#[case] text: &str, #[case] lat: f64, #[case] lon: f64, #[case] short: &str)
+    {
+        let reference = LatLon::new(lat, lon);
+        let code: OpenLocationCode = text.parse().unwrap();
+        assert_eq!(code.shorten(reference).unwrap(), short);
+        assert_eq!(OpenLocationCode::recover(short, reference), Ok(code));
+    }
+
+    #[rstest]
+    #[case("9G8F+6X", 47.4, 8.6, "8FVC9G8F+6X")]
+    #[case("8F+GG", 47.4, 8.6, "8FVCCJ8F+GG")]
+    #[case("2222+22", -89.6, 0.0, "2F222222+22")]
+    #[case("XXXX+XX", 89.6, -179.9, "CVXXXXXX+XX")]
+    #[case("2222+22", 0.0, 179.99, "62G22222+22")]
+    fn test_recover(#[case] short: &str, #[case] lat: f64, #[case] lon: f64, #[case] expected: &str)
+    {
+        let code = OpenLocationCode::recover(short, LatLon::new(lat, lon)).unwrap();
+        assert_eq!(code.as_str(), expected);
+    }
+
+    #[rstest]
+    #[case("8FWC2345+G6", true, false, true)]
+    #[case("8FWC2345+G6G", true, false, true)]
+    #[case("8fwc2345+", true, false, true)]
+    #[case("8FWCX400+", true, false, true)]
+    #[case("WC2345+G6g", true, true, false)]
+    #[case("2345+G6", true, true, false)]
+    #[case("45+G6", true, true, false)]
+    #[case("+G6", true, true, false)]
+    #[case("G+", false, false, false)]
+    #[case("+", false, false, false)]
+    #[case("8FWC2345+G", false, false, false)]
+    #[case("8FWC2_45+G6", false, false, false)]
+    #[case("8FWC2η45+G6", false, false, false)]
+    #[case("8FWC2345+G6+", false, false, false)]
+    #[case("8FWC2345G6+", false, false, false)]
+    #[case("8FWC2300+G6", false, false, false)]
+    #[case("WC2300+G6g", false, false, false)]
+    #[case("WC2345+G", false, false, false)]
+    #[case("WC2300+", false, false, false)]
+    #[case("C2345+G6", false, false, false)]
+    #[case("8FWC2345", false, false, false)]
+    #[case("22222222+22", true, false, true)]
+    #[case("FFFFFFFF+", true, false, false)]
+    #[case("2XXX0000+", true, false, false)]
+    fn test_validation(#[case] code: &str, #[case] valid: bool, #[case] short: bool, #[case] full: bool)
+    {
+        assert_eq!(OpenLocationCode::is_valid(code), valid);
+        assert_eq!(OpenLocationCode::is_short(code), short);
+        assert_eq!(OpenLocationCode::is_full(code), full);
+    }
+
+    #[test]
+    fn test_errors()
+    {
+        let p = LatLon::new(47.0, 8.0);
+        assert_eq!(OpenLocationCode::new(p, 1), Err(OlcError::InvalidLength(1)));
+        assert_eq!(OpenLocationCode::new(p, 7), Err(OlcError::InvalidLength(7)));
+        assert_eq!(OpenLocationCode::new(p, 20).unwrap().length(), OpenLocationCode::MAX_LENGTH);
+        assert_eq!("8FWC2345+G".parse::<OpenLocationCode>(), Err(OlcError::InvalidCode("8FWC2345+G".to_string())));
+        assert_eq!("2345+G6".parse::<OpenLocationCode>(), Err(OlcError::NotFull("2345+G6".to_string())));
+        assert_eq!(OpenLocationCode::recover("22", p), Err(OlcError::InvalidCode("22".to_string())));
+        let code: OpenLocationCode = "8FVC2200+".parse().unwrap();
+        assert_eq!(code.shorten(p), Err(OlcError::Padded("8FVC2200+".to_string())));
+    }
+}
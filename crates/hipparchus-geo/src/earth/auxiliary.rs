@@ -1,6 +1,10 @@
 use num_enum::{IntoPrimitive, TryFromPrimitive};
+use hipparchus_mean::Power;
+use hipparchus_az::Azimuth;
+use crate::earth::ellipsoid::Ellipsoid;
+use crate::earth::models::Radius;
+use crate::geodesic::trig;
 
-// TODO: Add more auxiliary latitude definitions
 // See in  https://arxiv.org/pdf/2212.05818.pdf
 // See in https://github.com/geographiclib/geographiclib
 #[repr(i8)]
@@ -28,3 +32,371 @@ impl AuxiliaryLatitude
     pub const GEODESIC: Self = Self::Geographic;
     pub const REDUCED: Self = Self::Parametric;
 }
+
+impl Ellipsoid
+{
+    /// Convert the latitude `lat` (degrees) of the auxiliary latitude `from` to the auxiliary latitude `to`.
+    pub fn convert_latitude(&self, lat: f64, from: AuxiliaryLatitude, to: AuxiliaryLatitude) -> f64
+    {
+        if from == to || !lat.is_finite()
+        {
+            return lat;
+        }
+        let (s, c) = trig::sincosd(lat);
+        let zeta = self.convert_latitude_azimuth(Azimuth::new(s, c), from, to);
+        trig::atan2d(zeta.y(), zeta.x())
+    }
+
+    /// Convert the latitude `zeta` of the auxiliary latitude `from` to the auxiliary latitude `to`.
+    ///
+    /// The parametric and geocentric latitudes are related to the geographic latitude by a scale of
+    /// the tangent, which is exact in the y/x representation; the rectifying, conformal and authalic
+    /// latitudes use Fourier series in the third flattening n to order n^6, evaluated with Clenshaw
+    /// summation. Other pairs are converted through the geographic latitude.
+    pub fn convert_latitude_azimuth(&self, zeta: Azimuth<f64>, from: AuxiliaryLatitude, to: AuxiliaryLatitude) -> Azimuth<f64>
+    {
+        if from == to
+        {
+            return zeta;
+        }
+        if zeta.is_nan()
+        {
+            return Azimuth::nan();
+        }
+        let phi = match from
+        {
+            AuxiliaryLatitude::Geographic => zeta,
+            AuxiliaryLatitude::Parametric => Azimuth::new(zeta.y(), zeta.x() * self.q),
+            AuxiliaryLatitude::Geocentric => Azimuth::new(zeta.y(), zeta.x() * self.q.sq()),
+            AuxiliaryLatitude::Rectifying => Self::fourier(zeta, &self.rectifying_inverse_coeffs()),
+            AuxiliaryLatitude::Conformal => Self::fourier(zeta, &self.conformal_inverse_coeffs()),
+            AuxiliaryLatitude::Authalic => Self::fourier(zeta, &self.authalic_inverse_coeffs()),
+        };
+        match to
+        {
+            AuxiliaryLatitude::Geographic => phi,
+            AuxiliaryLatitude::Parametric => Azimuth::new(phi.y() * self.q, phi.x()),
+            AuxiliaryLatitude::Geocentric => Azimuth::new(phi.y() * self.q.sq(), phi.x()),
+            AuxiliaryLatitude::Rectifying => Self::fourier(phi, &self.rectifying_coeffs()),
+            AuxiliaryLatitude::Conformal => Self::fourier(phi, &self.conformal_coeffs()),
+            AuxiliaryLatitude::Authalic => Self::fourier(phi, &self.authalic_coeffs()),
+        }
+    }
+
+    /// Get the rectifying radius, i.e. the length of the meridian quadrant divided by π/2.
+    pub fn rectifying_radius(&self) -> f64
+    {
+        let n2 = self.n.sq();
+        self.a / (1.0 + self.n) * (1.0 + n2 * (1.0 / 4.0 + n2 * (1.0 / 64.0 + n2 / 256.0)))
+    }
+
+    /// Get the authalic radius, i.e. the radius of the sphere with the same surface area.
+    pub fn authalic_radius(&self) -> f64
+    {
+        self.radius(Radius::SurfaceAreaMean)
+    }
+
+    /// Get the signed eccentricity, positive for oblate and negative for prolate ellipsoids.
+    pub(crate) fn signed_eccentricity(&self) -> f64
+    {
+        self.f.signum() * self.e1sq.abs().sqrt()
+    }
+
+    /// Get the rectifying latitude μ (radians) from the geographic latitude φ (radians).
+    pub(crate) fn rectifying_latitude(&self, phi: f64) -> f64
+    {
+        phi + trig::sin_cos_series(true, phi.sin(), phi.cos(), &self.rectifying_coeffs())
+    }
+
+    /// Get the geographic latitude φ (radians) from the rectifying latitude μ (radians).
+    pub(crate) fn rectifying_latitude_inverse(&self, mu: f64) -> f64
+    {
+        mu + trig::sin_cos_series(true, mu.sin(), mu.cos(), &self.rectifying_inverse_coeffs())
+    }
+
+    /// Get the sine of the authalic latitude ξ from the sine of the geographic latitude φ.
+    pub(crate) fn authalic_sine(&self, sphi: f64) -> f64
+    {
+        self.authalic_q(sphi) / self.authalic_q(1.0)
+    }
+
+    // The function q(φ) of Snyder, the area under the parallel of φ scaled by 2π b².
+    fn authalic_q(&self, sphi: f64) -> f64
+    {
+        let es = self.signed_eccentricity();
+        let atanhee = if es == 0.0 { sphi } else { trig::eatanhe(sphi, es) / es.sq() };
+        (1.0 - self.e1sq) * (sphi / (1.0 - self.e1sq * sphi.sq()) + atanhee)
+    }
+
+    // Coefficients of the Fourier series of μ - φ in sin(2kφ), Karney (2023) to order n^6.
+    fn rectifying_coeffs(&self) -> [f64; 7]
+    {
+        let n = self.n;
+        let n2 = n.sq();
+        [
+            0.0,
+            n * (-3.0 / 2.0 + n2 * (9.0 / 16.0 - n2 * 3.0 / 32.0)),
+            n2 * (15.0 / 16.0 + n2 * (-15.0 / 32.0 + n2 * 135.0 / 2048.0)),
+            n2 * n * (-35.0 / 48.0 + n2 * 105.0 / 256.0),
+            n2 * n2 * (315.0 / 512.0 - n2 * 189.0 / 512.0),
+            n2 * n2 * n * (-693.0 / 1280.0),
+            n2 * n2 * n2 * (1001.0 / 2048.0),
+        ]
+    }
+
+    // Coefficients of the Fourier series of φ - μ in sin(2kμ), Karney (2023) to order n^6.
+    fn rectifying_inverse_coeffs(&self) -> [f64; 7]
+    {
+        let n = self.n;
+        let n2 = n.sq();
+        [
+            0.0,
+            n * (3.0 / 2.0 + n2 * (-27.0 / 32.0 + n2 * 269.0 / 512.0)),
+            n2 * (21.0 / 16.0 + n2 * (-55.0 / 32.0 + n2 * 6759.0 / 4096.0)),
+            n2 * n * (151.0 / 96.0 - n2 * 417.0 / 128.0),
+            n2 * n2 * (1097.0 / 512.0 - n2 * 15543.0 / 2560.0),
+            n2 * n2 * n * (8011.0 / 2560.0),
+            n2 * n2 * n2 * (293393.0 / 61440.0),
+        ]
+    }
+
+    // Add the Fourier series Σ c[k] sin(2kζ) to the latitude ζ.
+    fn fourier(zeta: Azimuth<f64>, c: &[f64]) -> Azimuth<f64>
+    {
+        let h = zeta.hypot();
+        let (s, c0) = (zeta.y() / h, zeta.x() / h);
+        Azimuth::with_radians(s.atan2(c0) + trig::sin_cos_series(true, s, c0, c))
+    }
+
+    // Coefficients of the Fourier series of χ - φ in sin(2kφ), Karney (2023) to order n^6.
+    fn conformal_coeffs(&self) -> [f64; 7]
+    {
+        let n = self.n;
+        [
+            0.0,
+            n * (-2.0 + n * (2.0 / 3.0 + n * (4.0 / 3.0 + n * (-82.0 / 45.0 + n * (32.0 / 45.0 + n * 4642.0 / 4725.0))))),
+            n.powi(2) * (5.0 / 3.0 + n * (-16.0 / 15.0 + n * (-13.0 / 9.0 + n * (904.0 / 315.0 - n * 1522.0 / 945.0)))),
+            n.powi(3) * (-26.0 / 15.0 + n * (34.0 / 21.0 + n * (8.0 / 5.0 - n * 12686.0 / 2835.0))),
+            n.powi(4) * (1237.0 / 630.0 + n * (-12.0 / 5.0 - n * 24832.0 / 14175.0)),
+            n.powi(5) * (-734.0 / 315.0 + n * 109598.0 / 31185.0),
+            n.powi(6) * (444337.0 / 155925.0),
+        ]
+    }
+
+    // Coefficients of the Fourier series of φ - χ in sin(2kχ), Karney (2023) to order n^6.
+    fn conformal_inverse_coeffs(&self) -> [f64; 7]
+    {
+        let n = self.n;
+        [
+            0.0,
+            n * (2.0 + n * (-2.0 / 3.0 + n * (-2.0 + n * (116.0 / 45.0 + n * (26.0 / 45.0 - n * 2854.0 / 675.0))))),
+            n.powi(2) * (7.0 / 3.0 + n * (-8.0 / 5.0 + n * (-227.0 / 45.0 + n * (2704.0 / 315.0 + n * 2323.0 / 945.0)))),
+            n.powi(3) * (56.0 / 15.0 + n * (-136.0 / 35.0 + n * (-1262.0 / 105.0 + n * 73814.0 / 2835.0))),
+            n.powi(4) * (4279.0 / 630.0 + n * (-332.0 / 35.0 - n * 399572.0 / 14175.0)),
+            n.powi(5) * (4174.0 / 315.0 - n * 144838.0 / 6237.0),
+            n.powi(6) * (601676.0 / 22275.0),
+        ]
+    }
+
+    // Coefficients of the Fourier series of ξ - φ in sin(2kφ), Karney (2023) to order n^6.
+    fn authalic_coeffs(&self) -> [f64; 7]
+    {
+        let n = self.n;
+        [
+            0.0,
+            n * (-4.0 / 3.0 + n * (-4.0 / 45.0 + n * (88.0 / 315.0 + n * (538.0 / 4725.0 + n * (20824.0 / 467775.0 - n * 44732.0 / 2837835.0))))),
+            n.powi(2) * (34.0 / 45.0 + n * (8.0 / 105.0 + n * (-2482.0 / 14175.0 + n * (-37192.0 / 467775.0 - n * 12467764.0 / 212837625.0)))),
+            n.powi(3) * (-1532.0 / 2835.0 + n * (-898.0 / 14175.0 + n * (54968.0 / 467775.0 + n * 100320856.0 / 1915538625.0))),
+            n.powi(4) * (6007.0 / 14175.0 + n * (24496.0 / 467775.0 - n * 5884124.0 / 70945875.0)),
+            n.powi(5) * (-23356.0 / 66825.0 - n * 839792.0 / 19348875.0),
+            n.powi(6) * (570284222.0 / 1915538625.0),
+        ]
+    }
+
+    // Coefficients of the Fourier series of φ - ξ in sin(2kξ), Karney (2023) to order n^6.
+    fn authalic_inverse_coeffs(&self) -> [f64; 7]
+    {
+        let n = self.n;
+        [
+            0.0,
+            n * (4.0 / 3.0 + n * (4.0 / 45.0 + n * (-16.0 / 35.0 + n * (-2582.0 / 14175.0 + n * (60136.0 / 467775.0 + n * 28112932.0 / 212837625.0))))),
+            n.powi(2) * (46.0 / 45.0 + n * (152.0 / 945.0 + n * (-11966.0 / 14175.0 + n * (-21016.0 / 51975.0 + n * 251310128.0 / 638512875.0)))),
+            n.powi(3) * (3044.0 / 2835.0 + n * (3802.0 / 14175.0 + n * (-94388.0 / 66825.0 - n * 8797648.0 / 10945935.0))),
+            n.powi(4) * (6059.0 / 4725.0 + n * (41072.0 / 93555.0 - n * 1472637812.0 / 638512875.0)),
+            n.powi(5) * (768272.0 / 467775.0 + n * 455935736.0 / 638512875.0),
+            n.powi(6) * (4210684958.0 / 1915538625.0),
+        ]
+    }
+}
+
+/// Get tan χ from tan φ, where χ is the conformal latitude and es the signed eccentricity.
+pub(crate) fn taupf(tau: f64, es: f64) -> f64
+{
+    if !tau.is_finite()
+    {
+        return tau;
+    }
+    let tau1 = tau.hypot(1.0);
+    let sig = trig::eatanhe(tau / tau1, es).sinh();
+    sig.hypot(1.0) * tau - sig * tau1
+}
+
+/// Get tan φ from tan χ, the inverse of `taupf` solved by Newton's method.
+pub(crate) fn tauf(taup: f64, es: f64) -> f64
+{
+    const NUMIT: usize = 5;
+    let tol = f64::EPSILON.sqrt() / 10.0;
+    let taumax = 2.0 / f64::EPSILON.sqrt();
+    let e2m = 1.0 - es.sq();
+    let mut tau = if taup.abs() > 70.0 { taup * trig::eatanhe(1.0, es).exp() } else { taup / e2m };
+    if tau.is_nan() || tau.abs() >= taumax
+    {
+        return tau;
+    }
+    let stol = tol * taup.abs().max(1.0);
+    for _ in 0..NUMIT
+    {
+        let taupa = taupf(tau, es);
+        let dtau = (taup - taupa) * (1.0 + e2m * tau.sq()) / (e2m * tau.hypot(1.0) * taupa.hypot(1.0));
+        tau += dtau;
+        if dtau.is_nan() || dtau.abs() < stol
+        {
+            break;
+        }
+    }
+    tau
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::earth::models::{Model, WGS84, Sphere};
+    use float_cmp::assert_approx_eq;
+    use rstest::*;
+
+    #[test]
+    fn test_rectifying_radius()
+    {
+        // The length of the meridian quadrant of WGS84.
+        let elps = WGS84::elps();
+        assert_approx_eq!(f64, elps.rectifying_radius() * std::f64::consts::FRAC_PI_2, 10001965.729313, epsilon = 1e-6);
+        let elps = Sphere::elps();
+        assert_eq!(elps.rectifying_radius(), Sphere::A);
+        assert_approx_eq!(f64, elps.authalic_radius(), Sphere::A, epsilon = 1e-6);
+    }
+
+    #[rstest]
+    #[case(0.0)]
+    #[case(10.0)]
+    #[case(33.3)]
+    #[case(45.0)]
+    #[case(-60.0)]
+    #[case(89.9)]
+    #[case(90.0)]
+    fn test_rectifying_latitude(#[case] lat: f64)
+    {
+        let elps = WGS84::elps();
+        let phi = lat.to_radians();
+        let mu = elps.rectifying_latitude(phi);
+        assert_approx_eq!(f64, elps.rectifying_latitude_inverse(mu), phi, epsilon = 1e-15);
+        if lat == 90.0
+        {
+            assert_approx_eq!(f64, mu, phi, epsilon = 1e-15);
+        }
+    }
+
+    #[rstest]
+    #[case(0.0, 0.0)]
+    #[case(1.0, 0.993309339535224)]
+    #[case(f64::INFINITY, f64::INFINITY)]
+    fn test_taupf(#[case] tau: f64, #[case] expected: f64)
+    {
+        let es = WGS84::elps().signed_eccentricity();
+        assert_approx_eq!(f64, taupf(tau, es), expected, epsilon = 1e-14);
+        assert_eq!(taupf(tau, 0.0), tau);
+        assert_approx_eq!(f64, tauf(expected, es), tau, epsilon = 1e-14);
+    }
+
+    #[test]
+    fn test_authalic_sine()
+    {
+        let elps = WGS84::elps();
+        assert_eq!(elps.authalic_sine(0.0), 0.0);
+        assert_approx_eq!(f64, elps.authalic_sine(1.0), 1.0);
+        assert_approx_eq!(f64, elps.authalic_sine(-1.0), -1.0);
+        let elps = Sphere::elps();
+        assert_approx_eq!(f64, elps.authalic_sine(0.5), 0.5);
+    }
+
+    #[rstest]
+    #[case(33.3, [33.2117596879361, 33.1236378280237, 33.1676617752479, 33.12369695755365, 33.1823436328213])]
+    #[case(-60.0, [-59.9166077970211, -59.8330761504926, -59.8748855936439, -59.83321615835, -59.8887855698852])]
+    #[case(89.9, [89.8996635917045, 89.8993260517083, 89.8994951759565, 89.8993268076963, 89.8995513050662])]
+    fn test_convert_latitude(#[case] lat: f64, #[case] expected: [f64; 5])
+    {
+        let elps = WGS84::elps();
+        let auxs = [AuxiliaryLatitude::BETA, AuxiliaryLatitude::THETA, AuxiliaryLatitude::MU, AuxiliaryLatitude::CHI, AuxiliaryLatitude::XI];
+        for (aux, v) in auxs.iter().zip(expected.iter())
+        {
+            assert_approx_eq!(f64, elps.convert_latitude(lat, AuxiliaryLatitude::PHI, *aux), *v, epsilon = 1e-13);
+            assert_approx_eq!(f64, elps.convert_latitude(*v, *aux, AuxiliaryLatitude::PHI), lat, epsilon = 1e-13);
+        }
+        for (from, u) in auxs.iter().zip(expected.iter())
+        {
+            for (to, v) in auxs.iter().zip(expected.iter())
+            {
+                assert_approx_eq!(f64, elps.convert_latitude(*u, *from, *to), *v, epsilon = 1e-13);
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(0.0)]
+    #[case(90.0)]
+    #[case(-90.0)]
+    fn test_convert_latitude_fixed(#[case] lat: f64)
+    {
+        let elps = WGS84::elps();
+        for from in 0..6
+        {
+            for to in 0..6
+            {
+                let from = AuxiliaryLatitude::try_from(from).unwrap();
+                let to = AuxiliaryLatitude::try_from(to).unwrap();
+                assert_eq!(elps.convert_latitude(lat, from, to), lat);
+            }
+        }
+        assert!(elps.convert_latitude(f64::NAN, AuxiliaryLatitude::PHI, AuxiliaryLatitude::MU).is_nan());
+    }
+
+    #[test]
+    fn test_convert_latitude_sphere()
+    {
+        let elps = Sphere::elps();
+        assert_approx_eq!(f64, elps.convert_latitude(45.0, AuxiliaryLatitude::CHI, AuxiliaryLatitude::XI), 45.0, epsilon = 1e-14);
+        assert_approx_eq!(f64, elps.convert_latitude(-12.5, AuxiliaryLatitude::THETA, AuxiliaryLatitude::MU), -12.5, epsilon = 1e-14);
+    }
+
+    #[test]
+    fn test_convert_latitude_azimuth()
+    {
+        let elps = WGS84::elps();
+        let phi = Azimuth::with_degrees(33.3);
+        let beta = elps.convert_latitude_azimuth(phi, AuxiliaryLatitude::PHI, AuxiliaryLatitude::BETA);
+        assert_approx_eq!(f64, beta.y() / beta.x(), elps.q * phi.y() / phi.x(), epsilon = 1e-15);
+        assert_approx_eq!(f64, beta.degrees(), 33.2117596879361, epsilon = 1e-13);
+
+        // The conformal latitude agrees with the exact formula.
+        let chi = elps.convert_latitude_azimuth(phi, AuxiliaryLatitude::PHI, AuxiliaryLatitude::CHI);
+        let tau = phi.y() / phi.x();
+        assert_approx_eq!(f64, chi.y() / chi.x(), taupf(tau, elps.signed_eccentricity()), epsilon = 1e-15);
+
+        // The authalic latitude agrees with the exact formula.
+        let xi = elps.convert_latitude_azimuth(phi, AuxiliaryLatitude::PHI, AuxiliaryLatitude::XI);
+        assert_approx_eq!(f64, xi.y() / xi.hypot(), elps.authalic_sine(phi.y() / phi.hypot()), epsilon = 1e-15);
+
+        assert!(elps.convert_latitude_azimuth(Azimuth::nan(), AuxiliaryLatitude::PHI, AuxiliaryLatitude::XI).is_nan());
+        assert_eq!(elps.convert_latitude_azimuth(phi, AuxiliaryLatitude::XI, AuxiliaryLatitude::XI), phi);
+    }
+}
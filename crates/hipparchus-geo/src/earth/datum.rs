@@ -0,0 +1,272 @@
+use hipparchus_mean::Power;
+use crate::coords::latlonalt::LatLonAlt;
+use crate::earth::ecef::Ecef;
+use crate::earth::ellipsoid::Ellipsoid;
+use crate::earth::models::{Model, Airy1830, Clarke1866, Krasov40, WGS84};
+use crate::geodesic::trig;
+
+/// Arc-seconds in radians.
+const ARCSEC: f64 = std::f64::consts::PI / 648000.0;
+
+/// Seven-parameter Helmert transformation of ECEF coordinates in the position vector convention (EPSG:9606).
+///
+/// The coordinate frame convention (EPSG:9607) has the rotations with the opposite signs, see [`Helmert::coordinate_frame`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Helmert
+{
+    /// translation along the X axis (meters).
+    pub tx: f64,
+
+    /// translation along the Y axis (meters).
+    pub ty: f64,
+
+    /// translation along the Z axis (meters).
+    pub tz: f64,
+
+    /// rotation about the X axis (arc-seconds).
+    pub rx: f64,
+
+    /// rotation about the Y axis (arc-seconds).
+    pub ry: f64,
+
+    /// rotation about the Z axis (arc-seconds).
+    pub rz: f64,
+
+    /// scale difference (parts per million).
+    pub ds: f64,
+}
+
+impl Helmert
+{
+    /// Create the transformation with the parameters in the position vector convention.
+    pub fn new(tx: f64, ty: f64, tz: f64, rx: f64, ry: f64, rz: f64, ds: f64) -> Self
+    {
+        Self { tx, ty, tz, rx, ry, rz, ds }
+    }
+
+    /// Create the transformation with the parameters in the coordinate frame convention.
+    pub fn coordinate_frame(tx: f64, ty: f64, tz: f64, rx: f64, ry: f64, rz: f64, ds: f64) -> Self
+    {
+        Self::new(tx, ty, tz, -rx, -ry, -rz, ds)
+    }
+
+    /// Create the transformation with the translations only.
+    pub fn translation(tx: f64, ty: f64, tz: f64) -> Self
+    {
+        Self::new(tx, ty, tz, 0.0, 0.0, 0.0, 0.0)
+    }
+
+    /// Get the reverse transformation by negating all the parameters, which is accurate to the second order of the rotations and scale.
+    pub fn inverse(&self) -> Self
+    {
+        Self::new(-self.tx, -self.ty, -self.tz, -self.rx, -self.ry, -self.rz, -self.ds)
+    }
+
+    /// Transform the ECEF coordinates with the small-angle rotation matrix.
+    pub fn apply(&self, p: Ecef) -> Ecef
+    {
+        let s = 1.0 + self.ds * 1e-6;
+        let (rx, ry, rz) = (self.rx * ARCSEC, self.ry * ARCSEC, self.rz * ARCSEC);
+        Ecef::new
+        (
+            self.tx + s * (p.x - rz * p.y + ry * p.z),
+            self.ty + s * (rz * p.x + p.y - rx * p.z),
+            self.tz + s * (-ry * p.x + rx * p.y + p.z),
+        )
+    }
+}
+
+/// The method of a datum transformation.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DatumMethod
+{
+    /// Helmert transformation through ECEF.
+    Helmert(Helmert),
+
+    /// Standard Molodensky transformation of the geodetic coordinates with the translations (meters).
+    Molodensky { dx: f64, dy: f64, dz: f64 },
+
+    /// Abridged Molodensky transformation of the geodetic coordinates with the translations (meters).
+    AbridgedMolodensky { dx: f64, dy: f64, dz: f64 },
+}
+
+/// Transformation of the geodetic coordinates from the source datum to the target datum.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DatumTransform
+{
+    /// ellipsoid of the source datum.
+    pub source: Ellipsoid,
+
+    /// ellipsoid of the target datum.
+    pub target: Ellipsoid,
+
+    /// method and parameters of the transformation.
+    pub method: DatumMethod,
+}
+
+impl DatumTransform
+{
+    /// Create the transformation between the ellipsoids.
+    pub fn with(source: Ellipsoid, target: Ellipsoid, method: DatumMethod) -> Self
+    {
+        Self { source, target, method }
+    }
+
+    /// Create the Helmert transformation between the ellipsoid models.
+    pub fn helmert<S, T>(h: Helmert) -> Self where S: Model, T: Model
+    {
+        Self::with(S::elps(), T::elps(), DatumMethod::Helmert(h))
+    }
+
+    /// Create the standard Molodensky transformation between the ellipsoid models.
+    pub fn molodensky<S, T>(dx: f64, dy: f64, dz: f64) -> Self where S: Model, T: Model
+    {
+        Self::with(S::elps(), T::elps(), DatumMethod::Molodensky { dx, dy, dz })
+    }
+
+    /// Create the abridged Molodensky transformation between the ellipsoid models.
+    pub fn abridged_molodensky<S, T>(dx: f64, dy: f64, dz: f64) -> Self where S: Model, T: Model
+    {
+        Self::with(S::elps(), T::elps(), DatumMethod::AbridgedMolodensky { dx, dy, dz })
+    }
+
+    /// OSGB36 on Airy (1830) to WGS84, by the Ordnance Survey with the accuracy of about 5 m.
+    pub fn osgb36_to_wgs84() -> Self
+    {
+        Self::helmert::<Airy1830, WGS84>(Helmert::new(446.448, -125.157, 542.060, 0.1502, 0.2470, 0.8421, -20.4894))
+    }
+
+    /// Pulkovo 1942 on Krassovsky (1940) to WGS84, by GOST R 51794-2001.
+    pub fn pulkovo42_to_wgs84() -> Self
+    {
+        Self::helmert::<Krasov40, WGS84>(Helmert::coordinate_frame(23.92, -141.27, -80.9, 0.0, -0.35, -0.82, -0.12))
+    }
+
+    /// NAD27 on Clarke (1866) to WGS84, by the mean of CONUS in NIMA TR8350.2.
+    pub fn nad27_to_wgs84() -> Self
+    {
+        Self::molodensky::<Clarke1866, WGS84>(-8.0, 160.0, 176.0)
+    }
+
+    /// Get the reverse transformation from the target datum to the source datum.
+    pub fn inverse(&self) -> Self
+    {
+        let method = match self.method
+        {
+            DatumMethod::Helmert(h) => DatumMethod::Helmert(h.inverse()),
+            DatumMethod::Molodensky { dx, dy, dz } => DatumMethod::Molodensky { dx: -dx, dy: -dy, dz: -dz },
+            DatumMethod::AbridgedMolodensky { dx, dy, dz } => DatumMethod::AbridgedMolodensky { dx: -dx, dy: -dy, dz: -dz },
+        };
+        Self::with(self.target, self.source, method)
+    }
+
+    /// Transform the point from the source datum to the target datum.
+    pub fn transform(&self, p: LatLonAlt) -> LatLonAlt
+    {
+        match self.method
+        {
+            DatumMethod::Helmert(h) => LatLonAlt::from_ecef(&self.target, h.apply(p.to_ecef(&self.source))),
+            DatumMethod::Molodensky { dx, dy, dz } => self.molodensky_shift(p, dx, dy, dz, false),
+            DatumMethod::AbridgedMolodensky { dx, dy, dz } => self.molodensky_shift(p, dx, dy, dz, true),
+        }
+    }
+
+    fn molodensky_shift(&self, p: LatLonAlt, dx: f64, dy: f64, dz: f64, abridged: bool) -> LatLonAlt
+    {
+        let elps = &self.source;
+        let (a, f, e2) = (elps.a, elps.f, elps.e1sq);
+        let (da, df) = (self.target.a - a, self.target.f - f);
+        let h = p.altitude();
+        let (sphi, cphi) = trig::sincosd(p.latitude());
+        let (slam, clam) = trig::sincosd(p.longitude());
+        let w = 1.0 - e2 * sphi.sq();
+        let rn = a / w.sqrt();
+        let rm = a * (1.0 - e2) / (w * w.sqrt());
+        let t = -dx * sphi * clam - dy * sphi * slam + dz * cphi;
+        let u = dx * cphi * clam + dy * cphi * slam + dz * sphi;
+        // The longitude is undefined at the poles, where it is kept.
+        let dlam = |r: f64| if cphi == 0.0 { 0.0 } else { (-dx * slam + dy * clam) / (r * cphi) };
+        let (dphi, dlam, dh) = if abridged
+        {
+            let k = a * df + f * da;
+            ((t + 2.0 * k * sphi * cphi) / rm, dlam(rn), u + k * sphi.sq() - da)
+        }
+        else
+        {
+            let (b, q) = (elps.b, sphi * cphi);
+            let dphi = (t + da * rn * e2 * q / a + df * (rm * a / b + rn * b / a) * q) / (rm + h);
+            (dphi, dlam(rn + h), u - da * a / rn + df * b / a * rn * sphi.sq())
+        };
+        LatLonAlt::new(p.latitude() + dphi.to_degrees(), p.longitude() + dlam.to_degrees(), h + dh)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use float_cmp::assert_approx_eq;
+    use rstest::*;
+
+    #[test]
+    fn test_helmert()
+    {
+        let h = Helmert::new(10.0, -20.0, 30.0, 1.0, -2.0, 3.0, 5.0);
+        let p = Ecef::new(3816209.6, 3737108.5, 3485109.6);
+        let q = h.apply(p);
+        assert_approx_eq!(f64, q.x, 3816150.5339911, epsilon = 1e-6);
+        assert_approx_eq!(f64, q.y, 3737145.7939661, epsilon = 1e-6);
+        assert_approx_eq!(f64, q.z, 3485212.1468494, epsilon = 1e-6);
+        let r = h.inverse().apply(q);
+        assert_approx_eq!(f64, r.distance(&p), 0.0, epsilon = 1e-2);
+        assert_eq!(Helmert::coordinate_frame(1.0, 2.0, 3.0, 0.1, 0.2, 0.3, 4.0), Helmert::new(1.0, 2.0, 3.0, -0.1, -0.2, -0.3, 4.0));
+    }
+
+    #[rstest]
+    #[case(DatumTransform::osgb36_to_wgs84(), 52.658007833, 1.716073973, 0.0, 52.6584160465, 1.7142045268, 44.7047116502)]
+    #[case(DatumTransform::pulkovo42_to_wgs84(), 55.75, 37.62, 150.0, 55.750036427, 37.6181267871, 154.3154270345)]
+    #[case(DatumTransform::nad27_to_wgs84(), 38.9, -77.0, 0.0, 38.9000326448, -76.9996749617, -36.4331548429)]
+    fn test_transform(#[case] tr: DatumTransform, #[case] lat: f64, #[case] lon: f64, #[case] h: f64, #[case] lat2: f64, #[case] lon2: f64, #[case] h2: f64)
+    {
+        let p = tr.transform(LatLonAlt::new(lat, lon, h));
+        assert_approx_eq!(f64, p.latitude(), lat2, epsilon = 1e-9);
+        assert_approx_eq!(f64, p.longitude(), lon2, epsilon = 1e-9);
+        assert_approx_eq!(f64, p.altitude(), h2, epsilon = 1e-4);
+
+        // The reverse transformation by the negated parameters recovers the point to a few centimeters.
+        let elps = WGS84::elps();
+        let q = tr.inverse().transform(p);
+        assert!(q.slant_distance(&elps, &LatLonAlt::new(lat, lon, h)) < 0.05);
+    }
+
+    #[test]
+    fn test_molodensky()
+    {
+        // The Molodensky transformations approximate the Helmert transformation with the translations only.
+        let (dx, dy, dz) = (-8.0, 160.0, 176.0);
+        let helmert = DatumTransform::helmert::<Clarke1866, WGS84>(Helmert::translation(dx, dy, dz));
+        let standard = DatumTransform::molodensky::<Clarke1866, WGS84>(dx, dy, dz);
+        let abridged = DatumTransform::abridged_molodensky::<Clarke1866, WGS84>(dx, dy, dz);
+        let elps = WGS84::elps();
+        for (lat, lon, h) in [(38.9, -77.0, 0.0), (60.0, -150.0, 2000.0), (-10.0, 120.0, -50.0)]
+        {
+            let p = LatLonAlt::new(lat, lon, h);
+            let exact = helmert.transform(p);
+            assert!(standard.transform(p).slant_distance(&elps, &exact) < 0.1);
+            assert!(abridged.transform(p).slant_distance(&elps, &exact) < 1.0);
+        }
+
+        // The longitude is kept at the poles.
+        for lat in [90.0, -90.0]
+        {
+            let p = LatLonAlt::new(lat, 10.0, 0.0);
+            let exact = helmert.transform(p);
+            for q in [standard.transform(p), abridged.transform(p)]
+            {
+                assert_eq!(q.longitude(), 10.0);
+                assert!(q.latitude().is_finite());
+                assert_approx_eq!(f64, q.altitude(), exact.altitude(), epsilon = 1.0);
+            }
+        }
+    }
+}
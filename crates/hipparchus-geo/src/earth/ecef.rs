@@ -0,0 +1,202 @@
+use hipparchus_mean::Power;
+use crate::earth::ellipsoid::Ellipsoid;
+use crate::geodesic::trig;
+
+/// Earth-centered, earth-fixed (ECEF) Cartesian coordinates in meters.
+///
+/// The Z axis points to the north pole, the X axis to the intersection of the equator and the
+/// prime meridian and the Y axis to the equator at 90°E.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Ecef
+{
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Ecef
+{
+    /// Create a new instance.
+    pub fn new(x: f64, y: f64, z: f64) -> Self
+    {
+        Self { x, y, z }
+    }
+
+    /// Get the distance to the other point (meters).
+    pub fn distance(&self, other: &Ecef) -> f64
+    {
+        (self.x - other.x).hypot(self.y - other.y).hypot(self.z - other.z)
+    }
+}
+
+impl Ellipsoid
+{
+    /// Convert the geodetic latitude, longitude (degrees) and height above the ellipsoid (meters) to ECEF.
+    pub fn geodetic_to_ecef(&self, lat: f64, lon: f64, h: f64) -> Ecef
+    {
+        let (sphi, cphi) = trig::sincosd(lat);
+        let (slam, clam) = trig::sincosd(lon);
+        let n = self.a / (1.0 - self.e1sq * sphi.sq()).sqrt();
+        let r = (n + h) * cphi;
+        Ecef::new(r * clam, r * slam, (n * (1.0 - self.e1sq) + h) * sphi)
+    }
+
+    /// Convert the ECEF coordinates to the geodetic latitude, longitude (degrees) and height above the ellipsoid (meters).
+    ///
+    /// The closed-form solution of Vermeille (2011), An analytical method to transform geocentric into
+    /// geodetic coordinates, is accurate to a few nanometers for all the points, including the ones
+    /// close to the center of the earth; the longitude at the poles is 0.
+    pub fn ecef_to_geodetic(&self, p: Ecef) -> (f64, f64, f64)
+    {
+        let (a, f, e2) = (self.a, self.f, self.e1sq);
+        let e2m = (1.0 - f).sq();
+        let e2a = e2.abs();
+        let e4a = e2.sq();
+        let maxrad = 2.0 * a / f64::EPSILON;
+
+        let mut r = p.x.hypot(p.y);
+        let (mut slam, mut clam) = if r != 0.0 { (p.y / r, p.x / r) } else { (0.0, 1.0) };
+        let mut h = r.hypot(p.z);
+        let (sphi, cphi);
+        if h > maxrad
+        {
+            // The point is so far that the ellipsoid is a point, and halving avoids overflow.
+            r = (p.x / 2.0).hypot(p.y / 2.0);
+            (slam, clam) = if r != 0.0 { (p.y / 2.0 / r, p.x / 2.0 / r) } else { (0.0, 1.0) };
+            let hh = (p.z / 2.0).hypot(r);
+            (sphi, cphi) = (p.z / 2.0 / hh, r / hh);
+        }
+        else if e4a == 0.0
+        {
+            // The ellipsoid is a sphere.
+            (sphi, cphi) = (p.z / h, r / h);
+            h -= a;
+        }
+        else
+        {
+            let mut pp = (r / a).sq();
+            let mut qq = e2m * (p.z / a).sq();
+            let rr = (pp + qq - e4a) / 6.0;
+            if f < 0.0
+            {
+                std::mem::swap(&mut pp, &mut qq);
+            }
+            if !(e4a * qq == 0.0 && rr <= 0.0)
+            {
+                let s = e4a * pp * qq / 4.0;
+                let r2 = rr.sq();
+                let r3 = rr * r2;
+                let disc = s * (2.0 * r3 + s);
+                let mut u = rr;
+                if disc >= 0.0
+                {
+                    let t3 = s + r3;
+                    let t3 = t3 + if t3 < 0.0 { -disc.sqrt() } else { disc.sqrt() };
+                    let t = t3.cbrt();
+                    u += t + if t != 0.0 { r2 / t } else { 0.0 };
+                }
+                else
+                {
+                    let ang = (-disc).sqrt().atan2(-(s + r3));
+                    u += 2.0 * rr * (ang / 3.0).cos();
+                }
+                let v = (u.sq() + e4a * qq).sqrt();
+                let uv = if u < 0.0 { e4a * qq / (v - u) } else { u + v };
+                let w = (e2a * (uv - qq) / (2.0 * v)).max(0.0);
+                let k = uv / ((uv + w.sq()).sqrt() + w);
+                let k1 = if f >= 0.0 { k } else { k - e2 };
+                let k2 = if f >= 0.0 { k + e2 } else { k };
+                let d = k1 * r / k2;
+                let hh = (p.z / k1).hypot(r / k2);
+                (sphi, cphi) = (p.z / k1 / hh, r / k2 / hh);
+                h = (1.0 - e2m / k1) * d.hypot(p.z);
+            }
+            else
+            {
+                // The point is on the singular disc of the focal points.
+                let zz = (if f >= 0.0 { e4a - pp } else { pp } / e2m).sqrt();
+                let xx = (if f < 0.0 { e4a - pp } else { pp }).sqrt();
+                let hh = zz.hypot(xx);
+                (sphi, cphi) = (if p.z < 0.0 { -zz / hh } else { zz / hh }, xx / hh);
+                h = -a * if f >= 0.0 { e2m } else { 1.0 } * hh / e2a;
+            }
+        }
+        (trig::atan2d(sphi, cphi), trig::atan2d(slam, clam), h)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::earth::models::{Model, WGS84, Sphere};
+    use float_cmp::assert_approx_eq;
+    use rstest::*;
+
+    #[test]
+    fn test_geodetic_to_ecef()
+    {
+        let elps = WGS84::elps();
+        let p = elps.geodetic_to_ecef(33.3, 44.4, 6000.0);
+        assert_approx_eq!(f64, p.x, 3816209.60449309, epsilon = 1e-7);
+        assert_approx_eq!(f64, p.y, 3737108.55025426, epsilon = 1e-7);
+        assert_approx_eq!(f64, p.z, 3485109.57256804, epsilon = 1e-7);
+        assert_eq!(elps.geodetic_to_ecef(0.0, 0.0, 0.0), Ecef::new(WGS84::A, 0.0, 0.0));
+        let p = elps.geodetic_to_ecef(90.0, 0.0, 0.0);
+        assert_approx_eq!(f64, p.z, WGS84::B, epsilon = 1e-8);
+    }
+
+    #[rstest]
+    #[case(33.3, 44.4, 6000.0)]
+    #[case(-89.9, -170.0, -100.0)]
+    #[case(0.0, 180.0, 400e3)]
+    #[case(45.0, 90.0, 36000e3)]
+    #[case(10.0, -20.0, -6000e3)]
+    #[case(90.0, 0.0, 0.0)]
+    #[case(-90.0, 0.0, 1e3)]
+    fn test_round_trip(#[case] lat: f64, #[case] lon: f64, #[case] h: f64)
+    {
+        let elps = WGS84::elps();
+        let (lat2, lon2, h2) = elps.ecef_to_geodetic(elps.geodetic_to_ecef(lat, lon, h));
+        assert_approx_eq!(f64, lat2, lat, epsilon = 1e-12);
+        assert_approx_eq!(f64, lon2, lon, epsilon = 1e-12);
+        assert_approx_eq!(f64, h2, h, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn test_special_points()
+    {
+        let elps = WGS84::elps();
+        let (lat, lon, h) = elps.ecef_to_geodetic(Ecef::new(0.0, 0.0, 0.0));
+        assert_eq!((lat, lon), (90.0, 0.0));
+        assert_approx_eq!(f64, h, -WGS84::B, epsilon = 1e-8);
+        let (lat, _, h) = elps.ecef_to_geodetic(Ecef::new(0.0, 0.0, -7e6));
+        assert_eq!(lat, -90.0);
+        assert_approx_eq!(f64, h, 7e6 - WGS84::B, epsilon = 1e-8);
+        let (lat, lon, _) = elps.ecef_to_geodetic(Ecef::new(1e300, 1e300, 0.0));
+        assert_eq!((lat, lon), (0.0, 45.0));
+        let (lat, lon, _) = elps.ecef_to_geodetic(Ecef::new(1.5e308, 1.5e308, 0.0));
+        assert_approx_eq!(f64, lat, 0.0, epsilon = 1e-12);
+        assert_approx_eq!(f64, lon, 45.0, epsilon = 1e-12);
+
+        let elps = Sphere::elps();
+        let (lat, lon, h) = elps.ecef_to_geodetic(Ecef::new(0.0, 7e6, 7e6));
+        assert_approx_eq!(f64, lat, 45.0, epsilon = 1e-13);
+        assert_approx_eq!(f64, lon, 90.0, epsilon = 1e-13);
+        assert_approx_eq!(f64, h, 7e6 * 2f64.sqrt() - Sphere::A, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn test_prolate()
+    {
+        let elps = Ellipsoid::new(6e6, -150.0);
+        for (lat, lon, h) in [(30.0, 10.0, 1000.0), (-70.0, -120.0, -2e6), (0.0, 0.0, 0.0)]
+        {
+            let (lat2, lon2, h2) = elps.ecef_to_geodetic(elps.geodetic_to_ecef(lat, lon, h));
+            assert_approx_eq!(f64, lat2, lat, epsilon = 1e-12);
+            assert_approx_eq!(f64, lon2, lon, epsilon = 1e-12);
+            assert_approx_eq!(f64, h2, h, epsilon = 1e-8);
+        }
+        assert_approx_eq!(f64, Ecef::new(3.0, 4.0, 0.0).distance(&Ecef::new(0.0, 0.0, 12.0)), 13.0);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::earth::ecef::Ecef;
+use crate::earth::ellipsoid::Ellipsoid;
+use crate::geodesic::trig;
+
+/// East, north and up coordinates in a local tangent plane (meters).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Enu
+{
+    pub east: f64,
+    pub north: f64,
+    pub up: f64,
+}
+
+/// North, east and down coordinates in a local tangent plane (meters).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Ned
+{
+    pub north: f64,
+    pub east: f64,
+    pub down: f64,
+}
+
+impl Enu
+{
+    /// Create a new instance.
+    pub fn new(east: f64, north: f64, up: f64) -> Self
+    {
+        Self { east, north, up }
+    }
+}
+
+impl Ned
+{
+    /// Create a new instance.
+    pub fn new(north: f64, east: f64, down: f64) -> Self
+    {
+        Self { north, east, down }
+    }
+}
+
+impl From<Ned> for Enu
+{
+    fn from(v: Ned) -> Self
+    {
+        Enu::new(v.east, v.north, -v.down)
+    }
+}
+
+impl From<Enu> for Ned
+{
+    fn from(v: Enu) -> Self
+    {
+        Ned::new(v.north, v.east, -v.up)
+    }
+}
+
+/// A local tangent plane at an origin on or above the ellipsoid.
+///
+/// The points are converted with the position of the origin in ECEF, while the vectors such as
+/// velocities are only rotated.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LocalFrame
+{
+    pub elps: Ellipsoid,
+    origin: Ecef,
+
+    // the rotation from ECEF to ENU, with rows of the east, north and up axes in ECEF
+    rotation: [[f64; 3]; 3],
+}
+
+impl LocalFrame
+{
+    /// Create the local frame at the geodetic latitude, longitude (degrees) and height (meters).
+    pub fn new(elps: Ellipsoid, lat0: f64, lon0: f64, h0: f64) -> Self
+    {
+        let (sphi, cphi) = trig::sincosd(lat0);
+        let (slam, clam) = trig::sincosd(lon0);
+        let rotation =
+        [
+            [-slam, clam, 0.0],
+            [-sphi * clam, -sphi * slam, cphi],
+            [cphi * clam, cphi * slam, sphi],
+        ];
+        Self { elps, origin: elps.geodetic_to_ecef(lat0, lon0, h0), rotation }
+    }
+
+    /// Get the origin of the frame in ECEF.
+    pub fn origin(&self) -> Ecef
+    {
+        self.origin
+    }
+
+    /// Get the rotation matrix from ECEF to ENU, its transpose rotates from ENU to ECEF.
+    pub fn enu_rotation(&self) -> [[f64; 3]; 3]
+    {
+        self.rotation
+    }
+
+    /// Get the rotation matrix from ECEF to NED, its transpose rotates from NED to ECEF.
+    pub fn ned_rotation(&self) -> [[f64; 3]; 3]
+    {
+        let [e, n, u] = self.rotation;
+        [n, e, u.map(|x| -x)]
+    }
+
+    /// Rotate the vector from ECEF to ENU.
+    pub fn ecef_vector_to_enu(&self, v: Ecef) -> Enu
+    {
+        let [e, n, u] = self.rotation.map(|r| r[0] * v.x + r[1] * v.y + r[2] * v.z);
+        Enu::new(e, n, u)
+    }
+
+    /// Rotate the vector from ENU to ECEF.
+    pub fn enu_vector_to_ecef(&self, v: Enu) -> Ecef
+    {
+        let r = &self.rotation;
+        let axis = |i: usize| r[0][i] * v.east + r[1][i] * v.north + r[2][i] * v.up;
+        Ecef::new(axis(0), axis(1), axis(2))
+    }
+
+    /// Convert the point from ECEF to ENU.
+    pub fn ecef_to_enu(&self, p: Ecef) -> Enu
+    {
+        self.ecef_vector_to_enu(Ecef::new(p.x - self.origin.x, p.y - self.origin.y, p.z - self.origin.z))
+    }
+
+    /// Convert the point from ENU to ECEF.
+    pub fn enu_to_ecef(&self, v: Enu) -> Ecef
+    {
+        let d = self.enu_vector_to_ecef(v);
+        Ecef::new(self.origin.x + d.x, self.origin.y + d.y, self.origin.z + d.z)
+    }
+
+    /// Convert the point from ECEF to NED.
+    pub fn ecef_to_ned(&self, p: Ecef) -> Ned
+    {
+        self.ecef_to_enu(p).into()
+    }
+
+    /// Convert the point from NED to ECEF.
+    pub fn ned_to_ecef(&self, v: Ned) -> Ecef
+    {
+        self.enu_to_ecef(v.into())
+    }
+
+    /// Convert the geodetic latitude, longitude (degrees) and height (meters) to ENU.
+    pub fn geodetic_to_enu(&self, lat: f64, lon: f64, h: f64) -> Enu
+    {
+        self.ecef_to_enu(self.elps.geodetic_to_ecef(lat, lon, h))
+    }
+
+    /// Convert the point from ENU to the geodetic latitude, longitude (degrees) and height (meters).
+    pub fn enu_to_geodetic(&self, v: Enu) -> (f64, f64, f64)
+    {
+        self.elps.ecef_to_geodetic(self.enu_to_ecef(v))
+    }
+
+    /// Convert the geodetic latitude, longitude (degrees) and height (meters) to NED.
+    pub fn geodetic_to_ned(&self, lat: f64, lon: f64, h: f64) -> Ned
+    {
+        self.geodetic_to_enu(lat, lon, h).into()
+    }
+
+    /// Convert the point from NED to the geodetic latitude, longitude (degrees) and height (meters).
+    pub fn ned_to_geodetic(&self, v: Ned) -> (f64, f64, f64)
+    {
+        self.enu_to_geodetic(v.into())
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::earth::models::{Model, WGS84};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_enu()
+    {
+        let frame = LocalFrame::new(WGS84::elps(), 33.0, 44.0, 20.0);
+        let v = frame.geodetic_to_enu(33.3, 44.4, 6000.0);
+        assert_approx_eq!(f64, v.east, 37288.9731966231, epsilon = 1e-7);
+        assert_approx_eq!(f64, v.north, 33374.294946904, epsilon = 1e-7);
+        assert_approx_eq!(f64, v.up, 5783.64661551818, epsilon = 1e-7);
+        let (lat, lon, h) = frame.enu_to_geodetic(v);
+        assert_approx_eq!(f64, lat, 33.3, epsilon = 1e-12);
+        assert_approx_eq!(f64, lon, 44.4, epsilon = 1e-12);
+        assert_approx_eq!(f64, h, 6000.0, epsilon = 1e-8);
+
+        let o = frame.geodetic_to_enu(33.0, 44.0, 20.0);
+        assert_approx_eq!(f64, o.east.hypot(o.north).hypot(o.up), 0.0, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn test_ned()
+    {
+        let frame = LocalFrame::new(WGS84::elps(), -12.0, 130.0, 0.0);
+        let v = frame.geodetic_to_ned(-12.0, 130.0, 150.0);
+        assert_approx_eq!(f64, v.down, -150.0, epsilon = 1e-8);
+        assert_approx_eq!(f64, v.north, 0.0, epsilon = 1e-8);
+        assert_approx_eq!(f64, v.east, 0.0, epsilon = 1e-8);
+
+        let v = frame.geodetic_to_ned(-11.99, 130.01, 10.0);
+        assert!(v.north > 0.0 && v.east > 0.0);
+        let (lat, lon, h) = frame.ned_to_geodetic(v);
+        assert_approx_eq!(f64, lat, -11.99, epsilon = 1e-12);
+        assert_approx_eq!(f64, lon, 130.01, epsilon = 1e-12);
+        assert_approx_eq!(f64, h, 10.0, epsilon = 1e-8);
+        assert_eq!(Ned::from(Enu::from(v)), v);
+    }
+
+    #[test]
+    fn test_rotation()
+    {
+        let frame = LocalFrame::new(WGS84::elps(), 51.5, -0.1, 45.0);
+        for r in [frame.enu_rotation(), frame.ned_rotation()]
+        {
+            // The rotation matrices are orthonormal.
+            for i in 0..3
+            {
+                for j in 0..3
+                {
+                    let dot: f64 = (0..3).map(|k| r[i][k] * r[j][k]).sum();
+                    assert_approx_eq!(f64, dot, if i == j { 1.0 } else { 0.0 }, epsilon = 1e-15);
+                }
+            }
+        }
+        // The velocity of 10 m/s to the north.
+        let v = frame.enu_vector_to_ecef(Enu::new(0.0, 10.0, 0.0));
+        let back = frame.ecef_vector_to_enu(v);
+        assert_approx_eq!(f64, back.north, 10.0, epsilon = 1e-14);
+        assert_approx_eq!(f64, back.east, 0.0, epsilon = 1e-14);
+        assert!(v.z > 0.0);
+        let ned = frame.ned_rotation();
+        assert_approx_eq!(f64, ned[0][0] * v.x + ned[0][1] * v.y + ned[0][2] * v.z, 10.0, epsilon = 1e-14);
+    }
+}
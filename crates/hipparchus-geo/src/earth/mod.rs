@@ -1,3 +1,7 @@
+pub mod auxiliary;
+pub mod datum;
+pub mod ecef;
+pub mod local;
 pub mod ellipsoid;
 pub mod models;
 
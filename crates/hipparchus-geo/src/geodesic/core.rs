@@ -1741,38 +1741,74 @@ mod tests
         assert_approx_eq!(f64, lat2, 45.30632, epsilon = 0.5e-5);
         assert_approx_eq!(f64, lon2, -180.0, epsilon = 0.5e-5);
         assert_approx_eq!(f64, azi2.abs(), 180.0, epsilon = 0.5e-5);
-        // geographiclib-rs does not appear to support Geodesic.inverse_line or
-        // or GeodesicLine.position as of 2021/01/18.
-        // let line = geod.inverse_line(45, 0, 80, -0.000000000000000003);
-        // let res = line.position(1e7, Caps::STANDARD | Caps::LONG_UNROLL);
-        // assert_approx_eq!(f64, lat2, 45.30632, epsilon = 0.5e-5);
-        // assert_approx_eq!(f64, lon2, -180, epsilon = 0.5e-5);
-        // assert_approx_eq!(f64, azi2.abs(), 180, epsilon = 0.5e-5);
+        let line = geod.inverse_line(45.0, 0.0, 80.0, -0.000000000000000003, Caps::ALL);
+        let res = line.position(1e7, Caps::STANDARD | Caps::LONG_UNROLL);
+        assert_approx_eq!(f64, res.lat2.unwrap(), 45.30632, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, res.lon2.unwrap(), -180.0, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, res.azi2.unwrap().abs(), 180.0, epsilon = 0.5e-5);
     }
 
-    // #[test]
-    // fn test_std_geodesic_geodsolve65() {
-    //     // Check for bug in east-going check in GeodesicLine (needed to check for
-    //     // sign of 0) and sign error in area calculation due to a bogus override
-    //     // of the code for alp12.  Found/fixed on 2015-12-19.
-    //     // These tests rely on Geodesic.inverse_line, which is not supported by
-    //     // geographiclib-rs as of 2021/01/18.
-    // }
-
-    // #[test]
-    // fn test_std_geodesic_geodsolve69() {
-    //     // Check for InverseLine if line is slightly west of S and that s13 is
-    //     // correctly set.
-    //     // These tests rely on Geodesic.inverse_line, which is not supported by
-    //     // geographiclib-rs as of 2021/01/18.
-    // }
-
-    // #[test]
-    // fn test_std_geodesic_geodsolve71() {
-    //     // Check that DirectLine sets s13.
-    //     // These tests rely on Geodesic.direct_line, which is not supported by
-    //     // geographiclib-rs as of 2021/01/18.
-    // }
+    #[test]
+    fn test_std_geodesic_geodsolve65() 
+    {
+        // Check for bug in east-going check in GeodesicLine (needed to check for
+        // sign of 0) and sign error in area calculation due to a bogus override
+        // of the code for alp12.  Found/fixed on 2015-12-19.
+        let geod = Geodesic::model::<WGS84>();
+        let line = geod.inverse_line(30.0, -0.000000000000000001, -31.0, 180.0, Caps::ALL);
+        assert_approx_eq!(f64, line.latitude(), 30.0, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, line.longitude(), 0.0, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, line.azimuth().abs(), 180.0, epsilon = 0.5e-5);
+        let res = line.position(1e7, Caps::ALL | Caps::LONG_UNROLL);
+        assert_approx_eq!(f64, res.lat2.unwrap(), -60.23169, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, res.lon2.unwrap(), -0.00000, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, res.azi2.unwrap().abs(), 180.00000, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, res.s12.unwrap(), 10000000.0, epsilon = 0.5);
+        assert_approx_eq!(f64, res.a12, 90.06544, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, res.m12.unwrap(), 6363636.0, epsilon = 0.5);
+        assert_approx_eq!(f64, res.M12.unwrap(), -0.0012834, epsilon = 0.5e-7);
+        assert_approx_eq!(f64, res.M21.unwrap(), 0.0013749, epsilon = 0.5e-7);
+        assert_approx_eq!(f64, res.S12.unwrap(), 0.0, epsilon = 0.5);
+        let res = line.position(2e7, Caps::ALL | Caps::LONG_UNROLL);
+        assert_approx_eq!(f64, res.lat2.unwrap(), -30.03547, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, res.lon2.unwrap(), -180.00000, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, res.azi2.unwrap(), -0.00000, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, res.s12.unwrap(), 20000000.0, epsilon = 0.5);
+        assert_approx_eq!(f64, res.a12, 179.96459, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, res.m12.unwrap(), 54342.0, epsilon = 0.5);
+        assert_approx_eq!(f64, res.M12.unwrap(), -1.0045592, epsilon = 0.5e-7);
+        assert_approx_eq!(f64, res.M21.unwrap(), -0.9954339, epsilon = 0.5e-7);
+        assert_approx_eq!(f64, res.S12.unwrap(), 127516405431022.0, epsilon = 0.5);
+    }
+
+    #[test]
+    fn test_std_geodesic_geodsolve69() 
+    {
+        // Check for InverseLine if line is slightly west of S and that s13 is
+        // correctly set.
+        let geod = Geodesic::model::<WGS84>();
+        let line = geod.inverse_line(-5.0, -0.000000000000002, -10.0, 180.0, Caps::ALL);
+        let res = line.position(2e7, Caps::STANDARD | Caps::LONG_UNROLL);
+        assert_approx_eq!(f64, res.lat2.unwrap(), 4.96445, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, res.lon2.unwrap(), -180.00000, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, res.azi2.unwrap(), -0.00000, epsilon = 0.5e-5);
+        let res = line.position(0.5 * line.distance(), Caps::STANDARD | Caps::LONG_UNROLL);
+        assert_approx_eq!(f64, res.lat2.unwrap(), -87.52461, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, res.lon2.unwrap(), -0.00000, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, res.azi2.unwrap(), -180.00000, epsilon = 0.5e-5);
+    }
+
+    #[test]
+    fn test_std_geodesic_geodsolve71() 
+    {
+        // Check that DirectLine sets s13.
+        let geod = Geodesic::model::<WGS84>();
+        let line = geod.direct_line(1.0, 2.0, 45.0, 1e7, Caps::ALL);
+        let res = line.position(0.5 * line.distance(), Caps::STANDARD | Caps::LONG_UNROLL);
+        assert_approx_eq!(f64, res.lat2.unwrap(), 30.92625, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, res.lon2.unwrap(), 37.54640, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, res.azi2.unwrap(), 55.43104, epsilon = 0.5e-5);
+    }
 
     #[test]
     fn test_std_geodesic_geodsolve74() 
@@ -1,6 +1,6 @@
 #![allow(non_snake_case)]
 use crate::geodesic::core::Geodesic;
-use crate::geodesic::caps::Caps;
+use crate::geodesic::caps::{Caps, Mask};
 
 /// Place a second point, given the first point, an azimuth, and a distance.
 ///
@@ -36,6 +36,90 @@ pub trait DirectGeodesic<T>
     fn direct(&self, lat1: f64, lon1: f64, azi1: f64, s12: f64) -> T;
 }
 
+/// The solution of the direct geodesic problem with named fields.
+///
+/// Only the outputs requested by the capabilities passed to [`Geodesic::solve_direct`] are
+/// populated, the others are left as `None`:
+/// - `Caps::LATITUDE`: lat2
+/// - `Caps::LONGITUDE`: lon2 (unrolled if `Caps::LONG_UNROLL` is requested as well)
+/// - `Caps::AZIMUTH`: azi2
+/// - `Caps::DISTANCE`: s12
+/// - `Caps::REDUCEDLENGTH`: m12
+/// - `Caps::GEODESICSCALE`: M12 & M21
+/// - `Caps::AREA`: S12
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DirectSolution
+{
+    /// arc length between point 1 and point 2 (degrees), always populated.
+    pub a12: f64,
+
+    /// latitude of point 2 (degrees).
+    pub lat2: Option<f64>,
+
+    /// longitude of point 2 (degrees).
+    pub lon2: Option<f64>,
+
+    /// (forward) azimuth at point 2 (degrees).
+    pub azi2: Option<f64>,
+
+    /// distance between point 1 and point 2 (meters).
+    pub s12: Option<f64>,
+
+    /// reduced length of geodesic (meters).
+    pub m12: Option<f64>,
+
+    /// geodesic scale of point 2 relative to point 1 (dimensionless).
+    pub M12: Option<f64>,
+
+    /// geodesic scale of point 1 relative to point 2 (dimensionless).
+    pub M21: Option<f64>,
+
+    /// area under the geodesic (meters<sup>2</sup>).
+    pub S12: Option<f64>,
+}
+
+impl DirectSolution
+{
+    /// Build the solution from the raw outputs of `GeodesicLine::_gen_position`, keeping the requested ones only.
+    pub(crate) fn with(outmask: Caps, raw: (f64, f64, f64, f64, f64, f64, f64, f64, f64)) -> Self
+    {
+        let (a12, lat2, lon2, azi2, s12, m12, M12, M21, S12) = raw;
+        let outmask = outmask & Mask::OUT;
+        let pick = |caps: Caps, v: f64| if outmask.intersects(caps) { Some(v) } else { None };
+        Self
+        {
+            a12,
+            lat2: pick(Caps::LATITUDE, lat2),
+            lon2: pick(Caps::LONGITUDE, lon2),
+            azi2: pick(Caps::AZIMUTH, azi2),
+            s12: pick(Caps::DISTANCE, s12),
+            m12: pick(Caps::REDUCEDLENGTH, m12),
+            M12: pick(Caps::GEODESICSCALE, M12),
+            M21: pick(Caps::GEODESICSCALE, M21),
+            S12: pick(Caps::AREA, S12),
+        }
+    }
+}
+
+impl Geodesic
+{
+    /// Solve the direct problem with distance `s12` (meters) and return the outputs selected by `caps` by name.
+    ///
+    /// See the documentation for the DirectGeodesic trait for the meaning of the arguments and outputs.
+    pub fn solve_direct(&self, lat1: f64, lon1: f64, azi1: f64, s12: f64, caps: Caps) -> DirectSolution
+    {
+        DirectSolution::with(caps, self._gen_direct(lat1, lon1, azi1, false, s12, caps))
+    }
+
+    /// Solve the direct problem with arc length `a12` (degrees) and return the outputs selected by `caps` by name.
+    ///
+    /// See the documentation for the DirectGeodesic trait for the meaning of the arguments and outputs.
+    pub fn solve_arc_direct(&self, lat1: f64, lon1: f64, azi1: f64, a12: f64, caps: Caps) -> DirectSolution
+    {
+        DirectSolution::with(caps, self._gen_direct(lat1, lon1, azi1, true, a12, caps))
+    }
+}
+
 impl DirectGeodesic<(f64, f64)> for Geodesic 
 {
     /// See the documentation for the DirectGeodesic trait.
@@ -168,6 +252,18 @@ impl DirectGeodesic<(f64, f64, f64, f64, f64, f64, f64, f64)> for Geodesic
     }
 }
 
+impl DirectGeodesic<DirectSolution> for Geodesic 
+{
+    /// See the documentation for the DirectGeodesic trait.
+    ///
+    /// # Returns
+    ///  - all outputs of the direct problem by name, see `DirectSolution`.
+    fn direct(&self, lat1: f64, lon1: f64, azi1: f64, s12: f64) -> DirectSolution 
+    {
+        self.solve_direct(lat1, lon1, azi1, s12, Caps::ALL)
+    }
+}
+
 #[cfg(test)]
 mod tests 
 {
@@ -307,4 +403,49 @@ mod tests
         assert!(lon2.is_nan());
         assert!(azi2.is_nan());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_direct_solution_caps() 
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let sol = geod.solve_direct(40.63972222, -73.77888889, 53.5, 5850e3, Caps::LATITUDE | Caps::LONGITUDE | Caps::AREA);
+        assert_approx_eq!(f64, sol.lat2.unwrap(), 49.01467, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, sol.lon2.unwrap(), 2.56106, epsilon = 0.5e-5);
+        assert!(sol.S12.is_some());
+        assert!(sol.azi2.is_none());
+        assert!(sol.s12.is_none());
+        assert!(sol.m12.is_none());
+        assert!(sol.M12.is_none());
+        assert!(sol.M21.is_none());
+    }
+
+    #[test]
+    fn test_direct_solution_all() 
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let sol: DirectSolution = geod.direct(40.63972222, -73.77888889, 53.5, 5850e3);
+        let (lat2, lon2, azi2, m12, M12, M21, S12, a12) = geod.direct(40.63972222, -73.77888889, 53.5, 5850e3);
+        assert_eq!(sol.a12, a12);
+        assert_eq!(sol.lat2, Some(lat2));
+        assert_eq!(sol.lon2, Some(lon2));
+        assert_eq!(sol.azi2, Some(azi2));
+        assert_eq!(sol.s12, Some(5850e3));
+        assert_eq!(sol.m12, Some(m12));
+        assert_eq!(sol.M12, Some(M12));
+        assert_eq!(sol.M21, Some(M21));
+        assert_eq!(sol.S12, Some(S12));
+    }
+
+    #[test]
+    fn test_arc_direct_solution() 
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let sol = geod.solve_direct(40.63972222, -73.77888889, 53.5, 5850e3, Caps::STANDARD);
+        let arc = geod.solve_arc_direct(40.63972222, -73.77888889, 53.5, sol.a12, Caps::STANDARD);
+        assert_approx_eq!(f64, arc.a12, sol.a12);
+        assert_approx_eq!(f64, arc.s12.unwrap(), 5850e3, epsilon = 1e-8);
+        assert_approx_eq!(f64, arc.lat2.unwrap(), sol.lat2.unwrap(), epsilon = 1e-13);
+        assert_approx_eq!(f64, arc.lon2.unwrap(), sol.lon2.unwrap(), epsilon = 1e-13);
+        assert_approx_eq!(f64, arc.azi2.unwrap(), sol.azi2.unwrap(), epsilon = 1e-13);
+    }
+}
@@ -0,0 +1,293 @@
+use crate::geodesic::caps::Caps;
+use crate::geodesic::core::Geodesic;
+use crate::geodesic::inverse::InverseGeodesic;
+use crate::geodesic::line::GeodesicLine;
+use crate::geodesic::trig;
+
+/// The intersection of two geodesic lines.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Intersection
+{
+    /// Latitude of the intersection (degrees).
+    pub lat: f64,
+
+    /// Longitude of the intersection (degrees).
+    pub lon: f64,
+
+    /// Distance from point 1 of the first line to the intersection (meters).
+    pub x: f64,
+
+    /// Distance from point 1 of the second line to the intersection (meters).
+    pub y: f64,
+
+    /// 1 if the lines are coincident and parallel, -1 if they are coincident and antiparallel, 0 otherwise.
+    pub coincidence: i8,
+}
+
+impl Intersection
+{
+    // The L1 distance of the intersection from the offsets (x0, y0) along the lines.
+    fn distance(&self, x0: f64, y0: f64) -> f64
+    {
+        (self.x - x0).abs() + (self.y - y0).abs()
+    }
+}
+
+// Solves the intersections of two geodesic lines by refining the starting offsets along the lines,
+// solving the triangle of the two points and the intersection on the authalic sphere at each step.
+struct Intersector<'a>
+{
+    geod: &'a Geodesic,
+    x: GeodesicLine,
+    y: GeodesicLine,
+    r: f64,
+    tol: f64,
+}
+
+impl<'a> Intersector<'a>
+{
+    const MAXIT: usize = 50;
+    // The limit of the distance searched by intersect_all in quarters of a great circle.
+    const MAXQUARTERS: f64 = 30.0;
+    const CAPS: Caps = Caps::LATITUDE.union(Caps::LONGITUDE).union(Caps::AZIMUTH).union(Caps::DISTANCE_IN);
+
+    fn new(geod: &'a Geodesic, x: &GeodesicLine, y: &GeodesicLine) -> Self
+    {
+        let line = |l: &GeodesicLine| geod.line(l.latitude(), l.longitude(), l.azimuth(), Self::CAPS);
+        let tol = 1e-3 * f64::EPSILON.sqrt() * geod.elps.a;
+        Self { geod, x: line(x), y: line(y), r: geod.elps.authalic_radius(), tol }
+    }
+
+    // Find the intersection from the offsets (x0, y0) along the lines, None if it does not converge.
+    fn solve(&self, x0: f64, y0: f64) -> Option<Intersection>
+    {
+        let (mut qx, mut qy) = (x0, y0);
+        for _ in 0..Self::MAXIT
+        {
+            let (px, py) = (self.x.position(qx, Self::CAPS), self.y.position(qy, Self::CAPS));
+            let (z, azixa, aziya, _a12) = self.geod.inverse(px.lat2.unwrap(), px.lon2.unwrap(), py.lat2.unwrap(), py.lon2.unwrap());
+            let (azix, aziy) = (px.azi2.unwrap(), py.azi2.unwrap());
+            if z == 0.0 || (z <= self.tol && coincidence(azix, aziy) != 0)
+            {
+                return Some(self.intersection(qx, qy));
+            }
+            // The bearings of the lines relative to the geodesic from the point on the first line to the one on the second.
+            let (sx, cx) = trig::sincosd(trig::ang_diff(azixa, azix).0);
+            let (sy, cy) = trig::sincosd(trig::ang_diff(aziya, aziy).0);
+            let (dx, dy) = if sx.abs() + sy.abs() <= f64::EPSILON.sqrt()
+            {
+                // The lines are coincident, so meet halfway.
+                (cx * z / 2.0, -cy * z / 2.0)
+            }
+            else
+            {
+                let (sz, cz) = (z / self.r).sin_cos();
+                let dx = (-sy * sz).atan2(sx * cy - cx * sy * cz);
+                let dy = (-sx * sz).atan2(sx * cy * cz - cx * sy);
+                let antipode = |d: f64| d - std::f64::consts::PI.copysign(d);
+                let (ax, ay) = (antipode(dx), antipode(dy));
+                if ax.abs() + ay.abs() < dx.abs() + dy.abs() { (ax * self.r, ay * self.r) } else { (dx * self.r, dy * self.r) }
+            };
+            qx += dx;
+            qy += dy;
+            if dx.abs() + dy.abs() <= self.tol
+            {
+                return Some(self.intersection(qx, qy));
+            }
+            if !(dx.abs() + dy.abs()).is_finite()
+            {
+                return None;
+            }
+        }
+        None
+    }
+
+    fn intersection(&self, x: f64, y: f64) -> Intersection
+    {
+        let (px, py) = (self.x.position(x, Self::CAPS), self.y.position(y, Self::CAPS));
+        let coincidence = coincidence(px.azi2.unwrap(), py.azi2.unwrap());
+        Intersection { lat: px.lat2.unwrap(), lon: px.lon2.unwrap(), x, y, coincidence }
+    }
+}
+
+// 1 if the azimuths (degrees) are parallel, -1 if they are antiparallel, 0 otherwise.
+fn coincidence(azix: f64, aziy: f64) -> i8
+{
+    let (s, c) = trig::sincosd(trig::ang_diff(azix, aziy).0);
+    if s.abs() <= f64::EPSILON.sqrt() { c.signum() as i8 } else { 0 }
+}
+
+impl Geodesic
+{
+    /// Find the intersection of the two lines nearest to their points 1, None if it does not converge.
+    ///
+    /// The distances of the intersection along the lines may be negative, i.e. behind point 1.
+    /// The intersection of coincident lines is the point halfway between their points 1.
+    pub fn intersect(&self, x: &GeodesicLine, y: &GeodesicLine) -> Option<Intersection>
+    {
+        Intersector::new(self, x, y).solve(0.0, 0.0)
+    }
+
+    /// Find all the intersections of the two lines with |x| + |y| not greater than `maxdist` (meters).
+    ///
+    /// The intersections are sorted by |x| + |y|, i.e. the sum of their distances from the points 1.
+    /// No intersection is returned if `maxdist` is negative or not finite, and `maxdist` is limited
+    /// to 30 quarters of a great circle, about 3e8 meters.
+    pub fn intersect_all(&self, x: &GeodesicLine, y: &GeodesicLine, maxdist: f64) -> Vec<Intersection>
+    {
+        if !(maxdist.is_finite() && maxdist >= 0.0)
+        {
+            return Vec::new();
+        }
+        let solver = Intersector::new(self, x, y);
+        // Two intersections are at least half a great circle apart along the lines unless they are coincident.
+        let d = std::f64::consts::FRAC_PI_2 * solver.r;
+        let maxdist = maxdist.min(Intersector::MAXQUARTERS * d);
+        let n = (maxdist / d).ceil() as i64 + 1;
+        let mut found: Vec<Intersection> = Vec::new();
+        for i in -n..=n
+        {
+            for j in -(n - i.abs())..=(n - i.abs())
+            {
+                let Some(p) = solver.solve(i as f64 * d, j as f64 * d) else { continue };
+                if p.distance(0.0, 0.0) <= maxdist && found.iter().all(|q| q.distance(p.x, p.y) > 1e3 * solver.tol)
+                {
+                    found.push(p);
+                }
+            }
+        }
+        found.sort_by(|p, q| p.distance(0.0, 0.0).total_cmp(&q.distance(0.0, 0.0)));
+        found
+    }
+
+    /// Find the intersection of the two segments from point 1 to point 3 of the lines.
+    ///
+    /// None is returned if the segments do not intersect, or if point 3 of either line is not set.
+    pub fn intersect_segments(&self, x: &GeodesicLine, y: &GeodesicLine) -> Option<Intersection>
+    {
+        let (sx, sy) = (x.distance(), y.distance());
+        let solver = Intersector::new(self, x, y);
+        let p = solver.solve(sx / 2.0, sy / 2.0)?;
+        let within = |s: f64, length: f64| s >= length.min(0.0) - solver.tol && s <= length.max(0.0) + solver.tol;
+        (within(p.x, sx) && within(p.y, sy)).then_some(p)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use rstest::*;
+    use float_cmp::assert_approx_eq;
+    use crate::earth::models::WGS84;
+
+    fn check(geod: &Geodesic, x: &GeodesicLine, y: &GeodesicLine, p: &Intersection)
+    {
+        let px = x.position(p.x, Caps::LATITUDE | Caps::LONGITUDE);
+        let py = y.position(p.y, Caps::LATITUDE | Caps::LONGITUDE);
+        let z: f64 = geod.inverse(px.lat2.unwrap(), px.lon2.unwrap(), py.lat2.unwrap(), py.lon2.unwrap());
+        assert!(z < 1e-6, "{p:?} is {z} meters apart");
+        let z: f64 = geod.inverse(px.lat2.unwrap(), px.lon2.unwrap(), p.lat, p.lon);
+        assert!(z < 1e-6);
+    }
+
+    #[test]
+    fn test_intersect_meridian()
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let equator = geod.line(0.0, 0.0, 90.0, Caps::STANDARD | Caps::DISTANCE_IN);
+        let meridian = geod.line(-10.0, 20.0, 0.0, Caps::STANDARD | Caps::DISTANCE_IN);
+        let p = geod.intersect(&equator, &meridian).unwrap();
+        let s: f64 = geod.inverse(-10.0, 20.0, 0.0, 20.0);
+        assert_approx_eq!(f64, p.x, geod.elps.a * 20f64.to_radians(), epsilon = 1e-6);
+        assert_approx_eq!(f64, p.y, s, epsilon = 1e-6);
+        assert_approx_eq!(f64, p.lat, 0.0, epsilon = 1e-12);
+        assert_approx_eq!(f64, p.lon, 20.0, epsilon = 1e-12);
+        assert_eq!(p.coincidence, 0);
+    }
+
+    #[rstest]
+    #[case((40.6, -73.8, 49.01666667, 2.55), (60.0, -30.0, 30.0, -10.0))]
+    #[case((-33.9, 18.4, -37.8, 145.0), (-70.0, 80.0, 10.0, 90.0))]
+    #[case((51.5, -0.1, 35.7, 139.7), (30.0, 60.0, 80.0, 100.0))]
+    #[case((1.0, 1.0, 2.0, 2.0), (1.0, 2.0, 2.0, 1.0))]
+    fn test_intersect(#[case] x: (f64, f64, f64, f64), #[case] y: (f64, f64, f64, f64))
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let ((lat1x, lon1x, lat2x, lon2x), (lat1y, lon1y, lat2y, lon2y)) = (x, y);
+        let x = geod.inverse_line(lat1x, lon1x, lat2x, lon2x, Caps::STANDARD | Caps::DISTANCE_IN);
+        let y = geod.inverse_line(lat1y, lon1y, lat2y, lon2y, Caps::STANDARD | Caps::DISTANCE_IN);
+        let p = geod.intersect(&x, &y).unwrap();
+        check(&geod, &x, &y, &p);
+        let q = geod.intersect_segments(&x, &y).unwrap();
+        check(&geod, &x, &y, &q);
+        assert_approx_eq!(f64, q.x, p.x, epsilon = 1e-6);
+        assert_approx_eq!(f64, q.y, p.y, epsilon = 1e-6);
+        assert!(q.x > 0.0 && q.x < x.distance() && q.y > 0.0 && q.y < y.distance());
+    }
+
+    #[test]
+    fn test_intersect_segments_disjoint()
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let x = geod.inverse_line(0.0, 0.0, 10.0, 10.0, Caps::STANDARD | Caps::DISTANCE_IN);
+        let y = geod.inverse_line(0.0, 10.0, 4.0, 6.0, Caps::STANDARD | Caps::DISTANCE_IN);
+        assert_eq!(geod.intersect_segments(&x, &y), None);
+        let p = geod.intersect(&x, &y).unwrap();
+        check(&geod, &x, &y, &p);
+        assert!(p.y > y.distance());
+
+        // Segments sharing an endpoint.
+        let y = geod.inverse_line(10.0, 10.0, 0.0, 20.0, Caps::STANDARD | Caps::DISTANCE_IN);
+        let p = geod.intersect_segments(&x, &y).unwrap();
+        assert_approx_eq!(f64, p.x, x.distance(), epsilon = 1e-6);
+        assert_approx_eq!(f64, p.y, 0.0, epsilon = 1e-6);
+
+        // Point 3 is not set.
+        let y = geod.line(0.0, 10.0, -45.0, Caps::STANDARD | Caps::DISTANCE_IN);
+        assert_eq!(geod.intersect_segments(&x, &y), None);
+    }
+
+    #[rstest]
+    #[case(1.0)]
+    #[case(-1.0)]
+    fn test_intersect_coincident(#[case] direction: f64)
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let x = geod.line(10.0, 20.0, 30.0, Caps::STANDARD | Caps::DISTANCE_IN);
+        let p = x.position(1e6, Caps::STANDARD);
+        let azi = if direction > 0.0 { p.azi2.unwrap() } else { p.azi2.unwrap() + 180.0 };
+        let y = geod.line(p.lat2.unwrap(), p.lon2.unwrap(), azi, Caps::STANDARD | Caps::DISTANCE_IN);
+        let q = geod.intersect(&x, &y).unwrap();
+        assert_eq!(q.coincidence, direction as i8);
+        assert_approx_eq!(f64, q.x, 0.5e6, epsilon = 1e-3);
+        assert_approx_eq!(f64, q.y, -0.5e6 * direction, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_intersect_all()
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let equator = geod.line(0.0, 0.0, 90.0, Caps::STANDARD | Caps::DISTANCE_IN);
+        let meridian = geod.line(0.0, 90.0, 0.0, Caps::STANDARD | Caps::DISTANCE_IN);
+        let quarter = geod.elps.a * std::f64::consts::FRAC_PI_2;
+        let quarter_meridian: f64 = geod.inverse(0.0, 0.0, 90.0, 0.0);
+        let half = 2.0 * quarter_meridian;
+        let expected = [(quarter, 0.0), (-quarter, half), (-quarter, -half), (-3.0 * quarter, 0.0)];
+
+        let all = geod.intersect_all(&equator, &meridian, 4e7);
+        assert_eq!(all.len(), expected.len());
+        for p in all.iter()
+        {
+            check(&geod, &equator, &meridian, p);
+            assert!(expected.iter().any(|&(x, y)| (p.x - x).abs() < 1e-6 && (p.y - y).abs() < 1e-6), "{p:?}");
+        }
+        assert_approx_eq!(f64, all[0].x, quarter, epsilon = 1e-6);
+        assert_eq!(geod.intersect_all(&equator, &meridian, 2e7).len(), 1);
+        assert!(geod.intersect_all(&equator, &meridian, 1e7).is_empty());
+        assert!(geod.intersect_all(&equator, &meridian, -1.0).is_empty());
+        assert!(geod.intersect_all(&equator, &meridian, f64::NAN).is_empty());
+        assert!(geod.intersect_all(&equator, &meridian, f64::INFINITY).is_empty());
+        assert_eq!(geod.intersect_all(&equator, &meridian, 1e300), geod.intersect_all(&equator, &meridian, 4e8));
+    }
+}
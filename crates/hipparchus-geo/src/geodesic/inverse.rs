@@ -1,6 +1,6 @@
 #![allow(non_snake_case)]
 use crate::geodesic::core::Geodesic;
-use crate::geodesic::caps::Caps;
+use crate::geodesic::caps::{Caps, Mask};
 
 /// Measure the distance (and other values) between two points.
 ///
@@ -42,6 +42,76 @@ pub trait InverseGeodesic<T>
     fn inverse(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> T;
 }
 
+/// The solution of the inverse geodesic problem with named fields.
+///
+/// Only the outputs requested by the capabilities passed to [`Geodesic::solve_inverse`] are
+/// populated, the others are left as `None`:
+/// - `Caps::DISTANCE`: s12
+/// - `Caps::AZIMUTH`: azi1 & azi2
+/// - `Caps::REDUCEDLENGTH`: m12
+/// - `Caps::GEODESICSCALE`: M12 & M21
+/// - `Caps::AREA`: S12
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct InverseSolution
+{
+    /// arc length of between point 1 and point 2 (degrees), always populated.
+    pub a12: f64,
+
+    /// distance between point 1 and point 2 (meters).
+    pub s12: Option<f64>,
+
+    /// azimuth at point 1 (degrees).
+    pub azi1: Option<f64>,
+
+    /// (forward) azimuth at point 2 (degrees).
+    pub azi2: Option<f64>,
+
+    /// reduced length of geodesic (meters).
+    pub m12: Option<f64>,
+
+    /// geodesic scale of point 2 relative to point 1 (dimensionless).
+    pub M12: Option<f64>,
+
+    /// geodesic scale of point 1 relative to point 2 (dimensionless).
+    pub M21: Option<f64>,
+
+    /// area under the geodesic (meters<sup>2</sup>).
+    pub S12: Option<f64>,
+}
+
+impl InverseSolution
+{
+    /// Build the solution from the raw outputs of `Geodesic::_gen_inverse_azi`, keeping the requested ones only.
+    pub(crate) fn with(outmask: Caps, raw: (f64, f64, f64, f64, f64, f64, f64, f64)) -> Self
+    {
+        let (a12, s12, azi1, azi2, m12, M12, M21, S12) = raw;
+        let outmask = outmask & Mask::OUT;
+        let pick = |caps: Caps, v: f64| if outmask.intersects(caps) { Some(v) } else { None };
+        Self
+        {
+            a12,
+            s12: pick(Caps::DISTANCE, s12),
+            azi1: pick(Caps::AZIMUTH, azi1),
+            azi2: pick(Caps::AZIMUTH, azi2),
+            m12: pick(Caps::REDUCEDLENGTH, m12),
+            M12: pick(Caps::GEODESICSCALE, M12),
+            M21: pick(Caps::GEODESICSCALE, M21),
+            S12: pick(Caps::AREA, S12),
+        }
+    }
+}
+
+impl Geodesic
+{
+    /// Solve the inverse problem and return the outputs selected by `caps` by name.
+    ///
+    /// See the documentation for the InverseGeodesic trait for the meaning of the arguments and outputs.
+    pub fn solve_inverse(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64, caps: Caps) -> InverseSolution
+    {
+        InverseSolution::with(caps, self._gen_inverse_azi(lat1, lon1, lat2, lon2, caps))
+    }
+}
+
 impl InverseGeodesic<f64> for Geodesic 
 {
     /// See the documentation for the InverseGeodesic trait.
@@ -215,6 +285,18 @@ impl InverseGeodesic<(f64, f64, f64, f64, f64, f64, f64, f64)> for Geodesic
     }
 }
 
+impl InverseGeodesic<InverseSolution> for Geodesic 
+{
+    /// See the documentation for the InverseGeodesic trait.
+    ///
+    /// # Returns
+    /// - all outputs of the inverse problem by name, see `InverseSolution`.
+    fn inverse(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> InverseSolution 
+    {
+        self.solve_inverse(lat1, lon1, lat2, lon2, Caps::ALL)
+    }
+}
+
 #[cfg(test)]
 mod tests 
 {
@@ -455,4 +537,40 @@ mod tests
         assert_approx_eq!(f64, azi2, 134.22776532670, epsilon = 0.5e-11);
         assert_approx_eq!(f64, s12, 19974354.765767, epsilon = 0.5e-6);
     }
+
+    #[test]
+    fn test_inverse_solution_caps() 
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let sol = geod.solve_inverse(40.6, -73.8, 49.01666667, 2.55, Caps::DISTANCE | Caps::AZIMUTH | Caps::AREA);
+        assert_approx_eq!(f64, sol.azi1.unwrap(), 53.47022, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, sol.azi2.unwrap(), 111.59367, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, sol.s12.unwrap(), 5853226.0, epsilon = 0.5);
+        assert!(sol.S12.is_some());
+        assert!(sol.m12.is_none());
+        assert!(sol.M12.is_none());
+        assert!(sol.M21.is_none());
+
+        let sol = geod.solve_inverse(40.6, -73.8, 49.01666667, 2.55, Caps::DISTANCE);
+        assert_approx_eq!(f64, sol.s12.unwrap(), 5853226.0, epsilon = 0.5);
+        assert!(sol.azi1.is_none());
+        assert!(sol.azi2.is_none());
+        assert!(sol.S12.is_none());
+    }
+
+    #[test]
+    fn test_inverse_solution_all() 
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let sol: InverseSolution = geod.inverse(54.1589, 15.3872, 54.1591, 15.3877);
+        let (s12, azi1, azi2, m12, M12, M21, S12, a12) = geod.inverse(54.1589, 15.3872, 54.1591, 15.3877);
+        assert_eq!(sol.a12, a12);
+        assert_eq!(sol.s12, Some(s12));
+        assert_eq!(sol.azi1, Some(azi1));
+        assert_eq!(sol.azi2, Some(azi2));
+        assert_eq!(sol.m12, Some(m12));
+        assert_eq!(sol.M12, Some(M12));
+        assert_eq!(sol.M21, Some(M21));
+        assert_eq!(sol.S12, Some(S12));
+    }
 }
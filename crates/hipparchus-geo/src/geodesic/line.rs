@@ -8,8 +8,14 @@ use crate::geodesic::math;
 use crate::geodesic::coeff::*;
 use crate::geodesic::trig;
 use crate::geodesic::core::Geodesic;
-use std::collections::HashMap;
+use crate::geodesic::direct::DirectSolution;
 
+/// A geodesic line starting at a given point with a given azimuth.
+///
+/// The series coefficients of the geodesic are computed once when the line is constructed, so
+/// that many points can be placed along the same geodesic cheaply with `position` and
+/// `arc_position`. Lines are usually built with `Geodesic::line`, `Geodesic::inverse_line`,
+/// `Geodesic::direct_line` or `Geodesic::arc_direct_line`.
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
 pub struct GeodesicLine
 {
@@ -404,59 +410,125 @@ impl GeodesicLine
         (a12, lat2, lon2, azi2, s12, m12, M12, M21, S12)
     }
 
-    // not currently used, but maybe some day
-    #[allow(dead_code)]
-    pub fn Position(&self, s12: f64, outmask: Option<Caps>) -> HashMap<String, f64>
+    /// Place a point at distance `s12` (meters) from point 1 along the line.
+    ///
+    /// Only the outputs selected by `outmask` and supported by the capabilities of the line are
+    /// populated. The line must have been created with `Caps::DISTANCE_IN`, otherwise the populated
+    /// outputs are NaN.
+    pub fn position(&self, s12: f64, outmask: Caps) -> DirectSolution
     {
-        let outmask = match outmask
-        {
-            Some(outmask) => outmask,
-            None => Caps::STANDARD,
-        };
-        let mut result: HashMap<String, f64> = HashMap::new();
-        result.insert("lat1".to_string(), self.lat1);
-        result.insert("azi1".to_string(), self.azi1);
-        result.insert("s12".to_string(), s12);
-        let lon1 = if outmask.intersects(Caps::LONG_UNROLL)
+        DirectSolution::with(outmask & self.caps, self._gen_position(false, s12, outmask))
+    }
+
+    /// Place a point at arc length `a12` (degrees) from point 1 along the line.
+    ///
+    /// Only the outputs selected by `outmask` and supported by the capabilities of the line are populated.
+    pub fn arc_position(&self, a12: f64, outmask: Caps) -> DirectSolution
+    {
+        DirectSolution::with(outmask & self.caps, self._gen_position(true, a12, outmask))
+    }
+
+    /// Specify the position of point 3 on the line by the distance `s13` (meters) from point 1.
+    pub fn set_distance(&mut self, s13: f64)
+    {
+        self._s13 = s13;
+        self._a13 = self._gen_position(false, s13, Caps::empty()).0;
+    }
+
+    /// Specify the position of point 3 on the line by the arc length `a13` (degrees) from point 1.
+    pub fn set_arc(&mut self, a13: f64)
+    {
+        self._a13 = a13;
+        self._s13 = self._gen_position(true, a13, Caps::DISTANCE).4;
+    }
+
+    /// Get the latitude of point 1 (degrees).
+    pub fn latitude(&self) -> f64
+    {
+        self.lat1
+    }
+
+    /// Get the longitude of point 1 (degrees).
+    pub fn longitude(&self) -> f64
+    {
+        self.lon1
+    }
+
+    /// Get the azimuth at point 1 (degrees).
+    pub fn azimuth(&self) -> f64
+    {
+        self.azi1
+    }
+
+    /// Get the distance between point 1 and point 3 (meters), NaN if point 3 is not set.
+    pub fn distance(&self) -> f64
+    {
+        self._s13
+    }
+
+    /// Get the arc length between point 1 and point 3 (degrees), NaN if point 3 is not set.
+    pub fn arc(&self) -> f64
+    {
+        self._a13
+    }
+
+    /// Get the capabilities of the line.
+    pub fn caps(&self) -> Caps
+    {
+        self.caps
+    }
+
+    /// Get the geodesic of the ellipsoid of the line.
+    pub(crate) fn geodesic(&self) -> Geodesic
+    {
+        Geodesic::new(self._a, self.f)
+    }
+}
+
+impl Geodesic
+{
+    /// Create a geodesic line starting at (lat1, lon1) with azimuth azi1 (degrees).
+    ///
+    /// The capabilities `caps` select the outputs which can be computed by `position` and
+    /// `arc_position` on the line; add `Caps::DISTANCE_IN` to allow `position` to be used.
+    pub fn line(&self, lat1: f64, lon1: f64, azi1: f64, caps: Caps) -> GeodesicLine
+    {
+        GeodesicLine::new(self, lat1, lon1, azi1, Some(caps), None, None)
+    }
+
+    /// Create a geodesic line from point 1 to point 2, with point 3 set at point 2.
+    pub fn inverse_line(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64, caps: Caps) -> GeodesicLine
+    {
+        let (a12, _s12, salp1, calp1, _salp2, _calp2, _m12, _M12, _M21, _S12) =
+            self._gen_inverse(lat1, lon1, lat2, lon2, Caps::empty());
+        let azi1 = trig::atan2d(salp1, calp1);
+        let caps = if (caps & Mask::OUT).intersects(Caps::DISTANCE_IN & Mask::OUT)
         {
-            self.lon1
+            caps | Caps::DISTANCE
         }
         else
         {
-            self.lon1.norm_degrees(Remainder::InvertedSymmetry)
+            caps
         };
-        result.insert("lon1".to_string(), lon1);
+        let mut line = GeodesicLine::new(self, lat1, lon1, azi1, Some(caps), Some(salp1), Some(calp1));
+        line.set_arc(a12);
+        line
+    }
 
-        let (a12, lat2, lon2, azi2, _s12, m12, M12, M21, S12) =
-            self._gen_position(false, s12, outmask);
-        let outmask = outmask & Mask::OUT;
-        result.insert("a12".to_string(), a12);
-        if outmask.intersects(Caps::LATITUDE) 
-        {
-            result.insert("lat2".to_string(), lat2);
-        }
-        if outmask.intersects(Caps::LONGITUDE)
-        {
-            result.insert("lon2".to_string(), lon2);
-        }
-        if outmask.intersects(Caps::AZIMUTH)
-        {
-            result.insert("azi2".to_string(), azi2);
-        }
-        if outmask.intersects(Caps::REDUCEDLENGTH)
-        {
-            result.insert("m12".to_string(), m12);
-        }
-        if outmask.intersects(Caps::GEODESICSCALE)
-        {
-            result.insert("M12".to_string(), M12);
-            result.insert("M21".to_string(), M21);
-        }
-        if outmask.intersects(Caps::AREA)
-        {
-            result.insert("S12".to_string(), S12);
-        }
-        result
+    /// Create a geodesic line starting at (lat1, lon1) with azimuth azi1, with point 3 set at distance s12 (meters).
+    pub fn direct_line(&self, lat1: f64, lon1: f64, azi1: f64, s12: f64, caps: Caps) -> GeodesicLine
+    {
+        let mut line = self.line(lat1, lon1, azi1, caps | Caps::DISTANCE_IN);
+        line.set_distance(s12);
+        line
+    }
+
+    /// Create a geodesic line starting at (lat1, lon1) with azimuth azi1, with point 3 set at arc length a12 (degrees).
+    pub fn arc_direct_line(&self, lat1: f64, lon1: f64, azi1: f64, a12: f64, caps: Caps) -> GeodesicLine
+    {
+        let mut line = self.line(lat1, lon1, azi1, caps);
+        line.set_arc(a12);
+        line
     }
 }
 
@@ -466,6 +538,7 @@ mod tests
     use super::*;
     use crate::geodesic::core::Geodesic;
     use crate::earth::models::WGS84;
+    use float_cmp::assert_approx_eq;
 
     #[test]
     fn test_gen_position() 
@@ -510,4 +583,65 @@ mod tests
         assert!(gl._s13.is_nan());
         assert!(gl._a13.is_nan());
     }
+
+    #[test]
+    fn test_position() 
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let line = geod.line(40.63972222, -73.77888889, 53.5, Caps::STANDARD | Caps::DISTANCE_IN);
+        let res = line.position(5850e3, Caps::STANDARD);
+        let sol = geod.solve_direct(40.63972222, -73.77888889, 53.5, 5850e3, Caps::STANDARD);
+        assert_eq!(res, sol);
+        assert!(res.m12.is_none());
+        assert!(res.S12.is_none());
+
+        // outputs not supported by the capabilities of the line are not populated
+        let res = line.position(5850e3, Caps::ALL);
+        assert!(res.lat2.is_some());
+        assert!(res.m12.is_none());
+        assert!(res.S12.is_none());
+    }
+
+    #[test]
+    fn test_arc_position() 
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let line = geod.line(40.63972222, -73.77888889, 53.5, Caps::STANDARD);
+        let res = line.arc_position(52.6, Caps::STANDARD);
+        let sol = geod.solve_arc_direct(40.63972222, -73.77888889, 53.5, 52.6, Caps::STANDARD);
+        assert_eq!(res, sol);
+
+        // distance mode is not available without DISTANCE_IN
+        let res = line.position(5850e3, Caps::LATITUDE);
+        assert!(res.lat2.unwrap().is_nan());
+    }
+
+    #[test]
+    fn test_inverse_line() 
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let line = geod.inverse_line(40.6, -73.8, 49.01666667, 2.55, Caps::STANDARD | Caps::DISTANCE_IN);
+        let sol = geod.solve_inverse(40.6, -73.8, 49.01666667, 2.55, Caps::STANDARD);
+        assert_approx_eq!(f64, line.azimuth(), sol.azi1.unwrap(), epsilon = 1e-13);
+        assert_approx_eq!(f64, line.distance(), sol.s12.unwrap(), epsilon = 1e-8);
+        assert_approx_eq!(f64, line.arc(), sol.a12, epsilon = 1e-13);
+        let res = line.position(line.distance(), Caps::STANDARD);
+        assert_approx_eq!(f64, res.lat2.unwrap(), 49.01666667, epsilon = 1e-12);
+        assert_approx_eq!(f64, res.lon2.unwrap(), 2.55, epsilon = 1e-12);
+        assert_approx_eq!(f64, res.azi2.unwrap(), sol.azi2.unwrap(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_direct_line() 
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let line = geod.direct_line(40.63972222, -73.77888889, 53.5, 5850e3, Caps::STANDARD);
+        assert_eq!(line.distance(), 5850e3);
+        let sol = geod.solve_direct(40.63972222, -73.77888889, 53.5, 5850e3, Caps::STANDARD);
+        assert_approx_eq!(f64, line.arc(), sol.a12, epsilon = 1e-13);
+
+        let line = geod.arc_direct_line(40.63972222, -73.77888889, 53.5, sol.a12, Caps::STANDARD);
+        assert_approx_eq!(f64, line.arc(), sol.a12);
+        assert_approx_eq!(f64, line.distance(), 5850e3, epsilon = 1e-8);
+    }
 }
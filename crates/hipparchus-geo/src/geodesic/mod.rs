@@ -1,7 +1,14 @@
 pub use core::Geodesic;
-pub use inverse::InverseGeodesic;
-pub use direct::DirectGeodesic;
-pub use polygon::{PolygonArea, Winding};
+pub use caps::Caps;
+pub use inverse::{InverseGeodesic, InverseSolution};
+pub use direct::{DirectGeodesic, DirectSolution};
+pub use line::GeodesicLine;
+pub use waypoint::{Waypoint, Waypoints};
+pub use intersect::Intersection;
+pub use track::TrackSolution;
+pub use polygon::{PolygonArea, PolygonMeasure, Polygon, MultiPolygon, PolygonError, Winding, Geofence};
+pub(crate) use polygon::PolygonAccumulator;
+pub(crate) use waypoint::Spacing;
 
 mod core;
 mod caps;
@@ -12,4 +19,7 @@ mod constants;
 mod coeff;
 mod direct;
 mod inverse;
-mod trig;
+pub(crate) mod trig;
+mod waypoint;
+mod intersect;
+mod track;
@@ -1,6 +1,7 @@
 use crate::geodesic::trig;
 use crate::geodesic::caps::Caps;
 use crate::geodesic::core::Geodesic;
+use crate::coords::latlon::LatLon;
 use hipparchus_az::{Degrees, Remainder};
 
 #[cfg(feature = "accurate")]
@@ -10,7 +11,7 @@ use accurate::traits::*;
 ///
 /// The standard winding of a Simple Feature polygon is counter-clockwise. However, if the polygon is a hole, then the winding is clockwise.
 /// ESRI Shapefile polygons are opposite, with the outer-ring being clockwise and holes being counter-clockwise.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Winding 
 {
     Clockwise,
@@ -25,11 +26,37 @@ impl Default for Winding
     }
 }
 
-/// Compute the perimeter and area of a polygon on a Geodesic.
+impl Winding
+{
+    /// Get the opposite winding.
+    pub fn reverse(self) -> Self
+    {
+        match self
+        {
+            Winding::Clockwise => Winding::CounterClockwise,
+            Winding::CounterClockwise => Winding::Clockwise,
+        }
+    }
+}
+
+/// The perimeter, area and vertex count of a polygon.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PolygonMeasure
+{
+    /// Perimeter of the polygon (meters).
+    pub perimeter: f64,
+
+    /// Area of the polygon (meters²), positive if the polygon is wound as expected.
+    pub area: f64,
+
+    /// Number of vertices of the polygon.
+    pub count: usize,
+}
+
+// The running sums of the edges of a polygon, shared by the geodesic and rhumb polygons.
 #[derive(Debug, Clone)]
-pub struct PolygonArea<'a> 
+pub(crate) struct PolygonAccumulator
 {
-    geoid: &'a Geodesic,
     winding: Winding,
     num: usize,
 
@@ -52,12 +79,12 @@ pub struct PolygonArea<'a>
     latest_lon: f64,
 }
 
-/// PolygonArea can be used to compute the perimeter and area of a polygon on a Geodesic.
-impl<'a> PolygonArea<'a> {
-    /// Create a new PolygonArea using a Geodesic.
-    pub fn new(geoid: &'a Geodesic, winding: Winding) -> PolygonArea {
-        PolygonArea {
-            geoid,
+impl PolygonAccumulator
+{
+    pub(crate) fn new(winding: Winding) -> Self
+    {
+        Self
+        {
             winding,
 
             num: 0,
@@ -82,8 +109,14 @@ impl<'a> PolygonArea<'a> {
         }
     }
 
-    /// Add a point to the polygon
-    pub fn add_point(&mut self, lat: f64, lon: f64)
+    // Get the number of the points added.
+    pub(crate) fn count(&self) -> usize
+    {
+        self.num
+    }
+
+    // Add a point, `edge` solves the inverse problem from the latest point for the distance and the area.
+    pub(crate) fn add_point(&mut self, lat: f64, lon: f64, edge: impl FnOnce(f64, f64) -> (f64, f64))
     {
         if self.num == 0
         {
@@ -92,12 +125,9 @@ impl<'a> PolygonArea<'a> {
         }
         else
         {
-            #[allow(non_snake_case)]
-            let(_a12, s12, _salp1, _calp1, _salp2, _calp2, _m12, _M12, _M21, S12) = self
-                .geoid
-                ._gen_inverse(self.latest_lat, self.latest_lon, lat, lon, Caps::POLYGONAREA);
+            let (s12, area12) = edge(self.latest_lat, self.latest_lon);
             self.perimetersum += s12;
-            self.areasum += S12;
+            self.areasum += area12;
             self.crossings += PolygonArea::transit(self.latest_lon, lon);
         }
         self.latest_lat = lat;
@@ -105,39 +135,108 @@ impl<'a> PolygonArea<'a> {
         self.num += 1;
     }
 
+    // Add an edge of length `distance`, `edge` solves the direct problem from the latest point for
+    // the unrolled position of the next point and the area.
+    pub(crate) fn add_edge(&mut self, distance: f64, edge: impl FnOnce(f64, f64) -> (f64, f64, f64))
+    {
+        let (lat, lon, area12) = edge(self.latest_lat, self.latest_lon);
+        self.perimetersum += distance;
+        self.areasum += area12;
+        self.crossings += PolygonArea::transitdirect(self.latest_lon, lon);
+        self.latest_lat = lat;
+        self.latest_lon = lon;
+        self.num += 1;
+    }
+
+    // Close the polygon, `edge` solves the inverse problem from the latest to the initial point.
+    pub(crate) fn compute(mut self, geoid_area: f64, sign: bool, edge: impl FnOnce(f64, f64, f64, f64) -> (f64, f64)) -> PolygonMeasure
+    {
+        if self.num > 0
+        {
+            let (s12, area12) = edge(self.latest_lat, self.latest_lon, self.initial_lat, self.initial_lon);
+            self.perimetersum += s12;
+            self.areasum += area12;
+            self.crossings += PolygonArea::transit(self.latest_lon, self.initial_lon);
+        }
+
+        let perimetersum;
+        let areasum;
+
+        #[cfg(not(feature = "accurate"))]
+        {
+            perimetersum = self.perimetersum;
+            areasum = self.areasum;
+        }
+        #[cfg(feature = "accurate")]
+        {
+            perimetersum = self.perimetersum.sum();
+            areasum = self.areasum.sum();
+        }
+
+        // Properly take into account crossings when calculating area.
+        let areasum = PolygonArea::reduce(areasum, geoid_area, self.crossings, self.winding, sign);
+
+        PolygonMeasure { perimeter: perimetersum, area: areasum, count: self.num }
+    }
+}
+
+/// Compute the perimeter and area of a polygon on a Geodesic.
+#[derive(Debug, Clone)]
+pub struct PolygonArea<'a> 
+{
+    geoid: &'a Geodesic,
+    sums: PolygonAccumulator,
+}
+
+/// PolygonArea can be used to compute the perimeter and area of a polygon on a Geodesic.
+impl<'a> PolygonArea<'a> {
+    /// Create a new PolygonArea using a Geodesic.
+    pub fn new(geoid: &'a Geodesic, winding: Winding) -> PolygonArea {
+        PolygonArea { geoid, sums: PolygonAccumulator::new(winding) }
+    }
+
+    /// Add a point to the polygon
+    pub fn add_point(&mut self, lat: f64, lon: f64)
+    {
+        self.sums.add_point(lat, lon, |lat1, lon1|
+        {
+            #[allow(non_snake_case)]
+            let (_a12, s12, _salp1, _calp1, _salp2, _calp2, _m12, _M12, _M21, S12) = self
+                .geoid
+                ._gen_inverse(lat1, lon1, lat, lon, Caps::POLYGONAREA);
+            (s12, S12)
+        });
+    }
+
     /// Add an edge to the polygon using an azimuth (in degrees) and a distance (in meters). This can only be called after at least one point has been added.
     ///
     /// # Panics
     /// Panics if no points have been added yet.
     pub fn add_edge(&mut self, azimuth: f64, distance: f64)
     {
-        if self.num == 0
+        if self.sums.count() == 0
         {
             panic!("PolygonArea::add_edge: No points added yet");
         }
 
-        #[allow(non_snake_case)]
-        let (_a12, lat, lon, _azi2, _s12, _m12, _M12, _M21, S12) = self.geoid._gen_direct
-        (
-            self.latest_lat,
-            self.latest_lon,
-            azimuth,
-            false,
-            distance,
-            Caps::POLYGONAREA,
-        );
-        self.perimetersum += distance;
-        self.areasum += S12;
-        self.crossings += PolygonArea::transitdirect(self.latest_lon, lon);
-        self.latest_lat = lat;
-        self.latest_lon = lon;
-        self.num += 1;
+        self.sums.add_edge(distance, |lat1, lon1|
+        {
+            #[allow(non_snake_case)]
+            let (_a12, lat, lon, _azi2, _s12, _m12, _M12, _M21, S12) = self.geoid._gen_direct
+            (
+                lat1,
+                lon1,
+                azimuth,
+                false,
+                distance,
+                Caps::POLYGONAREA,
+            );
+            (lat, lon, S12)
+        });
     }
 
-    /// Consumes the PolygonArea and returns the following tuple:
-    ///  - 0: Perimeter in (meters) of the polygon.
-    ///  - 1: Area (meters²) of the polygon.
-    ///  - 2: Number of points added to the polygon.
+    /// Consumes the PolygonArea and returns the perimeter (meters), the area (meters²) and the
+    /// number of points added to the polygon.
     ///
     /// # Parameters
     ///
@@ -151,51 +250,26 @@ impl<'a> PolygonArea<'a> {
     /// 1. The winding of the polygon is opposite the winding specified during creation of the PolygonArea.
     /// 2. The polygon is larger than half the planet. In this case, to get the final area of the polygon, add the area of the planet to the result. If you expect to be dealing with polygons of this size pass `signed = false` to `compute()` to get the correct result.
     ///
-    pub fn compute(mut self, sign: bool) -> (f64, f64, usize)
-    {
-        #[allow(non_snake_case)]
-        let (_a12, s12, _salp1, _calp1, _salp2, _calp2, _m12, _M12, _M21, S12) =
-            self.geoid._gen_inverse(
-                self.latest_lat,
-                self.latest_lon,
-                self.initial_lat,
-                self.initial_lon,
-                Caps::POLYGONAREA,
-            );
-        self.perimetersum += s12;
-        self.areasum += S12;
-
-        let perimetersum;
-        let areasum;
-
-        #[cfg(not(feature = "accurate"))]
-        {
-            perimetersum = self.perimetersum;
-            areasum = self.areasum;
-        }
-        #[cfg(feature = "accurate")]
+    pub fn compute(self, sign: bool) -> PolygonMeasure
+    {
+        let geoid = self.geoid;
+        self.sums.compute(geoid.area(), sign, |lat1, lon1, lat2, lon2|
         {
-            perimetersum = self.perimetersum.sum();
-            areasum = self.areasum.sum();
-        }
-
-        self.crossings += PolygonArea::transit(self.latest_lon, self.initial_lon);
-
-        // Properly take into account crossings when calculating area.
-        let areasum = self.reduce_area(areasum, sign);
-
-        (perimetersum, areasum, self.num)
+            #[allow(non_snake_case)]
+            let (_a12, s12, _salp1, _calp1, _salp2, _calp2, _m12, _M12, _M21, S12) = geoid._gen_inverse(lat1, lon1, lat2, lon2, Caps::POLYGONAREA);
+            (s12, S12)
+        })
     }
 
     /// Check what the perimeter and area would be if this point was added to the polygon without actually adding it
-    pub fn test_point(&self, lat: f64, lon: f64, sign: bool) -> (f64, f64, usize) {
+    pub fn test_point(&self, lat: f64, lon: f64, sign: bool) -> PolygonMeasure {
         let mut pa = self.clone();
         pa.add_point(lat, lon);
         pa.compute(sign)
     }
 
     /// Check what the perimeter and area would be if this edge was added to the polygon without actually adding it
-    pub fn test_edge(&self, azimuth: f64, distance: f64, sign: bool) -> (f64, f64, usize) {
+    pub fn test_edge(&self, azimuth: f64, distance: f64, sign: bool) -> PolygonMeasure {
         let mut pa = self.clone();
         pa.add_edge(azimuth, distance);
         pa.compute(sign)
@@ -203,7 +277,7 @@ impl<'a> PolygonArea<'a> {
 
     // Return 1 or -1 if crossing prime meridian in east or west direction.
     // Otherwise return zero.  longitude = +/-0 considered to be positive.
-    fn transit(lon1: f64, lon2: f64) -> i64 
+    pub(crate) fn transit(lon1: f64, lon2: f64) -> i64 
     {
         let (lon12, _lon12s) = trig::ang_diff(lon1, lon2);
         let lon1 = lon1.norm_degrees(Remainder::InvertedSymmetry);
@@ -225,7 +299,7 @@ impl<'a> PolygonArea<'a> {
         }
     }
 
-    fn transitdirect(lon1: f64, lon2: f64) -> i64 {
+    pub(crate) fn transitdirect(lon1: f64, lon2: f64) -> i64 {
         // We want to compute exactly: floor(lon2 / 360) - floor(lon1 / 360)
         let lon1 = lon1 % 720.0;
         let lon2 = lon2 % 720.0;
@@ -237,13 +311,13 @@ impl<'a> PolygonArea<'a> {
         a - b
     }
 
-    fn reduce_area(&self, area: f64, signed: bool) -> f64 {
-        let geoid_area = self.geoid.area(); // Area of the planet
+    // Reduce the accumulated area with the clockwise sense to the area of the polygon with the winding.
+    pub(crate) fn reduce(area: f64, geoid_area: f64, crossings: i64, winding: Winding, signed: bool) -> f64 {
         let mut area = area % geoid_area;
 
         // Translation of the following cpp code:
         // if (crossings & 1) area += (area < 0 ? 1 : -1) * _area0/2;
-        if self.crossings % 2 != 0 {
+        if crossings % 2 != 0 {
             if area < 0.0 {
                 area += geoid_area / 2.0;
             } else {
@@ -252,7 +326,7 @@ impl<'a> PolygonArea<'a> {
         }
 
         // Area is with the clockwise sense. If needed convert to counter-clockwise convention.
-        area = match self.winding {
+        area = match winding {
             Winding::Clockwise => area,
             Winding::CounterClockwise => -area,
         };
@@ -275,12 +349,346 @@ impl<'a> PolygonArea<'a> {
     }
 }
 
+/// The errors in measuring a polygon.
+///
+/// The rings are identified by the index of the polygon in the multipolygon (0 for a single
+/// polygon) and the index of the ring in the polygon (0 for the outer ring, i for the i-th hole).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PolygonError
+{
+    /// The ring has less than 3 distinct vertices.
+    TooFewVertices { polygon: usize, ring: usize },
+
+    /// The ring is not wound as expected.
+    WindingMismatch { polygon: usize, ring: usize, expected: Winding },
+}
+
+impl std::fmt::Display for PolygonError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self
+        {
+            PolygonError::TooFewVertices { polygon, ring } =>
+                write!(f, "ring {ring} of polygon {polygon} has less than 3 vertices"),
+            PolygonError::WindingMismatch { polygon, ring, expected } =>
+                write!(f, "ring {ring} of polygon {polygon} is not wound {expected:?}"),
+        }
+    }
+}
+
+impl std::error::Error for PolygonError {}
+
+/// A polygon with an outer ring and optional holes, with vertices in degrees.
+///
+/// The rings may be closed or not, i.e. the last vertex repeating the first one is ignored.
+/// The outer ring is expected to be wound as the winding given to `measure` and the holes are
+/// expected to be wound in the opposite direction. Each ring must cover less than half of the
+/// ellipsoid, so that its winding can be told from the sign of its area.
+#[derive(Debug, Clone)]
+pub struct Polygon
+{
+    rings: Vec<Vec<LatLon>>,
+}
+
+impl Polygon
+{
+    /// Create a polygon with the outer ring and the holes.
+    pub fn new(outer: Vec<LatLon>, holes: Vec<Vec<LatLon>>) -> Self
+    {
+        let mut rings = Vec::with_capacity(holes.len() + 1);
+        rings.push(outer);
+        rings.extend(holes);
+        Self { rings }
+    }
+
+    /// Get the outer ring.
+    pub fn outer(&self) -> &[LatLon]
+    {
+        &self.rings[0]
+    }
+
+    /// Get the holes.
+    pub fn holes(&self) -> &[Vec<LatLon>]
+    {
+        &self.rings[1..]
+    }
+
+    /// Add a hole to the polygon.
+    pub fn add_hole(&mut self, hole: Vec<LatLon>)
+    {
+        self.rings.push(hole);
+    }
+
+    /// Compute the perimeter, the area and the vertex count of the polygon on the geodesic.
+    ///
+    /// The perimeter includes the holes and the area excludes them. The area is positive if the
+    /// outer ring is wound as `winding`, otherwise an error is returned as for mismatching holes.
+    pub fn measure(&self, geod: &Geodesic, winding: Winding) -> Result<PolygonMeasure, PolygonError>
+    {
+        self.measure_nth(geod, winding, 0)
+    }
+
+    fn measure_nth(&self, geod: &Geodesic, winding: Winding, polygon: usize) -> Result<PolygonMeasure, PolygonError>
+    {
+        let mut total = PolygonMeasure { perimeter: 0.0, area: 0.0, count: 0 };
+        for (ring, vertices) in self.rings.iter().enumerate()
+        {
+            let vertices = open_ring(vertices);
+            if !has_three_distinct(vertices)
+            {
+                return Err(PolygonError::TooFewVertices { polygon, ring });
+            }
+
+            let mut pa = PolygonArea::new(geod, winding);
+            vertices.iter().for_each(|v| pa.add_point(v.latitude(), v.longitude()));
+            let measure = pa.compute(true);
+            let (expected, valid) = if ring == 0
+            {
+                (winding, measure.area > 0.0)
+            }
+            else
+            {
+                (winding.reverse(), measure.area < 0.0)
+            };
+            if !valid
+            {
+                return Err(PolygonError::WindingMismatch { polygon, ring, expected });
+            }
+            total.perimeter += measure.perimeter;
+            total.area += measure.area;
+            total.count += measure.count;
+        }
+        Ok(total)
+    }
+}
+
+impl Polygon
+{
+    /// Check whether the point is inside the outer ring and outside all the holes of the polygon.
+    ///
+    /// The rings are expected to be wound as described in `measure`, the winding is not validated.
+    /// See `Geofence` for the handling of edges and boundaries, and to test many points.
+    pub fn contains(&self, geod: &Geodesic, winding: Winding, p: LatLon) -> bool
+    {
+        self.geofence(geod, winding).contains(p)
+    }
+
+    /// Prepare the polygon for the repeated point-in-polygon tests.
+    pub fn geofence<'a>(&self, geod: &'a Geodesic, winding: Winding) -> Geofence<'a>
+    {
+        Geofence { geod, polygons: vec![self.rings(geod, winding)] }
+    }
+
+    fn rings(&self, geod: &Geodesic, winding: Winding) -> Vec<Ring>
+    {
+        self.rings.iter().enumerate().map(|(i, vertices)|
+        {
+            Ring::new(geod, open_ring(vertices), if i == 0 { winding } else { winding.reverse() })
+        }).collect()
+    }
+}
+
+/// A collection of polygons.
+#[derive(Debug, Clone, Default)]
+pub struct MultiPolygon
+{
+    polygons: Vec<Polygon>,
+}
+
+impl MultiPolygon
+{
+    /// Create a multipolygon with the polygons.
+    pub fn new(polygons: Vec<Polygon>) -> Self
+    {
+        Self { polygons }
+    }
+
+    /// Get the polygons.
+    pub fn polygons(&self) -> &[Polygon]
+    {
+        &self.polygons
+    }
+
+    /// Add a polygon to the multipolygon.
+    pub fn add_polygon(&mut self, polygon: Polygon)
+    {
+        self.polygons.push(polygon);
+    }
+
+    /// Compute the total perimeter, area and vertex count of the polygons on the geodesic.
+    ///
+    /// The polygons are supposed not to overlap, see `Polygon::measure` for the expected windings.
+    pub fn measure(&self, geod: &Geodesic, winding: Winding) -> Result<PolygonMeasure, PolygonError>
+    {
+        let mut total = PolygonMeasure { perimeter: 0.0, area: 0.0, count: 0 };
+        for (i, polygon) in self.polygons.iter().enumerate()
+        {
+            let measure = polygon.measure_nth(geod, winding, i)?;
+            total.perimeter += measure.perimeter;
+            total.area += measure.area;
+            total.count += measure.count;
+        }
+        Ok(total)
+    }
+
+    /// Check whether the point is inside any of the polygons, see `Polygon::contains`.
+    pub fn contains(&self, geod: &Geodesic, winding: Winding, p: LatLon) -> bool
+    {
+        self.geofence(geod, winding).contains(p)
+    }
+
+    /// Prepare the polygons for the repeated point-in-polygon tests.
+    pub fn geofence<'a>(&self, geod: &'a Geodesic, winding: Winding) -> Geofence<'a>
+    {
+        Geofence { geod, polygons: self.polygons.iter().map(|polygon| polygon.rings(geod, winding)).collect() }
+    }
+}
+
+// Drop the last vertex of a closed ring.
+fn open_ring(vertices: &[LatLon]) -> &[LatLon]
+{
+    match vertices.split_last()
+    {
+        Some((last, others)) if others.first() == Some(last) => others,
+        _ => vertices,
+    }
+}
+
+// Check whether there are at least 3 distinct vertices.
+fn has_three_distinct(vertices: &[LatLon]) -> bool
+{
+    let Some(&a) = vertices.first() else { return false };
+    let Some(&b) = vertices.iter().find(|&&v| v != a) else { return false };
+    vertices.iter().any(|&v| v != a && v != b)
+}
+
+/// Polygons prepared for the repeated point-in-polygon tests on the geodesic.
+///
+/// The edges and the areas of the rings are computed once, so that a test costs one inverse
+/// geodesic per vertex, or two for a vertex on the meridian opposite to the point. The edges are
+/// geodesics, so the rings may cross the antimeridian or enclose a pole. Points on the boundary
+/// may be reported either inside or outside.
+#[derive(Debug, Clone)]
+pub struct Geofence<'a>
+{
+    geod: &'a Geodesic,
+
+    // the outer ring and the holes of each polygon.
+    polygons: Vec<Vec<Ring>>,
+}
+
+impl Geofence<'_>
+{
+    /// Check whether the point is inside the outer ring and outside all the holes of any polygon.
+    pub fn contains(&self, p: LatLon) -> bool
+    {
+        let (lat, lon) = (p.latitude(), p.longitude());
+        self.polygons.iter().any(|rings| match rings.split_first()
+        {
+            Some((outer, holes)) => outer.contains(self.geod, lat, lon) && !holes.iter().any(|hole| hole.contains(self.geod, lat, lon)),
+            None => false,
+        })
+    }
+}
+
+// A ring with its edges and area.
+#[derive(Debug, Clone)]
+struct Ring
+{
+    winding: Winding,
+    vertices: Vec<(f64, f64)>,
+
+    // the area under each edge from the previous vertex and whether it crosses the prime meridian.
+    edges: Vec<(f64, i64)>,
+
+    // the area of the ring (meters²), between 0 and the area of the ellipsoid.
+    area: f64,
+}
+
+impl Ring
+{
+    fn new(geod: &Geodesic, vertices: &[LatLon], winding: Winding) -> Self
+    {
+        let vertices: Vec<(f64, f64)> = vertices.iter().map(|v| (v.latitude(), v.longitude())).collect();
+        let n = vertices.len();
+        let edges: Vec<(f64, i64)> = (0..n).map(|i|
+        {
+            let (u, v) = (vertices[(i + n - 1) % n], vertices[i]);
+            #[allow(non_snake_case)]
+            let (_a12, _s12, _salp1, _calp1, _salp2, _calp2, _m12, _M12, _M21, S12) = geod._gen_inverse(u.0, u.1, v.0, v.1, Caps::AREA);
+            (S12, PolygonArea::transit(u.1, v.1))
+        }).collect();
+        let (sum, crossings) = edges.iter().fold((0.0, 0), |(sum, crossings), &(s, c)| (sum + s, crossings + c));
+        let area = PolygonArea::reduce(sum, geod.area(), crossings, winding, false);
+        Self { winding, vertices, edges, area }
+    }
+
+    // Check whether the point P is inside the ring.
+    //
+    // Seen from P, the ring either separates P from its antipode P' or not, which is told by the
+    // winding number w of the ring around P from the sweep of the azimuths at P of the vertices. It
+    // tells whether P is inside if P' is not, and the opposite otherwise. Whether P' is inside is
+    // told by the difference between the area of the ring and the area of the fan of geodesic
+    // triangles (P, Vi-1, Vi), which is a whole multiple of the area of the ellipsoid.
+    fn contains(&self, geod: &Geodesic, lat: f64, lon: f64) -> bool
+    {
+        let n = self.vertices.len();
+        if n < 3
+        {
+            return false;
+        }
+
+        // The azimuth at P and the areas under the geodesics from P to each vertex and back. The
+        // latter is the opposite of the former, but for the meridians through a pole.
+        let spokes: Vec<(f64, f64, f64)> = self.vertices.iter().map(|&(lat2, lon2)|
+        {
+            #[allow(non_snake_case)]
+            let (_a12, _s12, azi1, _azi2, _m12, _M12, _M21, S12) = geod._gen_inverse_azi(lat, lon, lat2, lon2, Caps::AZIMUTH | Caps::AREA);
+            #[allow(non_snake_case)]
+            let S21 = if trig::ang_diff(lon, lon2).0.abs() == 180.0
+            {
+                geod._gen_inverse_azi(lat2, lon2, lat, lon, Caps::AREA).7
+            }
+            else
+            {
+                -S12
+            };
+            (azi1, S12, S21)
+        }).collect();
+
+        let mut sweep = 0.0;
+        let mut fan = 0.0;
+        for i in 0..n
+        {
+            let j = (i + n - 1) % n;
+            let ((azi1, s1, _), (azi2, _, s2)) = (spokes[j], spokes[i]);
+            sweep += trig::ang_diff(azi1, azi2).0;
+
+            let (edge, transit) = self.edges[i];
+            let (u, v) = (self.vertices[j], self.vertices[i]);
+            let crossings = PolygonArea::transit(lon, u.1) + transit + PolygonArea::transit(v.1, lon);
+            fan += PolygonArea::reduce(s1 + edge + s2, geod.area(), crossings, self.winding, true);
+        }
+
+        // The sweep is clockwise while the counter-clockwise winding is the positive one.
+        let w = match self.winding
+        {
+            Winding::Clockwise => sweep / 360.0,
+            Winding::CounterClockwise => -sweep / 360.0,
+        };
+        let k = (self.area - fan) / geod.area();
+        w.round() as i64 + k.round() as i64 == 1
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::geodesic::core::Geodesic;
     use crate::earth::models::WGS84;
     use float_cmp::assert_approx_eq;
+    use rstest::*;
 
     #[test]
     fn test_simple_polygonarea() {
@@ -292,7 +700,7 @@ mod tests {
         pa.add_point(1.0, 1.0);
         pa.add_point(1.0, 0.0);
 
-        let (perimeter, area, _count) = pa.compute(true);
+        let PolygonMeasure { perimeter, area, .. } = pa.compute(true);
 
         assert_approx_eq!(f64, perimeter, 443770.917, epsilon = 1.0e-3);
         assert_approx_eq!(f64, area, 12308778361.469, epsilon = 1.0e-3);
@@ -304,7 +712,7 @@ mod tests {
         pa.add_point(1.0, 1.0);
         pa.add_point(1.0, 0.0);
 
-        let (perimeter, area, _count) = pa.compute(true);
+        let PolygonMeasure { perimeter, area, .. } = pa.compute(true);
 
         assert_approx_eq!(f64, perimeter, 443770.917, epsilon = 1.0e-3);
         assert_approx_eq!(f64, area, -12308778361.469, epsilon = 1.0e-3);
@@ -321,7 +729,7 @@ mod tests {
         pa.add_point(89.0, 90.0);
         pa.add_point(89.0, 180.0);
         pa.add_point(89.0, 270.0);
-        let (perimeter, area, _count) = pa.compute(true);
+        let PolygonMeasure { perimeter, area, .. } = pa.compute(true);
         assert_approx_eq!(f64, perimeter, 631819.8745, epsilon = 1.0e-4);
         assert_approx_eq!(f64, area, 24952305678.0, epsilon = 1.0);
 
@@ -330,7 +738,7 @@ mod tests {
         pa.add_point(-89.0, 90.0);
         pa.add_point(-89.0, 180.0);
         pa.add_point(-89.0, 270.0);
-        let (perimeter, area, _count) = pa.compute(true);
+        let PolygonMeasure { perimeter, area, .. } = pa.compute(true);
         assert_approx_eq!(f64, perimeter, 631819.8745, epsilon = 1.0e-4);
         assert_approx_eq!(f64, area, -24952305678.0, epsilon = 1.0);
 
@@ -339,7 +747,7 @@ mod tests {
         pa.add_point(-1.0, 0.0);
         pa.add_point(0.0, 1.0);
         pa.add_point(1.0, 0.0);
-        let (perimeter, area, _count) = pa.compute(true);
+        let PolygonMeasure { perimeter, area, .. } = pa.compute(true);
         assert_approx_eq!(f64, perimeter, 627598.2731, epsilon = 1.0e-4);
         assert_approx_eq!(f64, area, 24619419146.0, epsilon = 1.0);
 
@@ -347,7 +755,7 @@ mod tests {
         pa.add_point(90.0, 0.0);
         pa.add_point(0.0, 0.0);
         pa.add_point(0.0, 90.0);
-        let (perimeter, area, _count) = pa.compute(true);
+        let PolygonMeasure { perimeter, area, .. } = pa.compute(true);
         assert_approx_eq!(f64, perimeter, 30022685.0, epsilon = 1.0);
         assert_approx_eq!(f64, area, 63758202715511.0, epsilon = 1.0);
     }
@@ -361,7 +769,7 @@ mod tests {
         pa.add_point(89.0, 0.1);
         pa.add_point(89.0, 90.1);
         pa.add_point(89.0, -179.9);
-        let (perimeter, area, _) = pa.compute(true);
+        let PolygonMeasure { perimeter, area, .. } = pa.compute(true);
         assert_approx_eq!(f64, perimeter, 539297.0, epsilon = 1.0);
         assert_approx_eq!(f64, area, 12476152838.5, epsilon = 1.0);
     }
@@ -376,7 +784,7 @@ mod tests {
         pa.add_point(9.0, -0.00000000000001);
         pa.add_point(9.0, 180.0);
         pa.add_point(9.0, 0.0);
-        let (perimeter, area, _) = pa.compute(true);
+        let PolygonMeasure { perimeter, area, .. } = pa.compute(true);
         assert_approx_eq!(f64, perimeter, 36026861.0, epsilon = 1.0);
         assert_approx_eq!(f64, area, 0.0, epsilon = 1.0);
 
@@ -384,7 +792,7 @@ mod tests {
         pa.add_point(9.0, 0.00000000000001);
         pa.add_point(9.0, 0.0);
         pa.add_point(9.0, 180.0);
-        let (perimeter, area, _) = pa.compute(true);
+        let PolygonMeasure { perimeter, area, .. } = pa.compute(true);
         assert_approx_eq!(f64, perimeter, 36026861.0, epsilon = 1.0);
         assert_approx_eq!(f64, area, 0.0, epsilon = 1.0);
 
@@ -392,7 +800,7 @@ mod tests {
         pa.add_point(9.0, 0.00000000000001);
         pa.add_point(9.0, 180.0);
         pa.add_point(9.0, 0.0);
-        let (perimeter, area, _) = pa.compute(true);
+        let PolygonMeasure { perimeter, area, .. } = pa.compute(true);
         assert_approx_eq!(f64, perimeter, 36026861.0, epsilon = 1.0);
         assert_approx_eq!(f64, area, 0.0, epsilon = 1.0);
 
@@ -400,7 +808,7 @@ mod tests {
         pa.add_point(9.0, -0.00000000000001);
         pa.add_point(9.0, 0.0);
         pa.add_point(9.0, 180.0);
-        let (perimeter, area, _) = pa.compute(true);
+        let PolygonMeasure { perimeter, area, .. } = pa.compute(true);
         assert_approx_eq!(f64, perimeter, 36026861.0, epsilon = 1.0);
         assert_approx_eq!(f64, area, 0.0, epsilon = 1.0);
     }
@@ -414,7 +822,7 @@ mod tests {
         pa.add_point(66.562222222, 0.0);
         pa.add_point(66.562222222, 180.0);
         pa.add_point(66.562222222, 360.0);
-        let (perimeter, area, _) = pa.compute(true);
+        let PolygonMeasure { perimeter, area, .. } = pa.compute(true);
         assert_approx_eq!(f64, perimeter, 10465729.0, epsilon = 1.0);
         assert_approx_eq!(f64, area, 0.0);
     }
@@ -430,7 +838,7 @@ mod tests {
         pa.add_point(66.562222222, -180.0);
         pa.add_point(66.562222222, -360.0);
 
-        let (perimeter, area, _) = pa.compute(true);
+        let PolygonMeasure { perimeter, area, .. } = pa.compute(true);
         assert_approx_eq!(f64, perimeter, 10465729.0, epsilon = 1.0);
         assert_approx_eq!(f64, area, 0.0);
     }
@@ -448,7 +856,7 @@ mod tests {
         pa.add_point(89.0, 0.0);
         pa.add_point(89.0, 120.0);
         pa.add_point(89.0, 240.0);
-        let (perimeter, area, _) = pa.compute(true);
+        let PolygonMeasure { perimeter, area, .. } = pa.compute(true);
         assert_approx_eq!(f64, perimeter, 1160741.0, epsilon = 1.0);
         assert_approx_eq!(f64, area, 32415230256.0, epsilon = 1.0);
     }
@@ -463,7 +871,7 @@ mod tests {
         pa.add_point(2.0, 1.0);
         pa.add_point(1.0, 2.0);
         pa.add_point(3.0, 3.0);
-        let (_, area, _) = pa.compute(true);
+        let PolygonMeasure { area, .. } = pa.compute(true);
         assert_approx_eq!(f64, area, 18454562325.45119, epsilon = 1.0e-4);
 
         // Switching the winding
@@ -471,7 +879,7 @@ mod tests {
         pa.add_point(2.0, 1.0);
         pa.add_point(1.0, 2.0);
         pa.add_point(3.0, 3.0);
-        let (_, area, _) = pa.compute(true);
+        let PolygonMeasure { area, .. } = pa.compute(true);
         assert_approx_eq!(f64, area, -18454562325.45119, epsilon = 1.0e-4);
 
         // Swaping lat and lon
@@ -479,7 +887,7 @@ mod tests {
         pa.add_point(1.0, 2.0);
         pa.add_point(2.0, 1.0);
         pa.add_point(3.0, 3.0);
-        let (_, area, _) = pa.compute(true);
+        let PolygonMeasure { area, .. } = pa.compute(true);
         assert_approx_eq!(f64, area, -18454562325.45119, epsilon = 1.0e-4);
     }
 
@@ -492,21 +900,21 @@ mod tests {
         let geoid = Geodesic::model::<WGS84>();
 
         let mut pa = PolygonArea::new(&geoid, Winding::CounterClockwise);
-        let (perimeter, area, _) = pa.clone().compute(true);
+        let PolygonMeasure { perimeter, area, .. } = pa.clone().compute(true);
         assert_approx_eq!(f64, perimeter, 0.0);
         assert_approx_eq!(f64, area, 0.0);
 
-        let (perimeter, area, _) = pa.test_point(1.0, 1.0, true);
+        let PolygonMeasure { perimeter, area, .. } = pa.test_point(1.0, 1.0, true);
         assert_approx_eq!(f64, perimeter, 0.0);
         assert_approx_eq!(f64, area, 0.0);
 
         let result = std::panic::catch_unwind(|| {
-            let (_, _, _) = pa.test_edge(90.0, 1000.0, true);
+            let _ = pa.test_edge(90.0, 1000.0, true);
         });
         assert!(result.is_err());
 
         pa.add_point(1.0, 1.0);
-        let (perimeter, area, _) = pa.clone().compute(true);
+        let PolygonMeasure { perimeter, area, .. } = pa.clone().compute(true);
         assert_approx_eq!(f64, perimeter, 0.0);
         assert_approx_eq!(f64, area, 0.0);
     }
@@ -547,43 +955,43 @@ mod tests {
             pa_counter.add_point(lat, 60.0);
             pa_counter.add_point(lat, 180.0);
 
-            let (_, area, _) = pa_counter.test_point(lat, -60.0, true);
+            let PolygonMeasure { area, .. } = pa_counter.test_point(lat, -60.0, true);
             assert_approx_eq!(f64, area, i as f64 * r, epsilon = 0.5);
 
-            let (_, area, _) = pa_counter.test_point(lat, -60.0, false);
+            let PolygonMeasure { area, .. } = pa_counter.test_point(lat, -60.0, false);
             assert_approx_eq!(f64, area, i as f64 * r, epsilon = 0.5);
 
-            let (_, area, _) = pa_clockwise.test_point(lat, -60.0, true);
+            let PolygonMeasure { area, .. } = pa_clockwise.test_point(lat, -60.0, true);
             assert_approx_eq!(f64, area, -i as f64 * r, epsilon = 0.5);
 
-            let (_, area, _) = pa_clockwise.test_point(lat, -60.0, false);
+            let PolygonMeasure { area, .. } = pa_clockwise.test_point(lat, -60.0, false);
             assert_approx_eq!(f64, area, (-i as f64 * r) + a0, epsilon = 0.5);
 
-            let (_, area, _) = pa_counter.test_edge(azi, s, true);
+            let PolygonMeasure { area, .. } = pa_counter.test_edge(azi, s, true);
             assert_approx_eq!(f64, area, i as f64 * r, epsilon = 0.5);
 
-            let (_, area, _) = pa_counter.test_edge(azi, s, false);
+            let PolygonMeasure { area, .. } = pa_counter.test_edge(azi, s, false);
             assert_approx_eq!(f64, area, i as f64 * r, epsilon = 0.5);
 
-            let (_, area, _) = pa_clockwise.test_edge(azi, s, true);
+            let PolygonMeasure { area, .. } = pa_clockwise.test_edge(azi, s, true);
             assert_approx_eq!(f64, area, -i as f64 * r, epsilon = 0.5);
 
-            let (_, area, _) = pa_clockwise.test_edge(azi, s, false);
+            let PolygonMeasure { area, .. } = pa_clockwise.test_edge(azi, s, false);
             assert_approx_eq!(f64, area, (-i as f64 * r) + a0, epsilon = 0.5);
 
             pa_clockwise.add_point(lat, -60.0);
             pa_counter.add_point(lat, -60.0);
 
-            let (_, area, _) = pa_counter.clone().compute(true);
+            let PolygonMeasure { area, .. } = pa_counter.clone().compute(true);
             assert_approx_eq!(f64, area, r * i as f64, epsilon = 0.5);
 
-            let (_, area, _) = pa_counter.clone().compute(false);
+            let PolygonMeasure { area, .. } = pa_counter.clone().compute(false);
             assert_approx_eq!(f64, area, r * i as f64, epsilon = 0.5);
 
-            let (_, area, _) = pa_clockwise.clone().compute(true);
+            let PolygonMeasure { area, .. } = pa_clockwise.clone().compute(true);
             assert_approx_eq!(f64, area, -(r * i as f64), epsilon = 0.5);
 
-            let (_, area, _) = pa_clockwise.clone().compute(false);
+            let PolygonMeasure { area, .. } = pa_clockwise.clone().compute(false);
             assert_approx_eq!(f64, area, -(r * i as f64) + a0, epsilon = 0.5);
         }
     }
@@ -599,7 +1007,213 @@ mod tests {
         pa.add_edge(90.0, 1000.0);
         pa.add_edge(0.0, 1000.0);
         pa.add_edge(-90.0, 1000.0);
-        let (_, area, _) = pa.compute(true);
+        let PolygonMeasure { area, .. } = pa.compute(true);
         assert_approx_eq!(f64, area, 1000000.0, epsilon = 0.01);
     }
+
+    fn ring(vertices: &[(f64, f64)]) -> Vec<LatLon>
+    {
+        vertices.iter().map(|&(lat, lon)| LatLon::new(lat, lon)).collect()
+    }
+
+    #[test]
+    fn test_polygon_measure()
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let outer = ring(&[(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)]);
+        let polygon = Polygon::new(outer.clone(), vec![]);
+        let m = polygon.measure(&geod, Winding::CounterClockwise).unwrap();
+        assert_approx_eq!(f64, m.perimeter, 443770.917, epsilon = 1.0e-3);
+        assert_approx_eq!(f64, m.area, 12308778361.469, epsilon = 1.0e-3);
+        assert_eq!(m.count, 4);
+
+        // closed ring gives the same result
+        let mut closed = outer.clone();
+        closed.push(LatLon::new(0.0, 0.0));
+        let polygon = Polygon::new(closed, vec![]);
+        assert_eq!(polygon.measure(&geod, Winding::CounterClockwise).unwrap(), m);
+
+        // ESRI winding
+        let reversed: Vec<LatLon> = ring(&[(1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.0, 0.0)]);
+        let polygon = Polygon::new(reversed, vec![]);
+        let r = polygon.measure(&geod, Winding::Clockwise).unwrap();
+        assert_approx_eq!(f64, r.perimeter, m.perimeter, epsilon = 1.0e-6);
+        assert_approx_eq!(f64, r.area, m.area, epsilon = 1.0e-3);
+    }
+
+    #[test]
+    fn test_polygon_holes()
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let outer = ring(&[(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)]);
+        let hole1 = ring(&[(0.2, 0.2), (0.4, 0.2), (0.4, 0.4), (0.2, 0.4)]);
+        let hole2 = ring(&[(0.6, 0.6), (0.8, 0.6), (0.8, 0.8), (0.6, 0.8)]);
+
+        let m0 = Polygon::new(outer.clone(), vec![]).measure(&geod, Winding::CounterClockwise).unwrap();
+        let mut pa = PolygonArea::new(&geod, Winding::Clockwise);
+        hole1.iter().for_each(|v| pa.add_point(v.latitude(), v.longitude()));
+        let h1 = pa.compute(true);
+        let mut pa = PolygonArea::new(&geod, Winding::Clockwise);
+        hole2.iter().for_each(|v| pa.add_point(v.latitude(), v.longitude()));
+        let h2 = pa.compute(true);
+
+        let mut polygon = Polygon::new(outer, vec![hole1]);
+        polygon.add_hole(hole2);
+        assert_eq!(polygon.holes().len(), 2);
+        let m = polygon.measure(&geod, Winding::CounterClockwise).unwrap();
+        assert_approx_eq!(f64, m.area, m0.area - h1.area - h2.area, epsilon = 1.0e-3);
+        assert_approx_eq!(f64, m.perimeter, m0.perimeter + h1.perimeter + h2.perimeter, epsilon = 1.0e-6);
+        assert_eq!(m.count, 12);
+    }
+
+    #[test]
+    fn test_polygon_errors()
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let outer = ring(&[(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)]);
+        let hole = ring(&[(0.2, 0.2), (0.4, 0.2), (0.4, 0.4), (0.2, 0.4)]);
+
+        let polygon = Polygon::new(ring(&[(0.0, 0.0), (0.0, 1.0), (0.0, 0.0)]), vec![]);
+        assert_eq!(polygon.measure(&geod, Winding::CounterClockwise), Err(PolygonError::TooFewVertices { polygon: 0, ring: 0 }));
+        let polygon = Polygon::new(ring(&[(0.0, 0.0), (0.0, 1.0), (0.0, 1.0), (0.0, 0.0), (0.0, 1.0)]), vec![]);
+        assert_eq!(polygon.measure(&geod, Winding::CounterClockwise), Err(PolygonError::TooFewVertices { polygon: 0, ring: 0 }));
+
+        let polygon = Polygon::new(outer.clone(), vec![hole.clone()]);
+        assert_eq!
+        (
+            polygon.measure(&geod, Winding::Clockwise),
+            Err(PolygonError::WindingMismatch { polygon: 0, ring: 0, expected: Winding::Clockwise })
+        );
+
+        let reversed: Vec<LatLon> = hole.iter().rev().copied().collect();
+        let polygon = Polygon::new(outer, vec![hole, reversed]);
+        let err = polygon.measure(&geod, Winding::CounterClockwise).unwrap_err();
+        assert_eq!(err, PolygonError::WindingMismatch { polygon: 0, ring: 2, expected: Winding::Clockwise });
+        assert_eq!(err.to_string(), "ring 2 of polygon 0 is not wound Clockwise");
+    }
+
+    #[test]
+    fn test_multipolygon()
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let p1 = Polygon::new(ring(&[(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)]), vec![]);
+        let p2 = Polygon::new(ring(&[(10.0, 10.0), (10.0, 11.0), (11.0, 11.0), (11.0, 10.0)]), vec![]);
+        let m1 = p1.measure(&geod, Winding::CounterClockwise).unwrap();
+        let m2 = p2.measure(&geod, Winding::CounterClockwise).unwrap();
+
+        let mut mp = MultiPolygon::new(vec![p1]);
+        mp.add_polygon(p2);
+        assert_eq!(mp.polygons().len(), 2);
+        let m = mp.measure(&geod, Winding::CounterClockwise).unwrap();
+        assert_approx_eq!(f64, m.area, m1.area + m2.area, epsilon = 1.0e-3);
+        assert_approx_eq!(f64, m.perimeter, m1.perimeter + m2.perimeter, epsilon = 1.0e-6);
+        assert_eq!(m.count, 8);
+
+        mp.add_polygon(Polygon::new(ring(&[(20.0, 20.0), (21.0, 21.0)]), vec![]));
+        assert_eq!(mp.measure(&geod, Winding::CounterClockwise), Err(PolygonError::TooFewVertices { polygon: 2, ring: 0 }));
+    }
+
+    fn fence<'a>(geod: &'a Geodesic, vertices: &[(f64, f64)], winding: Winding) -> Geofence<'a>
+    {
+        Polygon::new(ring(vertices), vec![]).geofence(geod, winding)
+    }
+
+    #[rstest]
+    #[case(0.5, 0.5, true)]
+    #[case(0.999, 0.001, true)]
+    #[case(2.0, 2.0, false)]
+    #[case(-0.5, 0.5, false)]
+    #[case(-0.5, -179.5, false)]
+    fn test_contains_simple(#[case] lat: f64, #[case] lon: f64, #[case] inside: bool)
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let square = [(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)];
+        assert_eq!(fence(&geod, &square, Winding::CounterClockwise).contains(LatLon::new(lat, lon)), inside);
+        // the same ring wound clockwise encloses the rest of the ellipsoid
+        assert_eq!(fence(&geod, &square, Winding::Clockwise).contains(LatLon::new(lat, lon)), !inside);
+    }
+
+    #[rstest]
+    #[case(0.0, 180.0, true)]
+    #[case(0.0, -180.0, true)]
+    #[case(5.0, 179.0, true)]
+    #[case(-5.0, -171.0, true)]
+    #[case(0.0, 160.0, false)]
+    #[case(0.0, -160.0, false)]
+    #[case(0.0, 0.0, false)]
+    fn test_contains_antimeridian(#[case] lat: f64, #[case] lon: f64, #[case] inside: bool)
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let ring = [(-10.0, 170.0), (-10.0, -170.0), (10.0, -170.0), (10.0, 170.0)];
+        assert_eq!(fence(&geod, &ring, Winding::CounterClockwise).contains(LatLon::new(lat, lon)), inside);
+    }
+
+    #[rstest]
+    #[case(90.0, 0.0, true)]
+    #[case(89.0, 45.0, true)]
+    #[case(83.5, 45.0, true)]
+    #[case(82.0, 0.0, true)]
+    #[case(82.0, 45.0, false)]
+    #[case(70.0, 0.0, false)]
+    #[case(-89.0, 0.0, false)]
+    fn test_contains_pole(#[case] lat: f64, #[case] lon: f64, #[case] inside: bool)
+    {
+        let geod = Geodesic::model::<WGS84>();
+        // the edges between the vertices are geodesics bulging towards the pole
+        let ring = [(80.0, 0.0), (80.0, 90.0), (80.0, 180.0), (80.0, -90.0)];
+        assert_eq!(fence(&geod, &ring, Winding::CounterClockwise).contains(LatLon::new(lat, lon)), inside);
+        let ring = [(-80.0, 0.0), (-80.0, 90.0), (-80.0, 180.0), (-80.0, -90.0)];
+        assert_eq!(fence(&geod, &ring, Winding::Clockwise).contains(LatLon::new(-lat, lon)), inside);
+    }
+
+    #[rstest]
+    #[case(0.0, 0.0, true)]
+    #[case(90.0, 0.0, true)]
+    #[case(-50.0, 123.0, true)]
+    #[case(-70.0, 0.0, false)]
+    #[case(-90.0, 0.0, false)]
+    fn test_contains_large(#[case] lat: f64, #[case] lon: f64, #[case] inside: bool)
+    {
+        let geod = Geodesic::model::<WGS84>();
+        // the ring encloses more than half of the ellipsoid
+        let ring = [(-60.0, 0.0), (-60.0, 60.0), (-60.0, 120.0), (-60.0, 180.0), (-60.0, -120.0), (-60.0, -60.0)];
+        assert_eq!(fence(&geod, &ring, Winding::CounterClockwise).contains(LatLon::new(lat, lon)), inside);
+    }
+
+    #[test]
+    fn test_contains_edges()
+    {
+        let geod = Geodesic::model::<WGS84>();
+        assert!(!fence(&geod, &[], Winding::CounterClockwise).contains(LatLon::new(0.0, 0.0)));
+        assert!(!fence(&geod, &[(0.0, 0.0), (0.0, 0.01)], Winding::CounterClockwise).contains(LatLon::new(0.0, 0.0)));
+        let square = fence(&geod, &[(0.0, 0.0), (0.0, 0.009), (0.009, 0.009), (0.009, 0.0)], Winding::CounterClockwise);
+        assert!(square.contains(LatLon::new(0.0045, 0.0045)));
+        assert!(!square.contains(LatLon::new(0.0095, 0.0045)));
+        assert!(!square.contains(LatLon::new(-0.0005, 0.0045)));
+    }
+
+    #[test]
+    fn test_polygon_contains()
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let outer = ring(&[(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0), (0.0, 0.0)]);
+        let hole = ring(&[(0.2, 0.2), (0.4, 0.2), (0.4, 0.4), (0.2, 0.4)]);
+        let polygon = Polygon::new(outer, vec![hole]);
+        assert!(polygon.contains(&geod, Winding::CounterClockwise, LatLon::new(0.5, 0.5)));
+        assert!(!polygon.contains(&geod, Winding::CounterClockwise, LatLon::new(0.3, 0.3)));
+        assert!(!polygon.contains(&geod, Winding::CounterClockwise, LatLon::new(1.5, 0.5)));
+
+        let other = Polygon::new(ring(&[(10.0, 179.0), (10.0, -179.0), (11.0, -179.0), (11.0, 179.0)]), vec![]);
+        let mp = MultiPolygon::new(vec![polygon, other]);
+        assert!(mp.contains(&geod, Winding::CounterClockwise, LatLon::new(0.5, 0.5)));
+        assert!(mp.contains(&geod, Winding::CounterClockwise, LatLon::new(10.5, 180.0)));
+        assert!(!mp.contains(&geod, Winding::CounterClockwise, LatLon::new(0.3, 0.3)));
+        assert!(!mp.contains(&geod, Winding::CounterClockwise, LatLon::new(10.5, 0.0)));
+
+        let fence = mp.geofence(&geod, Winding::CounterClockwise);
+        for (lat, lon, inside) in [(0.5, 0.5, true), (10.5, 180.0, true), (0.3, 0.3, false), (10.5, 0.0, false), (-10.5, 180.0, false)]
+        {
+            assert_eq!(fence.contains(LatLon::new(lat, lon)), inside);
+        }
+    }
 }
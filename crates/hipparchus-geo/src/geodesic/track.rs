@@ -0,0 +1,238 @@
+use crate::geodesic::caps::Caps;
+use crate::geodesic::core::Geodesic;
+use crate::geodesic::inverse::InverseGeodesic;
+use crate::geodesic::line::GeodesicLine;
+use crate::geodesic::trig;
+
+/// The point of a geodesic line closest to a given point.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TrackSolution
+{
+    /// Latitude of the closest point (degrees).
+    pub lat: f64,
+
+    /// Longitude of the closest point (degrees).
+    pub lon: f64,
+
+    /// Along-track distance from point 1 of the line to the closest point (meters).
+    pub along: f64,
+
+    /// Cross-track distance from the closest point to the given point, positive if it is on the right of the line (meters).
+    pub cross: f64,
+}
+
+impl GeodesicLine
+{
+    const TRACK_MAXIT: usize = 50;
+    const TRACK_CAPS: Caps = Caps::LATITUDE.union(Caps::LONGITUDE).union(Caps::AZIMUTH).union(Caps::DISTANCE_IN);
+
+    /// Find the point of the whole line closest to the point (lat, lon), NaN if it does not converge.
+    ///
+    /// The geodesic from the closest point to the given point is perpendicular to the line. The
+    /// along-track distance may be negative, i.e. behind point 1.
+    pub fn track(&self, lat: f64, lon: f64) -> TrackSolution
+    {
+        let (geod, line) = self.track_line();
+        let r = geod.elps.authalic_radius();
+        let tol = 1e-3 * f64::EPSILON.sqrt() * geod.elps.a;
+        let mut s = 0.0;
+        for _ in 0..Self::TRACK_MAXIT
+        {
+            // Solve the right triangle on the authalic sphere for the distance to the nearer foot of the perpendicular.
+            let (_lat, _lon, d, _sin, cos) = offset(&geod, &line, s, lat, lon);
+            let (sd, cd) = (d / r).sin_cos();
+            let ds = r * (cos * sd).atan2(cd);
+            s += ds;
+            if ds.abs() <= tol
+            {
+                return track_solution(&geod, &line, s, lat, lon);
+            }
+        }
+        TrackSolution { lat: f64::NAN, lon: f64::NAN, along: f64::NAN, cross: f64::NAN }
+    }
+
+    /// Find the point of the segment from point 1 to point 3 of the line closest to the point (lat, lon).
+    ///
+    /// The closest point is one of the ends of the segment if the perpendicular from the given
+    /// point does not fall on the segment. The whole line is used if point 3 is not set.
+    pub fn closest(&self, lat: f64, lon: f64) -> TrackSolution
+    {
+        let foot = self.track(lat, lon);
+        let s13 = self.distance();
+        if !s13.is_finite() || (foot.along >= s13.min(0.0) && foot.along <= s13.max(0.0))
+        {
+            return foot;
+        }
+        let (geod, line) = self.track_line();
+        let (p1, p3) = (track_solution(&geod, &line, 0.0, lat, lon), track_solution(&geod, &line, s13, lat, lon));
+        if p1.cross.abs() <= p3.cross.abs() { p1 } else { p3 }
+    }
+
+    /// Get the along-track distance of the point (lat, lon) from point 1 of the line (meters).
+    pub fn along_track(&self, lat: f64, lon: f64) -> f64
+    {
+        self.track(lat, lon).along
+    }
+
+    /// Get the cross-track distance of the point (lat, lon) from the line, positive on the right (meters).
+    pub fn cross_track(&self, lat: f64, lon: f64) -> f64
+    {
+        self.track(lat, lon).cross
+    }
+
+    // The geodesic and a copy of the line which can place the points by distance.
+    fn track_line(&self) -> (Geodesic, GeodesicLine)
+    {
+        let geod = self.geodesic();
+        let line = geod.line(self.latitude(), self.longitude(), self.azimuth(), Self::TRACK_CAPS);
+        (geod, line)
+    }
+}
+
+// The point at s on the line, with the distance to the point (lat, lon) and the sine and cosine of
+// the angle between the line and the geodesic to that point.
+fn offset(geod: &Geodesic, line: &GeodesicLine, s: f64, lat: f64, lon: f64) -> (f64, f64, f64, f64, f64)
+{
+    let p = line.position(s, GeodesicLine::TRACK_CAPS);
+    let (lat1, lon1) = (p.lat2.unwrap(), p.lon2.unwrap());
+    let (d, azi1, _azi2, _a12) = geod.inverse(lat1, lon1, lat, lon);
+    let (sin, cos) = trig::sincosd(trig::ang_diff(p.azi2.unwrap(), azi1).0);
+    (lat1, lon1, d, sin, cos)
+}
+
+fn track_solution(geod: &Geodesic, line: &GeodesicLine, s: f64, lat: f64, lon: f64) -> TrackSolution
+{
+    let (lat1, lon1, d, sin, _cos) = offset(geod, line, s, lat, lon);
+    TrackSolution { lat: lat1, lon: lon1, along: s, cross: if d > 0.0 { d.copysign(sin) } else { 0.0 } }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use rstest::*;
+    use float_cmp::assert_approx_eq;
+    use crate::earth::models::{Sphere, WGS84};
+
+    #[test]
+    fn test_track_equator()
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let line = geod.line(0.0, 0.0, 90.0, Caps::STANDARD | Caps::DISTANCE_IN);
+        let t = line.track(10.0, 30.0);
+        let meridian: f64 = geod.inverse(0.0, 30.0, 10.0, 30.0);
+        assert_approx_eq!(f64, t.lat, 0.0, epsilon = 1e-12);
+        assert_approx_eq!(f64, t.lon, 30.0, epsilon = 1e-12);
+        assert_approx_eq!(f64, t.along, geod.elps.a * 30f64.to_radians(), epsilon = 1e-6);
+        assert_approx_eq!(f64, t.cross, -meridian, epsilon = 1e-6);
+        assert_approx_eq!(f64, line.cross_track(-10.0, -30.0), meridian, epsilon = 1e-6);
+        assert_approx_eq!(f64, line.along_track(-10.0, -30.0), -t.along, epsilon = 1e-6);
+        assert_eq!(line.track(0.0, 0.0), TrackSolution { lat: 0.0, lon: 0.0, along: 0.0, cross: 0.0 });
+    }
+
+    #[rstest]
+    #[case(0.0, 100.0)]
+    #[case(0.0, 150.0)]
+    #[case(1.0, 120.0)]
+    #[case(-2.0, 179.0)]
+    #[case(3.0, 180.0)]
+    #[case(1.0, -120.0)]
+    fn test_track_far(#[case] lat: f64, #[case] lon: f64)
+    {
+        // The points more than a quarter of the circumference down the equator.
+        let geod = Geodesic::model::<WGS84>();
+        let line = geod.line(0.0, 0.0, 90.0, Caps::STANDARD | Caps::DISTANCE_IN);
+        let t = line.track(lat, lon);
+        let meridian: f64 = geod.inverse(0.0, lon, lat, lon);
+        assert_approx_eq!(f64, t.lat, 0.0, epsilon = 1e-12);
+        assert_approx_eq!(f64, trig::ang_diff(t.lon, lon).0, 0.0, epsilon = 1e-12);
+        assert_approx_eq!(f64, t.along.abs(), geod.elps.a * lon.abs().to_radians(), epsilon = 1e-6);
+        assert_approx_eq!(f64, t.cross, -meridian.copysign(lat), epsilon = 1e-6);
+
+        let segment = geod.inverse_line(0.0, 0.0, 0.0, 170.0, Caps::STANDARD | Caps::DISTANCE_IN);
+        let c = segment.closest(lat, lon);
+        if lon > 0.0 && lon <= 170.0
+        {
+            assert_approx_eq!(f64, c.lon, lon, epsilon = 1e-12);
+            assert_approx_eq!(f64, c.cross, t.cross, epsilon = 1e-6);
+        }
+        else
+        {
+            let d: f64 = geod.inverse(0.0, c.lon, lat, lon);
+            assert!(c.lon == 0.0 || (c.lon - 170.0).abs() < 1e-12);
+            assert_approx_eq!(f64, c.cross.abs(), d, epsilon = 1e-6);
+        }
+    }
+
+    #[rstest]
+    #[case(40.0, -70.0, 60.0, 45.0, -60.0)]
+    #[case(-33.9, 18.4, 120.0, -40.0, 30.0)]
+    #[case(10.0, 100.0, -20.0, 50.0, 170.0)]
+    #[case(80.0, 0.0, 0.0, 85.0, 90.0)]
+    #[case(0.0, 0.0, 90.0, 20.0, 135.0)]
+    #[case(0.0, 0.0, 90.0, -30.0, -150.0)]
+    fn test_track_sphere(#[case] lat1: f64, #[case] lon1: f64, #[case] azi1: f64, #[case] lat: f64, #[case] lon: f64)
+    {
+        // The cross-track and along-track distances on the sphere from the right spherical triangle.
+        let geod = Geodesic::model::<Sphere>();
+        let r = geod.elps.a;
+        let line = geod.line(lat1, lon1, azi1, Caps::STANDARD | Caps::DISTANCE_IN);
+        let (d13, azi13, _azi2, _a12) = geod.inverse(lat1, lon1, lat, lon);
+        let (d13, theta) = (d13 / r, (azi13 - azi1).to_radians());
+        let cross = (d13.sin() * theta.sin()).asin();
+        let along = (d13.sin() * theta.cos()).atan2(d13.cos());
+        let t = line.track(lat, lon);
+        assert_approx_eq!(f64, t.cross, cross * r, epsilon = 1e-6);
+        assert_approx_eq!(f64, t.along, along * r, epsilon = 1e-6);
+    }
+
+    #[rstest]
+    #[case(40.6, -73.8, 49.01666667, 2.55, 55.0, -30.0)]
+    #[case(-33.9, 18.4, -37.8, 145.0, -60.0, 80.0)]
+    #[case(51.5, -0.1, 35.7, 139.7, 30.0, 60.0)]
+    fn test_track_perpendicular(#[case] lat1: f64, #[case] lon1: f64, #[case] lat2: f64, #[case] lon2: f64, #[case] lat: f64, #[case] lon: f64)
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let line = geod.inverse_line(lat1, lon1, lat2, lon2, Caps::STANDARD | Caps::DISTANCE_IN);
+        let t = line.track(lat, lon);
+        let p = line.position(t.along, Caps::STANDARD);
+        assert_approx_eq!(f64, p.lat2.unwrap(), t.lat, epsilon = 1e-12);
+        assert_approx_eq!(f64, p.lon2.unwrap(), t.lon, epsilon = 1e-12);
+        let (d, azi1, _azi2, _a12) = geod.inverse(t.lat, t.lon, lat, lon);
+        assert_approx_eq!(f64, d, t.cross.abs(), epsilon = 1e-6);
+        assert_approx_eq!(f64, trig::ang_diff(p.azi2.unwrap(), azi1).0.abs(), 90.0, epsilon = 1e-8);
+        for ds in [-1.0, 1.0]
+        {
+            let q = line.position(t.along + ds, Caps::STANDARD);
+            let dq: f64 = geod.inverse(q.lat2.unwrap(), q.lon2.unwrap(), lat, lon);
+            assert!(dq > d);
+        }
+    }
+
+    #[test]
+    fn test_closest()
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let line = geod.inverse_line(0.0, 0.0, 0.0, 10.0, Caps::STANDARD | Caps::DISTANCE_IN);
+
+        // The perpendicular falls on the segment.
+        let c = line.closest(5.0, 5.0);
+        assert_eq!(c, line.track(5.0, 5.0));
+        assert!(c.along > 0.0 && c.along < line.distance());
+
+        // The closest points are the ends of the segment.
+        let c = line.closest(-5.0, -5.0);
+        let d: f64 = geod.inverse(0.0, 0.0, -5.0, -5.0);
+        assert_eq!((c.lat, c.lon, c.along), (0.0, 0.0, 0.0));
+        assert_approx_eq!(f64, c.cross, d, epsilon = 1e-6);
+        let c = line.closest(5.0, 15.0);
+        let d: f64 = geod.inverse(0.0, 10.0, 5.0, 15.0);
+        assert_approx_eq!(f64, c.lon, 10.0, epsilon = 1e-12);
+        assert_eq!(c.along, line.distance());
+        assert_approx_eq!(f64, c.cross, -d, epsilon = 1e-6);
+
+        // The whole line is used without point 3.
+        let line = geod.line(0.0, 0.0, 90.0, Caps::STANDARD | Caps::DISTANCE_IN);
+        assert_eq!(line.closest(-5.0, -5.0), line.track(-5.0, -5.0));
+    }
+}
@@ -0,0 +1,277 @@
+#![allow(non_snake_case)]
+use crate::geodesic::caps::{Caps, Mask};
+use crate::geodesic::line::GeodesicLine;
+
+/// A point placed along a geodesic line.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Waypoint
+{
+    /// Latitude of the waypoint (degrees).
+    pub lat: f64,
+
+    /// Longitude of the waypoint (degrees).
+    pub lon: f64,
+
+    /// (forward) azimuth at the waypoint (degrees).
+    pub azi: f64,
+
+    /// Distance from point 1 of the line to the waypoint (meters).
+    pub s: f64,
+}
+
+impl Waypoint
+{
+    /// The maximum number of the waypoints placed by distance along a line.
+    pub const MAX_COUNT: usize = 1 << 24;
+}
+
+// The spacing of the waypoints from point 1 to point 3 of a line, shared by the geodesic and rhumb lines.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct Spacing
+{
+    // The distance between the waypoints, except for the last one (meters).
+    pub(crate) step: f64,
+
+    // The number of the steps, the last one may be shorter.
+    pub(crate) segments: usize,
+
+    // The number of the waypoints.
+    pub(crate) len: usize,
+}
+
+impl Spacing
+{
+    pub(crate) const EMPTY: Self = Self { step: 0.0, segments: 0, len: 0 };
+
+    // The waypoints every `step` meters along the distance `total`, none if there are more than `Waypoint::MAX_COUNT`.
+    pub(crate) fn by_distance(total: f64, step: f64) -> Self
+    {
+        if !(step > 0.0 && total.is_finite())
+        {
+            return Self::EMPTY;
+        }
+        let segments = (total.abs() / step).ceil();
+        if segments >= Waypoint::MAX_COUNT as f64
+        {
+            return Self::EMPTY;
+        }
+        let segments = segments as usize;
+        Self { step: step.copysign(total), segments, len: segments + 1 }
+    }
+
+    // The `n` equally spaced waypoints along the distance `total`.
+    pub(crate) fn by_count(total: f64, n: usize) -> Self
+    {
+        if !total.is_finite()
+        {
+            return Self::EMPTY;
+        }
+        let segments = n.saturating_sub(1);
+        let step = if segments > 0 { total / segments as f64 } else { 0.0 };
+        Self { step, segments, len: n }
+    }
+
+    // Whether the waypoint is the last one, placed exactly at point 3.
+    pub(crate) fn is_end(&self, index: usize) -> bool
+    {
+        index > 0 && index == self.segments
+    }
+}
+
+/// Iterator over the waypoints between point 1 and point 3 of a geodesic line.
+///
+/// The waypoints are equally spaced by distance, except for the last one which is always
+/// placed exactly at point 3 of the line.
+#[derive(Debug, Clone)]
+pub struct Waypoints<'a>
+{
+    line: &'a GeodesicLine,
+    outmask: Caps,
+    spacing: Spacing,
+    index: usize,
+}
+
+impl<'a> Waypoints<'a>
+{
+    fn new(line: &'a GeodesicLine, spacing: Spacing, outmask: Caps) -> Self
+    {
+        let outmask = Caps::LATITUDE | Caps::LONGITUDE | Caps::AZIMUTH | (outmask & Mask::OUT & Caps::LONG_UNROLL);
+        let spacing = if line.caps().contains(Caps::DISTANCE_IN) { spacing } else { Spacing::EMPTY };
+        Self { line, outmask, spacing, index: 0 }
+    }
+
+    fn waypoint(&self, index: usize) -> Waypoint
+    {
+        let (s, (_a12, lat, lon, azi, _s12, _m12, _M12, _M21, _S12)) = if self.spacing.is_end(index)
+        {
+            // Place the endpoint with the arc length of point 3 which is exact for lines built by inverse_line.
+            (self.line.distance(), self.line._gen_position(true, self.line.arc(), self.outmask))
+        }
+        else
+        {
+            let s = self.spacing.step * index as f64;
+            (s, self.line._gen_position(false, s, self.outmask))
+        };
+        Waypoint { lat, lon, azi, s }
+    }
+}
+
+impl Iterator for Waypoints<'_>
+{
+    type Item = Waypoint;
+
+    fn next(&mut self) -> Option<Waypoint>
+    {
+        if self.index >= self.spacing.len
+        {
+            return None;
+        }
+        let waypoint = self.waypoint(self.index);
+        self.index += 1;
+        Some(waypoint)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        let n = self.spacing.len - self.index;
+        (n, Some(n))
+    }
+}
+
+impl ExactSizeIterator for Waypoints<'_> {}
+
+impl GeodesicLine
+{
+    /// Iterate the waypoints from point 1 to point 3 of the line every `step` meters.
+    ///
+    /// The last waypoint is placed exactly at point 3, so it may be closer than `step` to the
+    /// previous one. Pass `Caps::LONG_UNROLL` in `outmask` to unroll the longitudes, so that they
+    /// change continuously along the line instead of being reduced to [-180°, 180°).
+    ///
+    /// No waypoint is returned if point 3 of the line is not set, the line is not created with
+    /// `Caps::DISTANCE_IN`, `step` is not a positive number or there would be more than
+    /// `Waypoint::MAX_COUNT` waypoints.
+    pub fn waypoints_by_distance(&self, step: f64, outmask: Caps) -> Waypoints<'_>
+    {
+        Waypoints::new(self, Spacing::by_distance(self.distance(), step), outmask)
+    }
+
+    /// Iterate `n` equally spaced waypoints from point 1 to point 3 of the line, both included.
+    ///
+    /// A single waypoint at point 1 is returned if `n` is 1. See `waypoints_by_distance` for the
+    /// meaning of `outmask` and the requirements on the line.
+    pub fn waypoints_by_count(&self, n: usize, outmask: Caps) -> Waypoints<'_>
+    {
+        Waypoints::new(self, Spacing::by_count(self.distance(), n), outmask)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::coords::latlon::LatLon;
+    use crate::earth::models::WGS84;
+    use crate::geodesic::core::Geodesic;
+    use crate::geodesic::inverse::InverseGeodesic;
+    use float_cmp::assert_approx_eq;
+    use rstest::*;
+
+    #[rstest]
+    #[case(100e3, 60)]
+    #[case(1000e3, 7)]
+    #[case(3000e3, 3)]
+    #[case(1e7, 2)]
+    fn test_waypoints_by_distance(#[case] step: f64, #[case] count: usize)
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let (p1, p2) = (LatLon::new(40.6, -73.8), LatLon::new(49.01666667, 2.55));
+        let line = geod.inverse_line(p1.latitude(), p1.longitude(), p2.latitude(), p2.longitude(), Caps::STANDARD | Caps::DISTANCE_IN);
+        let waypoints = line.waypoints_by_distance(step, Caps::empty());
+        assert_eq!(waypoints.len(), count);
+
+        let waypoints: Vec<Waypoint> = waypoints.collect();
+        assert_eq!(waypoints.len(), count);
+        for (i, w) in waypoints.iter().enumerate().take(count - 1)
+        {
+            assert_eq!(w.s, step * i as f64);
+            let s12: f64 = geod.inverse(p1.latitude(), p1.longitude(), w.lat, w.lon);
+            assert_approx_eq!(f64, s12, w.s, epsilon = 1e-6);
+        }
+        let first = waypoints.first().unwrap();
+        assert_eq!(first.s, 0.0);
+        assert_approx_eq!(f64, first.lat, 40.6, epsilon = 1e-13);
+        assert_approx_eq!(f64, first.lon, -73.8, epsilon = 1e-13);
+        assert_approx_eq!(f64, first.azi, line.azimuth(), epsilon = 1e-13);
+        let last = waypoints.last().unwrap();
+        assert_eq!(last.s, line.distance());
+        assert_approx_eq!(f64, last.lat, 49.01666667, epsilon = 1e-13);
+        assert_approx_eq!(f64, last.lon, 2.55, epsilon = 1e-13);
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(1, 1)]
+    #[case(2, 2)]
+    #[case(11, 11)]
+    fn test_waypoints_by_count(#[case] n: usize, #[case] count: usize)
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let line = geod.direct_line(40.63972222, -73.77888889, 53.5, 5850e3, Caps::STANDARD);
+        let waypoints: Vec<Waypoint> = line.waypoints_by_count(n, Caps::empty()).collect();
+        assert_eq!(waypoints.len(), count);
+        for (i, w) in waypoints.iter().enumerate()
+        {
+            let s = if i + 1 == count && i > 0 { 5850e3 } else { 5850e3 / (n - 1).max(1) as f64 * i as f64 };
+            assert_approx_eq!(f64, w.s, s, epsilon = 1e-8);
+            let sol = geod.solve_direct(40.63972222, -73.77888889, 53.5, w.s, Caps::STANDARD);
+            assert_approx_eq!(f64, w.lat, sol.lat2.unwrap(), epsilon = 1e-12);
+            assert_approx_eq!(f64, w.lon, sol.lon2.unwrap(), epsilon = 1e-12);
+            assert_approx_eq!(f64, w.azi, sol.azi2.unwrap(), epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_waypoints_long_unroll()
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let line = geod.direct_line(0.0, 170.0, 90.0, 4000e3, Caps::STANDARD);
+        let wrapped: Vec<Waypoint> = line.waypoints_by_count(5, Caps::empty()).collect();
+        let unrolled: Vec<Waypoint> = line.waypoints_by_count(5, Caps::LONG_UNROLL).collect();
+        assert!(wrapped.iter().all(|w| (-180.0..=180.0).contains(&w.lon)));
+        assert!(wrapped.last().unwrap().lon < 0.0);
+        assert!(unrolled.windows(2).all(|w| w[1].lon > w[0].lon));
+        assert!(unrolled.last().unwrap().lon > 180.0);
+        for (w, u) in wrapped.iter().zip(unrolled.iter())
+        {
+            assert_approx_eq!(f64, w.lat, u.lat);
+            assert_approx_eq!(f64, (u.lon - w.lon).rem_euclid(360.0).min(360.0 - (u.lon - w.lon).rem_euclid(360.0)), 0.0, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_waypoints_negative_distance()
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let line = geod.direct_line(10.0, 20.0, 30.0, -250e3, Caps::STANDARD);
+        let waypoints: Vec<Waypoint> = line.waypoints_by_distance(100e3, Caps::empty()).collect();
+        let s: Vec<f64> = waypoints.iter().map(|w| w.s).collect();
+        assert_eq!(s, vec![0.0, -100e3, -200e3, -250e3]);
+    }
+
+    #[test]
+    fn test_waypoints_empty()
+    {
+        let geod = Geodesic::model::<WGS84>();
+        let line = geod.line(10.0, 20.0, 30.0, Caps::STANDARD | Caps::DISTANCE_IN);
+        assert_eq!(line.waypoints_by_count(3, Caps::empty()).count(), 0);
+        let line = geod.inverse_line(10.0, 20.0, 30.0, 40.0, Caps::STANDARD);
+        assert_eq!(line.waypoints_by_count(3, Caps::empty()).count(), 0);
+        let line = geod.inverse_line(10.0, 20.0, 30.0, 40.0, Caps::STANDARD | Caps::DISTANCE_IN);
+        assert_eq!(line.waypoints_by_distance(0.0, Caps::empty()).count(), 0);
+        assert_eq!(line.waypoints_by_distance(f64::NAN, Caps::empty()).count(), 0);
+        assert_eq!(line.waypoints_by_distance(1e-300, Caps::empty()).count(), 0);
+        assert_eq!(line.waypoints_by_distance(line.distance() / Waypoint::MAX_COUNT as f64, Caps::empty()).len(), 0);
+        assert_eq!(line.waypoints_by_distance(line.distance() / (Waypoint::MAX_COUNT - 2) as f64, Caps::empty()).len(), Waypoint::MAX_COUNT - 1);
+    }
+}
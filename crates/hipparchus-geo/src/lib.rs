@@ -1,10 +1,23 @@
 // re-exports
 pub use self::coords::coord::*;
+pub use self::coords::freeform::*;
 pub use self::coords::latlon::*;
+pub use self::coords::latlonalt::*;
 pub use self::coords::iso6709::*;
+pub use self::coords::nmea::*;
 pub use self::coords::nmea0183::*;
 pub use self::coords::zone::*;
 pub use self::coords::orientation::*;
+pub use self::coords::mgrs::*;
+pub use self::coords::bbox::*;
+pub use self::coords::geohash::*;
+pub use self::coords::olc::*;
+pub use self::coords::maidenhead::*;
+pub use self::coords::cell::*;
+pub use self::earth::auxiliary::*;
+pub use self::earth::datum::*;
+pub use self::earth::ecef::*;
+pub use self::earth::local::*;
 pub use self::earth::ellipsoid::*;
 pub use self::earth::models::*;
 
@@ -12,3 +25,5 @@ pub use self::earth::models::*;
 pub mod coords;
 pub mod earth;
 pub mod geodesic;
+pub mod rhumb;
+pub mod projection;
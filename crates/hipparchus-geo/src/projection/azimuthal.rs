@@ -0,0 +1,323 @@
+use crate::coords::latlon::LatLon;
+use crate::earth::ellipsoid::Ellipsoid;
+use crate::earth::models::Model;
+use crate::geodesic::{Caps, Geodesic};
+use crate::geodesic::trig;
+use crate::projection::generic::{Projection, ProjectionSolution};
+
+/// The position of a point on an azimuthal projection.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AzimuthalSolution
+{
+    /// latitude of the point (degrees).
+    pub lat: f64,
+
+    /// longitude of the point (degrees).
+    pub lon: f64,
+
+    /// easting of the point from the centre (meters).
+    pub x: f64,
+
+    /// northing of the point from the centre (meters).
+    pub y: f64,
+
+    /// azimuth of the geodesic from the centre at the point (degrees).
+    pub azimuth: f64,
+
+    /// scale of the projection at the point perpendicular to the geodesic from the centre.
+    pub scale: f64,
+}
+
+impl AzimuthalSolution
+{
+    // The convergence is the azimuth of the geodesic at the point less its bearing on the map.
+    fn projection(&self) -> ProjectionSolution
+    {
+        let convergence = trig::ang_diff(trig::atan2d(self.x, self.y), self.azimuth).0;
+        ProjectionSolution { lat: self.lat, lon: self.lon, x: self.x, y: self.y, convergence, scale: self.scale }
+    }
+}
+
+/// Ellipsoidal azimuthal equidistant projection centred on a point.
+///
+/// The distances and azimuths of the geodesics from the centre are preserved, so the scale is 1
+/// along these geodesics and s12 / m12 perpendicular to them.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AzimuthalEquidistant
+{
+    pub elps: Ellipsoid,
+
+    /// centre of the projection.
+    pub center: LatLon,
+
+    geod: Geodesic,
+}
+
+impl AzimuthalEquidistant
+{
+    /// Create the projection centred on the point on the model.
+    pub fn model<T>(center: LatLon) -> Self where T: Model
+    {
+        Self::with(T::elps(), center)
+    }
+
+    pub fn with(elps: Ellipsoid, center: LatLon) -> Self
+    {
+        Self { elps, center, geod: Geodesic::with(elps) }
+    }
+
+    /// Project the point (lat, lon) in degrees.
+    pub fn forward(&self, lat: f64, lon: f64) -> AzimuthalSolution
+    {
+        let caps = Caps::AZIMUTH | Caps::DISTANCE | Caps::REDUCEDLENGTH;
+        let g = self.geod.solve_inverse(self.center.latitude(), self.center.longitude(), lat, lon, caps);
+        let (s, m) = (g.s12.unwrap(), g.m12.unwrap());
+        let (sin, cos) = trig::sincosd(g.azi1.unwrap());
+        AzimuthalSolution { lat, lon, x: s * sin, y: s * cos, azimuth: g.azi2.unwrap(), scale: scale(s, m) }
+    }
+
+    /// Unproject the point (x, y) in meters.
+    pub fn reverse(&self, x: f64, y: f64) -> AzimuthalSolution
+    {
+        let s = x.hypot(y);
+        let caps = Caps::STANDARD | Caps::REDUCEDLENGTH;
+        let g = self.geod.solve_direct(self.center.latitude(), self.center.longitude(), trig::atan2d(x, y), s, caps);
+        let (lat, lon) = (g.lat2.unwrap(), g.lon2.unwrap());
+        AzimuthalSolution { lat, lon, x, y, azimuth: g.azi2.unwrap(), scale: scale(s, g.m12.unwrap()) }
+    }
+}
+
+impl Projection for AzimuthalEquidistant
+{
+    fn forward(&self, lat: f64, lon: f64) -> ProjectionSolution
+    {
+        AzimuthalEquidistant::forward(self, lat, lon).projection()
+    }
+
+    fn reverse(&self, x: f64, y: f64) -> ProjectionSolution
+    {
+        AzimuthalEquidistant::reverse(self, x, y).projection()
+    }
+}
+
+// The scale perpendicular to the geodesic of the distance s and the reduced length m from the centre.
+fn scale(s: f64, m: f64) -> f64
+{
+    if s > 0.0 { s / m } else { 1.0 }
+}
+
+/// Ellipsoidal gnomonic projection centred on a point.
+///
+/// The geodesics through the centre are straight lines, and the other geodesics are very nearly
+/// straight, so a route is drawn as the line between its ends. The point at the distance s12 is
+/// at m12 / M12 from the centre, and only the points with M12 > 0, i.e. about less than a quarter
+/// of the meridian away, can be projected; the others are NaN.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Gnomonic
+{
+    pub elps: Ellipsoid,
+
+    /// centre of the projection.
+    pub center: LatLon,
+
+    geod: Geodesic,
+}
+
+impl Gnomonic
+{
+    const MAXIT: usize = 10;
+
+    /// Create the projection centred on the point on the model.
+    pub fn model<T>(center: LatLon) -> Self where T: Model
+    {
+        Self::with(T::elps(), center)
+    }
+
+    pub fn with(elps: Ellipsoid, center: LatLon) -> Self
+    {
+        Self { elps, center, geod: Geodesic::with(elps) }
+    }
+
+    /// Project the point (lat, lon) in degrees.
+    pub fn forward(&self, lat: f64, lon: f64) -> AzimuthalSolution
+    {
+        let caps = Caps::AZIMUTH | Caps::REDUCEDLENGTH | Caps::GEODESICSCALE;
+        let g = self.geod.solve_inverse(self.center.latitude(), self.center.longitude(), lat, lon, caps);
+        let (m, mm) = (g.m12.unwrap(), g.M12.unwrap());
+        let (x, y) = if mm > 0.0
+        {
+            let (sin, cos) = trig::sincosd(g.azi1.unwrap());
+            (m / mm * sin, m / mm * cos)
+        }
+        else
+        {
+            (f64::NAN, f64::NAN)
+        };
+        AzimuthalSolution { lat, lon, x, y, azimuth: g.azi2.unwrap(), scale: 1.0 / mm }
+    }
+
+    /// Unproject the point (x, y) in meters, NaN if the iteration does not converge.
+    ///
+    /// The distance along the geodesic from the centre is found by Newton's method, solving
+    /// m12 / M12 = ρ for the points near the centre and M12 / m12 = 1 / ρ for the far ones.
+    pub fn reverse(&self, x: f64, y: f64) -> AzimuthalSolution
+    {
+        let a = self.elps.a;
+        let caps = Caps::LATITUDE | Caps::LONGITUDE | Caps::AZIMUTH | Caps::DISTANCE_IN | Caps::REDUCEDLENGTH | Caps::GEODESICSCALE;
+        let line = self.geod.line(self.center.latitude(), self.center.longitude(), trig::atan2d(x, y), caps);
+        let mut rho = x.hypot(y);
+        let mut s = a * (rho / a).atan();
+        let little = rho <= a;
+        if !little
+        {
+            rho = 1.0 / rho;
+        }
+        let tol = 0.01 * f64::EPSILON.sqrt() * a;
+        for _ in 0..Self::MAXIT
+        {
+            let p = line.position(s, caps);
+            let (m, mm) = (p.m12.unwrap(), p.M12.unwrap());
+            let ds = if little { (m - rho * mm) * mm } else { (rho * m - mm) * m };
+            s -= ds;
+            if ds.abs() < tol
+            {
+                let p = line.position(s, caps);
+                let (lat, lon, azimuth) = (p.lat2.unwrap(), p.lon2.unwrap(), p.azi2.unwrap());
+                return AzimuthalSolution { lat, lon, x, y, azimuth, scale: 1.0 / p.M12.unwrap() };
+            }
+        }
+        AzimuthalSolution { lat: f64::NAN, lon: f64::NAN, x, y, azimuth: f64::NAN, scale: f64::NAN }
+    }
+}
+
+impl Projection for Gnomonic
+{
+    fn forward(&self, lat: f64, lon: f64) -> ProjectionSolution
+    {
+        Gnomonic::forward(self, lat, lon).projection()
+    }
+
+    fn reverse(&self, x: f64, y: f64) -> ProjectionSolution
+    {
+        Gnomonic::reverse(self, x, y).projection()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use rstest::*;
+    use float_cmp::assert_approx_eq;
+    use crate::earth::models::{Sphere, WGS84};
+
+    // The central angle (radians) and the azimuth (degrees) from the point 1 to the point 2 on the sphere.
+    fn great_circle(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> (f64, f64)
+    {
+        let (phi1, phi2, dlon) = (lat1.to_radians(), lat2.to_radians(), (lon2 - lon1).to_radians());
+        let c = (phi1.sin() * phi2.sin() + phi1.cos() * phi2.cos() * dlon.cos()).clamp(-1.0, 1.0).acos();
+        let azi = (dlon.sin() * phi2.cos()).atan2(phi1.cos() * phi2.sin() - phi1.sin() * phi2.cos() * dlon.cos());
+        (c, azi.to_degrees())
+    }
+
+    #[rstest]
+    #[case(40.0, -70.0, 41.0, -69.0)]
+    #[case(40.0, -70.0, 10.0, 30.0)]
+    #[case(-33.9, 18.4, 51.5, -0.1)]
+    #[case(0.0, 0.0, 0.0, 170.0)]
+    #[case(89.0, 0.0, 80.0, 180.0)]
+    fn test_sphere(#[case] lat0: f64, #[case] lon0: f64, #[case] lat: f64, #[case] lon: f64)
+    {
+        let r = Sphere::elps().a;
+        let center = LatLon::new(lat0, lon0);
+        let (c, azi) = great_circle(lat0, lon0, lat, lon);
+
+        let ae = AzimuthalEquidistant::model::<Sphere>(center).forward(lat, lon);
+        assert_approx_eq!(f64, ae.x.hypot(ae.y), r * c, epsilon = 1e-6);
+        assert_approx_eq!(f64, trig::atan2d(ae.x, ae.y), azi, epsilon = 1e-9);
+        assert_approx_eq!(f64, ae.scale, c / c.sin(), epsilon = 1e-12);
+
+        let gn = Gnomonic::model::<Sphere>(center).forward(lat, lon);
+        if c < std::f64::consts::FRAC_PI_2
+        {
+            assert_approx_eq!(f64, gn.x.hypot(gn.y), r * c.tan(), epsilon = 1e-6 * c.tan().max(1.0));
+            assert_approx_eq!(f64, trig::atan2d(gn.x, gn.y), azi, epsilon = 1e-9);
+            assert_approx_eq!(f64, gn.scale, 1.0 / c.cos(), epsilon = 1e-12 / c.cos());
+        }
+        else
+        {
+            assert!(gn.x.is_nan() && gn.y.is_nan());
+        }
+    }
+
+    #[test]
+    fn test_azimuthal_equidistant()
+    {
+        // GeodSolve0 of GeographicLib, JFK to CDG.
+        let ae = AzimuthalEquidistant::model::<WGS84>(LatLon::new(40.6, -73.8));
+        let s = ae.forward(49.01666667, 2.55);
+        assert_approx_eq!(f64, s.x.hypot(s.y), 5853226.0, epsilon = 0.5);
+        assert_approx_eq!(f64, trig::atan2d(s.x, s.y), 53.47022, epsilon = 0.5e-5);
+        assert_approx_eq!(f64, s.azimuth, 111.59367, epsilon = 0.5e-5);
+        assert!(s.scale > 1.0);
+
+        let r = ae.reverse(s.x, s.y);
+        assert_approx_eq!(f64, r.lat, 49.01666667, epsilon = 1e-9);
+        assert_approx_eq!(f64, r.lon, 2.55, epsilon = 1e-9);
+        assert_approx_eq!(f64, r.azimuth, s.azimuth, epsilon = 1e-9);
+        assert_approx_eq!(f64, r.scale, s.scale, epsilon = 1e-12);
+
+        let o = ae.forward(40.6, -73.8);
+        assert_eq!((o.x, o.y, o.scale), (0.0, 0.0, 1.0));
+    }
+
+    #[rstest]
+    #[case(40.6, -73.8, 49.01666667, 2.55)]
+    #[case(40.6, -73.8, -33.9, 151.2)]
+    #[case(-89.5, 0.0, -60.0, 120.0)]
+    #[case(0.0, 0.0, 0.0, 179.0)]
+    #[case(12.0, 34.0, 12.0, 34.0)]
+    fn test_round_trip(#[case] lat0: f64, #[case] lon0: f64, #[case] lat: f64, #[case] lon: f64)
+    {
+        let center = LatLon::new(lat0, lon0);
+        let projections: [&dyn Projection; 2] = [&AzimuthalEquidistant::model::<WGS84>(center), &Gnomonic::model::<WGS84>(center)];
+        for proj in projections
+        {
+            let s = proj.forward(lat, lon);
+            if s.x.is_nan()
+            {
+                continue;
+            }
+            let r = proj.reverse(s.x, s.y);
+            assert_approx_eq!(f64, r.lat, lat, epsilon = 1e-9);
+            assert_approx_eq!(f64, trig::ang_diff(r.lon, lon).0, 0.0, epsilon = 1e-9);
+            assert_approx_eq!(f64, r.scale, s.scale, epsilon = 1e-9 * s.scale);
+            assert_approx_eq!(f64, r.convergence, s.convergence, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_gnomonic()
+    {
+        let center = LatLon::new(45.0, 10.0);
+        let gn = Gnomonic::model::<WGS84>(center);
+        assert!(gn.forward(-50.0, 10.0).x.is_nan());
+        let o = gn.forward(45.0, 10.0);
+        assert_eq!((o.x, o.y), (0.0, 0.0));
+        assert_approx_eq!(f64, o.scale, 1.0, epsilon = 1e-15);
+
+        // A geodesic away from the centre is very nearly a straight line.
+        let geod = Geodesic::model::<WGS84>();
+        let line = geod.inverse_line(40.0, 0.0, 50.0, 20.0, Caps::STANDARD | Caps::DISTANCE_IN);
+        let p1 = gn.forward(40.0, 0.0);
+        let p2 = gn.forward(50.0, 20.0);
+        let length = (p2.x - p1.x).hypot(p2.y - p1.y);
+        for i in 1..10
+        {
+            let p = line.position(line.distance() * i as f64 / 10.0, Caps::STANDARD);
+            let q = gn.forward(p.lat2.unwrap(), p.lon2.unwrap());
+            let offset = ((q.x - p1.x) * (p2.y - p1.y) - (q.y - p1.y) * (p2.x - p1.x)) / length;
+            assert!(offset.abs() < 50.0, "offset {offset}");
+        }
+    }
+}
@@ -0,0 +1,411 @@
+use hipparchus_mean::Power;
+use hipparchus_az::{Degrees, Remainder};
+use crate::earth::auxiliary::{taupf, tauf, AuxiliaryLatitude};
+use crate::earth::ellipsoid::Ellipsoid;
+use crate::earth::models::Model;
+use crate::geodesic::trig;
+use crate::projection::generic::{Projection, ProjectionSolution};
+
+/// Errors of the conic projections.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ConicError
+{
+    /// The standard parallels are on the equator or symmetric about it, so the cone constant is 0
+    /// and the cone degenerates to a cylinder, e.g. Mercator for the Lambert conformal conic.
+    ZeroConeConstant { lat1: f64, lat2: f64 },
+}
+
+impl std::fmt::Display for ConicError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self
+        {
+            ConicError::ZeroConeConstant { lat1, lat2 } =>
+                write!(f, "standard parallels {lat1} and {lat2} give a cylinder instead of a cone, use a cylindrical projection"),
+        }
+    }
+}
+
+impl std::error::Error for ConicError {}
+
+/// Lambert conformal conic projection with one (EPSG:9801) or two (EPSG:9802) standard parallels.
+///
+/// The cone is tangent to the ellipsoid at the latitude of origin with the scale factor k0 for
+/// one standard parallel, or secant to the ellipsoid along the two standard parallels where the
+/// scale is 1. The convergence is the longitude from the central meridian times the cone constant.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LambertConformalConic
+{
+    /// the ellipsoid.
+    pub elps: Ellipsoid,
+
+    /// latitude of the origin (degrees).
+    pub lat0: f64,
+
+    /// longitude of the central meridian (degrees).
+    pub lon0: f64,
+
+    /// false easting (meters).
+    pub false_easting: f64,
+
+    /// false northing (meters).
+    pub false_northing: f64,
+
+    // signed eccentricity
+    es: f64,
+
+    // the cone constant
+    n: f64,
+
+    // the radius of the parallel of the isometric latitude 0 on the map
+    c: f64,
+
+    // the radius of the latitude of the origin on the map
+    rho0: f64,
+}
+
+impl LambertConformalConic
+{
+    /// Create the projection with one standard parallel at the latitude of origin on the model.
+    pub fn one_sp<T>(lat0: f64, lon0: f64, k0: f64) -> Result<Self, ConicError> where T: Model
+    {
+        Self::with_one_sp(T::elps(), lat0, lon0, k0)
+    }
+
+    /// Create the projection with two standard parallels and the latitude of the false origin on the model.
+    pub fn two_sp<T>(lat1: f64, lat2: f64, lat0: f64, lon0: f64) -> Result<Self, ConicError> where T: Model
+    {
+        Self::with_two_sp(T::elps(), lat1, lat2, lat0, lon0)
+    }
+
+    /// Create the projection with one standard parallel at the latitude of origin on the ellipsoid.
+    ///
+    /// Fails if the latitude of origin is on the equator, see `ConicError::ZeroConeConstant`.
+    pub fn with_one_sp(elps: Ellipsoid, lat0: f64, lon0: f64, k0: f64) -> Result<Self, ConicError>
+    {
+        Self::with(elps, lat0, lat0, lat0, lon0, k0)
+    }
+
+    /// Create the projection with two standard parallels and the latitude of the false origin on the ellipsoid.
+    ///
+    /// Fails if the standard parallels are symmetric about the equator, see `ConicError::ZeroConeConstant`.
+    pub fn with_two_sp(elps: Ellipsoid, lat1: f64, lat2: f64, lat0: f64, lon0: f64) -> Result<Self, ConicError>
+    {
+        Self::with(elps, lat1, lat2, lat0, lon0, 1.0)
+    }
+
+    /// Set the false easting and northing (meters).
+    pub fn false_origin(mut self, easting: f64, northing: f64) -> Self
+    {
+        self.false_easting = easting;
+        self.false_northing = northing;
+        self
+    }
+
+    fn with(elps: Ellipsoid, lat1: f64, lat2: f64, lat0: f64, lon0: f64, k0: f64) -> Result<Self, ConicError>
+    {
+        let es = elps.signed_eccentricity();
+        let (m1, psi1) = (parallel_radius(&elps, lat1), isometric_latitude(lat1, es));
+        let n = if lat1 == lat2
+        {
+            trig::sincosd(lat1).0
+        }
+        else
+        {
+            (m1.ln() - parallel_radius(&elps, lat2).ln()) / (isometric_latitude(lat2, es) - psi1)
+        };
+        check_cone_constant(n, lat1, lat2)?;
+        let c = k0 * elps.a * m1 * (n * psi1).exp() / n;
+        let rho0 = c * (-n * isometric_latitude(lat0, es)).exp();
+        Ok(Self { elps, lat0, lon0, false_easting: 0.0, false_northing: 0.0, es, n, c, rho0 })
+    }
+}
+
+impl Projection for LambertConformalConic
+{
+    fn forward(&self, lat: f64, lon: f64) -> ProjectionSolution
+    {
+        let rho = self.c * (-self.n * isometric_latitude(lat, self.es)).exp();
+        let theta = self.n * trig::ang_diff(self.lon0, lon).0.to_radians();
+        let x = self.false_easting + rho * theta.sin();
+        let y = self.false_northing + self.rho0 - rho * theta.cos();
+        solution(&self.elps, self.n, (lat, lon), (x, y), rho, theta)
+    }
+
+    fn reverse(&self, x: f64, y: f64) -> ProjectionSolution
+    {
+        let sign = self.n.signum();
+        let (dx, dy) = (x - self.false_easting, self.rho0 - (y - self.false_northing));
+        let rho = sign * dx.hypot(dy);
+        let theta = (sign * dx).atan2(sign * dy);
+        let psi = -(rho / self.c).ln() / self.n;
+        let lat = tauf(psi.sinh(), self.es).atan().to_degrees();
+        let lon = (self.lon0 + (theta / self.n).to_degrees()).norm_degrees(Remainder::InvertedSymmetry);
+        solution(&self.elps, self.n, (lat, lon), (x, y), rho, theta)
+    }
+}
+
+/// Albers equal-area conic projection with one or two (EPSG:9822) standard parallels.
+///
+/// The cone is secant to the ellipsoid along the two standard parallels, or tangent to it if
+/// they are the same. The areas are preserved, so the scale is the reciprocal along the meridian
+/// of the `scale` along the parallel.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AlbersEqualArea
+{
+    /// the ellipsoid.
+    pub elps: Ellipsoid,
+
+    /// latitude of the origin (degrees).
+    pub lat0: f64,
+
+    /// longitude of the central meridian (degrees).
+    pub lon0: f64,
+
+    /// false easting (meters).
+    pub false_easting: f64,
+
+    /// false northing (meters).
+    pub false_northing: f64,
+
+    // q at the pole, 2 times the square of the ratio of the authalic radius to the equatorial radius
+    qp: f64,
+
+    // the cone constant
+    n: f64,
+
+    // the constant C of Snyder
+    c: f64,
+
+    // the radius of the latitude of the origin on the map
+    rho0: f64,
+}
+
+impl AlbersEqualArea
+{
+    /// Create the projection with one standard parallel and the latitude of origin on the model.
+    pub fn one_sp<T>(lat1: f64, lat0: f64, lon0: f64) -> Result<Self, ConicError> where T: Model
+    {
+        Self::with_one_sp(T::elps(), lat1, lat0, lon0)
+    }
+
+    /// Create the projection with two standard parallels and the latitude of origin on the model.
+    pub fn two_sp<T>(lat1: f64, lat2: f64, lat0: f64, lon0: f64) -> Result<Self, ConicError> where T: Model
+    {
+        Self::with_two_sp(T::elps(), lat1, lat2, lat0, lon0)
+    }
+
+    /// Create the projection with one standard parallel and the latitude of origin on the ellipsoid.
+    ///
+    /// Fails if the standard parallel is on the equator, see `ConicError::ZeroConeConstant`.
+    pub fn with_one_sp(elps: Ellipsoid, lat1: f64, lat0: f64, lon0: f64) -> Result<Self, ConicError>
+    {
+        Self::with_two_sp(elps, lat1, lat1, lat0, lon0)
+    }
+
+    /// Create the projection with two standard parallels and the latitude of origin on the ellipsoid.
+    ///
+    /// Fails if the standard parallels are symmetric about the equator, see `ConicError::ZeroConeConstant`.
+    pub fn with_two_sp(elps: Ellipsoid, lat1: f64, lat2: f64, lat0: f64, lon0: f64) -> Result<Self, ConicError>
+    {
+        let qp = 2.0 * (elps.authalic_radius() / elps.a).sq();
+        let q = |lat: f64| qp * trig::sincosd(elps.convert_latitude(lat, AuxiliaryLatitude::Geographic, AuxiliaryLatitude::Authalic)).0;
+        let m1 = parallel_radius(&elps, lat1);
+        let n = if lat1 == lat2
+        {
+            trig::sincosd(lat1).0
+        }
+        else
+        {
+            (m1.sq() - parallel_radius(&elps, lat2).sq()) / (q(lat2) - q(lat1))
+        };
+        check_cone_constant(n, lat1, lat2)?;
+        let c = m1.sq() + n * q(lat1);
+        let rho0 = elps.a * (c - n * q(lat0)).sqrt() / n;
+        Ok(Self { elps, lat0, lon0, false_easting: 0.0, false_northing: 0.0, qp, n, c, rho0 })
+    }
+
+    /// Set the false easting and northing (meters).
+    pub fn false_origin(mut self, easting: f64, northing: f64) -> Self
+    {
+        self.false_easting = easting;
+        self.false_northing = northing;
+        self
+    }
+}
+
+impl Projection for AlbersEqualArea
+{
+    fn forward(&self, lat: f64, lon: f64) -> ProjectionSolution
+    {
+        let xi = self.elps.convert_latitude(lat, AuxiliaryLatitude::Geographic, AuxiliaryLatitude::Authalic);
+        let q = self.qp * trig::sincosd(xi).0;
+        let rho = self.elps.a * (self.c - self.n * q).sqrt() / self.n;
+        let theta = self.n * trig::ang_diff(self.lon0, lon).0.to_radians();
+        let x = self.false_easting + rho * theta.sin();
+        let y = self.false_northing + self.rho0 - rho * theta.cos();
+        solution(&self.elps, self.n, (lat, lon), (x, y), rho, theta)
+    }
+
+    fn reverse(&self, x: f64, y: f64) -> ProjectionSolution
+    {
+        let sign = self.n.signum();
+        let (dx, dy) = (x - self.false_easting, self.rho0 - (y - self.false_northing));
+        let rho = sign * dx.hypot(dy);
+        let theta = (sign * dx).atan2(sign * dy);
+        let q = (self.c - (rho * self.n / self.elps.a).sq()) / self.n;
+        let xi = (q / self.qp).clamp(-1.0, 1.0).asin().to_degrees();
+        let lat = self.elps.convert_latitude(xi, AuxiliaryLatitude::Authalic, AuxiliaryLatitude::Geographic);
+        let lon = (self.lon0 + (theta / self.n).to_degrees()).norm_degrees(Remainder::InvertedSymmetry);
+        solution(&self.elps, self.n, (lat, lon), (x, y), rho, theta)
+    }
+}
+
+// Reject the cone constant of 0 of the standard parallels on or symmetric about the equator.
+fn check_cone_constant(n: f64, lat1: f64, lat2: f64) -> Result<(), ConicError>
+{
+    if n == 0.0 || !n.is_finite()
+    {
+        return Err(ConicError::ZeroConeConstant { lat1, lat2 });
+    }
+    Ok(())
+}
+
+// The solution of the point at the radius rho and the angle theta (radians) on the map of the cone constant n.
+fn solution(elps: &Ellipsoid, n: f64, (lat, lon): (f64, f64), (x, y): (f64, f64), rho: f64, theta: f64) -> ProjectionSolution
+{
+    let scale = rho * n / (elps.a * parallel_radius(elps, lat));
+    ProjectionSolution { lat, lon, x, y, convergence: theta.to_degrees(), scale }
+}
+
+// The radius of the parallel divided by the equatorial radius, i.e. m of Snyder.
+fn parallel_radius(elps: &Ellipsoid, lat: f64) -> f64
+{
+    let (s, c) = trig::sincosd(lat);
+    c / (1.0 - elps.e1sq * s.sq()).sqrt()
+}
+
+// The isometric latitude ψ (radians) of the latitude (degrees).
+fn isometric_latitude(lat: f64, es: f64) -> f64
+{
+    taupf(lat.to_radians().tan(), es).asinh()
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use rstest::*;
+    use float_cmp::assert_approx_eq;
+    use crate::earth::models::{Clarke1866, WGS84, Sphere};
+
+    // The US survey foot (meters).
+    const US_FOOT: f64 = 1200.0 / 3937.0;
+
+    fn check(proj: &dyn Projection, lat: f64, lon: f64, x: f64, y: f64, scale: f64, convergence: f64)
+    {
+        let s = proj.forward(lat, lon);
+        assert_approx_eq!(f64, s.x, x, epsilon = 1e-3);
+        assert_approx_eq!(f64, s.y, y, epsilon = 1e-3);
+        assert_approx_eq!(f64, s.scale, scale, epsilon = 1e-9);
+        assert_approx_eq!(f64, s.convergence, convergence, epsilon = 1e-9);
+        let r = proj.reverse(x, y);
+        assert_approx_eq!(f64, r.lat, lat, epsilon = 1e-9);
+        assert_approx_eq!(f64, r.lon, lon, epsilon = 1e-9);
+        assert_approx_eq!(f64, r.scale, scale, epsilon = 1e-9);
+        assert_approx_eq!(f64, r.convergence, convergence, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_lcc_one_sp()
+    {
+        // EPSG Guidance Note 7-2, Lambert Conic Conformal (1SP) of Jamaica.
+        let lcc = LambertConformalConic::one_sp::<Clarke1866>(18.0, -77.0, 1.0).unwrap().false_origin(250000.0, 150000.0);
+        let lat = 17.0 + 55.0 / 60.0 + 55.8 / 3600.0;
+        let lon = -(76.0 + 56.0 / 60.0 + 37.26 / 3600.0);
+        check(&lcc, lat, lon, 255966.58184974367, 142493.511021439, 1.0000006964428496, 0.017402807066547593);
+        assert_approx_eq!(f64, lcc.forward(18.0, -77.0).scale, 1.0, epsilon = 1e-15);
+    }
+
+    #[test]
+    fn test_lcc_two_sp()
+    {
+        // EPSG Guidance Note 7-2, Lambert Conic Conformal (2SP) of Texas South Central, in US survey feet.
+        let (lat1, lat2) = (28.0 + 23.0 / 60.0, 30.0 + 17.0 / 60.0);
+        let lcc = LambertConformalConic::two_sp::<Clarke1866>(lat1, lat2, 27.0 + 50.0 / 60.0, -99.0).unwrap().false_origin(2000000.0 * US_FOOT, 0.0);
+        let s = lcc.forward(28.5, -96.0);
+        assert_approx_eq!(f64, s.x / US_FOOT, 2963503.91, epsilon = 5e-3);
+        assert_approx_eq!(f64, s.y / US_FOOT, 254759.80, epsilon = 5e-3);
+        check(&lcc, 28.5, -96.0, 903277.7991829005, 77650.94253891334, 0.9999685594445235, 1.469737890681915);
+        assert_approx_eq!(f64, lcc.forward(lat1, -90.0).scale, 1.0, epsilon = 1e-12);
+        assert_approx_eq!(f64, lcc.forward(lat2, -100.0).scale, 1.0, epsilon = 1e-12);
+
+        // The cone opens to the north in the southern hemisphere.
+        let lcc = LambertConformalConic::two_sp::<WGS84>(-30.0, -40.0, -35.0, 140.0).unwrap();
+        check(&lcc, -38.0, 150.0, 874698.2291850756, -375663.73574919254, 0.9975408979977691, -5.743203722402016);
+    }
+
+    #[test]
+    fn test_albers()
+    {
+        // Snyder, Map Projections - A Working Manual, p. 292.
+        let aea = AlbersEqualArea::two_sp::<Clarke1866>(29.5, 45.5, 23.0, -96.0).unwrap();
+        check(&aea, 35.0, -75.0, 1885472.72581357, 1535925.0049833711, 0.9915546117929487, 12.660973513187038);
+        assert_approx_eq!(f64, aea.forward(29.5, -96.0).scale, 1.0, epsilon = 1e-12);
+        assert_approx_eq!(f64, aea.forward(45.5, -96.0).scale, 1.0, epsilon = 1e-12);
+
+        let aea = AlbersEqualArea::two_sp::<WGS84>(-18.0, -36.0, 0.0, 132.0).unwrap().false_origin(500000.0, 1000000.0);
+        check(&aea, -25.0, 120.0, -695798.7661178594, -1758772.5109963175, 0.9885734428867458, 5.381747966015051);
+    }
+
+    #[rstest]
+    #[case(45.0, 0.0)]
+    #[case(-30.0, 20.0)]
+    fn test_one_sp(#[case] lat1: f64, #[case] lat0: f64)
+    {
+        // The scale is 1 along the only standard parallel, where the cone is tangent.
+        let aea = AlbersEqualArea::one_sp::<WGS84>(lat1, lat0, 10.0).unwrap();
+        assert_approx_eq!(f64, aea.forward(lat1, 30.0).scale, 1.0, epsilon = 1e-12);
+        let s = aea.forward(lat0, 10.0);
+        assert_approx_eq!(f64, s.x, 0.0, epsilon = 1e-6);
+        assert_approx_eq!(f64, s.y, 0.0, epsilon = 1e-6);
+        let lcc = LambertConformalConic::one_sp::<Sphere>(lat1, 10.0, 0.9999).unwrap();
+        assert_approx_eq!(f64, lcc.forward(lat1, -20.0).scale, 0.9999, epsilon = 1e-12);
+        assert_approx_eq!(f64, lcc.forward(lat1, 10.0).y, 0.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_zero_cone_constant()
+    {
+        let error = |lat1: f64, lat2: f64| ConicError::ZeroConeConstant { lat1, lat2 };
+        assert_eq!(LambertConformalConic::two_sp::<WGS84>(30.0, -30.0, 0.0, 0.0), Err(error(30.0, -30.0)));
+        assert_eq!(LambertConformalConic::one_sp::<WGS84>(0.0, 0.0, 1.0), Err(error(0.0, 0.0)));
+        assert_eq!(AlbersEqualArea::two_sp::<WGS84>(-45.0, 45.0, 0.0, 0.0), Err(error(-45.0, 45.0)));
+        assert_eq!(AlbersEqualArea::one_sp::<WGS84>(0.0, 10.0, 0.0), Err(error(0.0, 0.0)));
+        assert!(LambertConformalConic::two_sp::<WGS84>(30.0, -29.0, 0.0, 0.0).is_ok());
+    }
+
+    #[test]
+    fn test_round_trip()
+    {
+        let projections: [Box<dyn Projection>; 2] =
+        [
+            Box::new(LambertConformalConic::two_sp::<WGS84>(33.0, 45.0, 23.0, -96.0).unwrap().false_origin(1e6, 2e6)),
+            Box::new(AlbersEqualArea::two_sp::<WGS84>(33.0, 45.0, 23.0, -96.0).unwrap().false_origin(1e6, 2e6)),
+        ];
+        for proj in projections.iter()
+        {
+            for lat in [-10.0, 0.0, 23.0, 50.0, 80.0]
+            {
+                for lon in [-179.0, -120.0, -96.0, -60.0, 10.0]
+                {
+                    let s = proj.forward(lat, lon);
+                    let r = proj.reverse(s.x, s.y);
+                    assert_approx_eq!(f64, r.lat, lat, epsilon = 1e-9);
+                    assert_approx_eq!(f64, r.lon, lon, epsilon = 1e-9);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+/// The position of a point on a map projection.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ProjectionSolution
+{
+    /// latitude of the point (degrees).
+    pub lat: f64,
+
+    /// longitude of the point (degrees).
+    pub lon: f64,
+
+    /// easting of the point (meters).
+    pub x: f64,
+
+    /// northing of the point (meters).
+    pub y: f64,
+
+    /// meridian convergence, the bearing of the grid north clockwise from the true north (degrees).
+    pub convergence: f64,
+
+    /// scale of the projection at the point, along the parallel if it is not conformal.
+    pub scale: f64,
+}
+
+/// A map projection with all its parameters, e.g. the origin and the false easting/northing, fixed.
+pub trait Projection
+{
+    /// Project the point (lat, lon) in degrees.
+    fn forward(&self, lat: f64, lon: f64) -> ProjectionSolution;
+
+    /// Unproject the point (x, y) in meters.
+    fn reverse(&self, x: f64, y: f64) -> ProjectionSolution;
+}
@@ -0,0 +1,17 @@
+pub use generic::{Projection, ProjectionSolution};
+pub use tm::{TransverseMercator, TmSolution};
+pub use ps::{PolarStereographic, PsSolution};
+pub use utm::{Utm, UtmError};
+pub use ups::{Ups, UtmUps, UtmUpsPosition};
+pub use webmercator::{WebMercator, WebMercatorSolution, Tile, TileError};
+pub use conic::{LambertConformalConic, AlbersEqualArea, ConicError};
+pub use azimuthal::{AzimuthalEquidistant, Gnomonic, AzimuthalSolution};
+
+mod generic;
+mod tm;
+mod ps;
+mod utm;
+mod ups;
+mod webmercator;
+mod conic;
+mod azimuthal;
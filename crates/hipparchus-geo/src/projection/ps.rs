@@ -0,0 +1,162 @@
+use hipparchus_mean::Power;
+use hipparchus_az::{Degrees, Remainder};
+use crate::earth::auxiliary::{taupf, tauf};
+use crate::earth::ellipsoid::Ellipsoid;
+use crate::earth::models::Model;
+use crate::geodesic::trig;
+
+/// The position of a point on the polar stereographic projection.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PsSolution
+{
+    /// latitude of the point (degrees).
+    pub lat: f64,
+
+    /// longitude of the point (degrees).
+    pub lon: f64,
+
+    /// easting of the point relative to the pole (meters).
+    pub x: f64,
+
+    /// northing of the point relative to the pole (meters).
+    pub y: f64,
+
+    /// meridian convergence, the bearing of the grid north clockwise from the true north (degrees).
+    pub convergence: f64,
+
+    /// scale of the projection at the point.
+    pub scale: f64,
+}
+
+/// Polar stereographic projection centered at the north or the south pole.
+///
+/// The grid north is along the meridian 180° for the north pole and along the meridian 0° for
+/// the south pole, as in the Universal Polar Stereographic (UPS) system.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PolarStereographic
+{
+    pub elps: Ellipsoid,
+
+    /// scale factor at the pole.
+    pub k0: f64,
+
+    // signed eccentricity
+    es: f64,
+
+    // the ratio of the radius of curvature at the pole to the equatorial radius, over 2
+    c: f64,
+}
+
+impl PolarStereographic
+{
+    /// The scale factor at the pole of the UPS projection.
+    pub const UPS_K0: f64 = 0.994;
+
+    pub fn new(a: f64, f: f64, k0: f64) -> Self
+    {
+        Self::with(Ellipsoid::new(a, 1.0 / f), k0)
+    }
+
+    pub fn model<T>(k0: f64) -> Self where T: Model
+    {
+        Self::with(T::elps(), k0)
+    }
+
+    /// Create the projection used by UPS on the model.
+    pub fn ups<T>() -> Self where T: Model
+    {
+        Self::with(T::elps(), Self::UPS_K0)
+    }
+
+    pub fn with(elps: Ellipsoid, k0: f64) -> Self
+    {
+        let es = elps.signed_eccentricity();
+        let c = (1.0 - elps.f) * trig::eatanhe(1.0, es).exp();
+        Self { elps, k0, es, c }
+    }
+
+    /// Project the point (lat, lon) in degrees, centered at the north pole if `north` is true.
+    pub fn forward(&self, north: bool, lat: f64, lon: f64) -> PsSolution
+    {
+        let e2 = self.elps.e1sq;
+        let phi = if north { lat } else { -lat };
+        let tau = phi.to_radians().tan();
+        let secphi = tau.hypot(1.0);
+        let taup = taupf(tau, self.es);
+        let rho = taup.hypot(1.0) + taup.abs();
+        let rho = if taup < 0.0 { rho } else if phi != 90.0 { 1.0 / rho } else { 0.0 };
+        let rho = rho * 2.0 * self.k0 * self.elps.a / self.c;
+        let k = if phi != 90.0 { rho / self.elps.a * secphi * ((1.0 - e2) + e2 / secphi.sq()).sqrt() } else { self.k0 };
+        let (slam, clam) = trig::sincosd(lon);
+        PsSolution
+        {
+            lat,
+            lon,
+            x: rho * slam,
+            y: if north { -rho * clam } else { rho * clam },
+            convergence: (if north { lon } else { -lon }).norm_degrees(Remainder::InvertedSymmetry),
+            scale: k,
+        }
+    }
+
+    /// Unproject the point (x, y) in meters, centered at the north pole if `north` is true.
+    pub fn reverse(&self, north: bool, x: f64, y: f64) -> PsSolution
+    {
+        let e2 = self.elps.e1sq;
+        let rho = x.hypot(y);
+        let t = if rho != 0.0 { rho / (2.0 * self.k0 * self.elps.a / self.c) } else { f64::EPSILON.sq() };
+        let taup = (1.0 / t - t) / 2.0;
+        let tau = tauf(taup, self.es);
+        let secphi = tau.hypot(1.0);
+        let k = if rho != 0.0 { rho / self.elps.a * secphi * ((1.0 - e2) + e2 / secphi.sq()).sqrt() } else { self.k0 };
+        let lat = tau.atan().to_degrees();
+        let lon = trig::atan2d(x, if north { -y } else { y });
+        PsSolution
+        {
+            lat: if north { lat } else { -lat },
+            lon,
+            x,
+            y,
+            convergence: (if north { lon } else { -lon }).norm_degrees(Remainder::InvertedSymmetry),
+            scale: k,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::earth::models::WGS84;
+    use float_cmp::assert_approx_eq;
+    use rstest::*;
+
+    #[rstest]
+    #[case(true, 85.0, 30.0, 277728.69569134, -481040.21165723, 0.995894791674975)]
+    #[case(false, -85.0, -120.0, -481040.21165723, -277728.69569134, 0.995894791674975)]
+    #[case(true, 83.5, -60.0, -625615.43870638, -361199.24194631, 0.997204950272114)]
+    #[case(false, -89.0, 45.0, 78507.60526791, 78507.60526791, 0.994075701194405)]
+    fn test_ups(#[case] north: bool, #[case] lat: f64, #[case] lon: f64, #[case] x: f64, #[case] y: f64, #[case] k: f64)
+    {
+        let ps = PolarStereographic::ups::<WGS84>();
+        let fwd = ps.forward(north, lat, lon);
+        assert_approx_eq!(f64, fwd.x, x, epsilon = 1e-6);
+        assert_approx_eq!(f64, fwd.y, y, epsilon = 1e-6);
+        assert_approx_eq!(f64, fwd.scale, k, epsilon = 1e-14);
+        let rev = ps.reverse(north, fwd.x, fwd.y);
+        assert_approx_eq!(f64, rev.lat, lat, epsilon = 1e-12);
+        assert_approx_eq!(f64, rev.lon, lon, epsilon = 1e-12);
+        assert_approx_eq!(f64, rev.convergence, fwd.convergence, epsilon = 1e-12);
+        assert_approx_eq!(f64, rev.scale, k, epsilon = 1e-14);
+    }
+
+    #[test]
+    fn test_pole()
+    {
+        let ps = PolarStereographic::ups::<WGS84>();
+        let sol = ps.forward(true, 90.0, 30.0);
+        assert_eq!((sol.x, sol.y.abs(), sol.scale), (0.0, 0.0, PolarStereographic::UPS_K0));
+        let sol = ps.reverse(false, 0.0, 0.0);
+        assert_eq!((sol.lat, sol.scale), (-90.0, PolarStereographic::UPS_K0));
+    }
+}
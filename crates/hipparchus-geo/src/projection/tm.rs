@@ -0,0 +1,317 @@
+use hipparchus_mean::Power;
+use hipparchus_az::{Degrees, Remainder};
+use num::complex::Complex64;
+use crate::earth::auxiliary::{taupf, tauf};
+use crate::earth::ellipsoid::Ellipsoid;
+use crate::earth::models::Model;
+use crate::geodesic::trig;
+
+// The order of the Krüger series.
+const ORDER: usize = 6;
+
+/// The position of a point on the transverse Mercator projection.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TmSolution
+{
+    /// latitude of the point (degrees).
+    pub lat: f64,
+
+    /// longitude of the point (degrees).
+    pub lon: f64,
+
+    /// easting of the point relative to the central meridian (meters).
+    pub x: f64,
+
+    /// northing of the point relative to the equator (meters).
+    pub y: f64,
+
+    /// meridian convergence, the bearing of the grid north clockwise from the true north (degrees).
+    pub convergence: f64,
+
+    /// scale of the projection at the point.
+    pub scale: f64,
+}
+
+/// Transverse Mercator projection with the Krüger series to order n^6.
+///
+/// The series are accurate to a few nanometers within 3900 km of the central meridian, see
+/// Karney (2011), Transverse Mercator with an accuracy of a few nanometers.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TransverseMercator
+{
+    pub elps: Ellipsoid,
+
+    /// central scale factor.
+    pub k0: f64,
+
+    // signed eccentricity
+    es: f64,
+
+    // k0 times the rectifying radius
+    a1: f64,
+
+    // the ratio of the rectifying radius to the equatorial radius
+    b1: f64,
+
+    // the scale at the pole divided by k0
+    c: f64,
+
+    // coefficients of the forward and reverse series
+    alp: [f64; ORDER + 1],
+    bet: [f64; ORDER + 1],
+}
+
+impl TransverseMercator
+{
+    /// The central scale factor of the UTM projection.
+    pub const UTM_K0: f64 = 0.9996;
+
+    pub fn new(a: f64, f: f64, k0: f64) -> Self
+    {
+        Self::with(Ellipsoid::new(a, 1.0 / f), k0)
+    }
+
+    pub fn model<T>(k0: f64) -> Self where T: Model
+    {
+        Self::with(T::elps(), k0)
+    }
+
+    /// Create the projection used by UTM on the model.
+    pub fn utm<T>() -> Self where T: Model
+    {
+        Self::with(T::elps(), Self::UTM_K0)
+    }
+
+    pub fn with(elps: Ellipsoid, k0: f64) -> Self
+    {
+        let es = elps.signed_eccentricity();
+        let b1 = elps.rectifying_radius() / elps.a;
+        let c = (1.0 - elps.e1sq).sqrt() * trig::eatanhe(1.0, es).exp();
+        let n = elps.n;
+        let alp =
+        [
+            0.0,
+            n * (1.0 / 2.0 + n * (-2.0 / 3.0 + n * (5.0 / 16.0 + n * (41.0 / 180.0 + n * (-127.0 / 288.0 + n * 7891.0 / 37800.0))))),
+            n.powi(2) * (13.0 / 48.0 + n * (-3.0 / 5.0 + n * (557.0 / 1440.0 + n * (281.0 / 630.0 - n * 1983433.0 / 1935360.0)))),
+            n.powi(3) * (61.0 / 240.0 + n * (-103.0 / 140.0 + n * (15061.0 / 26880.0 + n * 167603.0 / 181440.0))),
+            n.powi(4) * (49561.0 / 161280.0 + n * (-179.0 / 168.0 + n * 6601661.0 / 7257600.0)),
+            n.powi(5) * (34729.0 / 80640.0 - n * 3418889.0 / 1995840.0),
+            n.powi(6) * (212378941.0 / 319334400.0),
+        ];
+        let bet =
+        [
+            0.0,
+            n * (1.0 / 2.0 + n * (-2.0 / 3.0 + n * (37.0 / 96.0 + n * (-1.0 / 360.0 + n * (-81.0 / 512.0 + n * 96199.0 / 604800.0))))),
+            n.powi(2) * (1.0 / 48.0 + n * (1.0 / 15.0 + n * (-437.0 / 1440.0 + n * (46.0 / 105.0 - n * 1118711.0 / 3870720.0)))),
+            n.powi(3) * (17.0 / 480.0 + n * (-37.0 / 840.0 + n * (-209.0 / 4480.0 + n * 5569.0 / 90720.0))),
+            n.powi(4) * (4397.0 / 161280.0 + n * (-11.0 / 504.0 - n * 830251.0 / 7257600.0)),
+            n.powi(5) * (4583.0 / 161280.0 - n * 108847.0 / 3991680.0),
+            n.powi(6) * (20648693.0 / 638668800.0),
+        ];
+        Self { elps, k0, es, a1: k0 * b1 * elps.a, b1, c, alp, bet }
+    }
+
+    /// Project the point (lat, lon) with the central meridian lon0 (degrees).
+    pub fn forward(&self, lon0: f64, lat: f64, lon: f64) -> TmSolution
+    {
+        let e2 = self.elps.e1sq;
+        let lon12 = trig::ang_diff(lon0, lon).0;
+        let latsign = if lat.is_sign_negative() { -1.0 } else { 1.0 };
+        let lonsign = if lon12.is_sign_negative() { -1.0 } else { 1.0 };
+        let (mut phi, mut lam) = (lat.abs(), lon12.abs());
+        let backside = lam > 90.0;
+        let mut latsign = latsign;
+        if backside
+        {
+            if phi == 0.0
+            {
+                latsign = -1.0;
+            }
+            lam = 180.0 - lam;
+        }
+        phi = phi.min(90.0);
+        let (sphi, cphi) = trig::sincosd(phi);
+        let (slam, clam) = trig::sincosd(lam);
+
+        // The transverse Mercator projection of the conformal sphere.
+        let (xip, etap, mut gamma, mut k) = if phi != 90.0
+        {
+            let tau = sphi / cphi;
+            let taup = taupf(tau, self.es);
+            let h = taup.hypot(clam);
+            let gamma = trig::atan2d(slam * taup, clam * taup.hypot(1.0));
+            let k = ((1.0 - e2) + e2 * cphi.sq()).sqrt() * tau.hypot(1.0) / h;
+            (taup.atan2(clam), (slam / h).asinh(), gamma, k)
+        }
+        else
+        {
+            (std::f64::consts::FRAC_PI_2, 0.0, lam, self.c)
+        };
+
+        // Apply the Krüger series with the Clenshaw summation.
+        let (c0, s0) = ((2.0 * xip).cos(), (2.0 * xip).sin());
+        let (ch0, sh0) = ((2.0 * etap).cosh(), (2.0 * etap).sinh());
+        let a = Complex64::new(2.0 * c0 * ch0, -2.0 * s0 * sh0);
+        let (mut y0, mut y1, mut z0, mut z1) = (Complex64::default(), Complex64::default(), Complex64::default(), Complex64::default());
+        let mut j = ORDER;
+        while j > 0
+        {
+            y1 = a * y0 - y1 + self.alp[j];
+            z1 = a * z0 - z1 + 2.0 * j as f64 * self.alp[j];
+            j -= 1;
+            y0 = a * y1 - y0 + self.alp[j];
+            z0 = a * z1 - z0 + 2.0 * j as f64 * self.alp[j];
+            j -= 1;
+        }
+        let z1 = Complex64::new(1.0, 0.0) - z1 + a * 0.5 * z0;
+        let y1 = Complex64::new(xip, etap) + Complex64::new(s0 * ch0, c0 * sh0) * y0;
+        gamma -= trig::atan2d(z1.im, z1.re);
+        k *= self.b1 * z1.norm();
+
+        let xi = if backside { std::f64::consts::PI - y1.re } else { y1.re };
+        if backside
+        {
+            gamma = 180.0 - gamma;
+        }
+        gamma = (gamma * latsign * lonsign).norm_degrees(Remainder::InvertedSymmetry);
+        TmSolution
+        {
+            lat,
+            lon,
+            x: self.a1 * y1.im * lonsign,
+            y: self.a1 * xi * latsign,
+            convergence: gamma,
+            scale: k * self.k0,
+        }
+    }
+
+    /// Unproject the point (x, y) in meters with the central meridian lon0 (degrees).
+    pub fn reverse(&self, lon0: f64, x: f64, y: f64) -> TmSolution
+    {
+        let e2 = self.elps.e1sq;
+        let xisign = if y.is_sign_negative() { -1.0 } else { 1.0 };
+        let etasign = if x.is_sign_negative() { -1.0 } else { 1.0 };
+        let (mut xi, eta) = ((y / self.a1).abs(), (x / self.a1).abs());
+        let backside = xi > std::f64::consts::FRAC_PI_2;
+        if backside
+        {
+            xi = std::f64::consts::PI - xi;
+        }
+
+        // Revert the Krüger series with the Clenshaw summation.
+        let (c0, s0) = ((2.0 * xi).cos(), (2.0 * xi).sin());
+        let (ch0, sh0) = ((2.0 * eta).cosh(), (2.0 * eta).sinh());
+        let a = Complex64::new(2.0 * c0 * ch0, -2.0 * s0 * sh0);
+        let (mut y0, mut y1, mut z0, mut z1) = (Complex64::default(), Complex64::default(), Complex64::default(), Complex64::default());
+        let mut j = ORDER;
+        while j > 0
+        {
+            y1 = a * y0 - y1 - self.bet[j];
+            z1 = a * z0 - z1 - 2.0 * j as f64 * self.bet[j];
+            j -= 1;
+            y0 = a * y1 - y0 - self.bet[j];
+            z0 = a * z1 - z0 - 2.0 * j as f64 * self.bet[j];
+            j -= 1;
+        }
+        let z1 = Complex64::new(1.0, 0.0) - z1 + a * 0.5 * z0;
+        let y1 = Complex64::new(xi, eta) + Complex64::new(s0 * ch0, c0 * sh0) * y0;
+        let mut gamma = trig::atan2d(z1.im, z1.re);
+        let mut k = self.b1 / z1.norm();
+
+        // Unproject the conformal sphere.
+        let (xip, etap) = (y1.re, y1.im);
+        let s = etap.sinh();
+        let c = xip.cos().max(0.0);
+        let r = s.hypot(c);
+        let (lat, mut lon) = if r != 0.0
+        {
+            let sxip = xip.sin();
+            let tau = tauf(sxip / r, self.es);
+            gamma += trig::atan2d(sxip * etap.tanh(), c);
+            k *= ((1.0 - e2) + e2 / (1.0 + tau.sq())).sqrt() * tau.hypot(1.0) * r;
+            (tau.atan().to_degrees(), trig::atan2d(s, c))
+        }
+        else
+        {
+            k *= self.c;
+            (90.0, 0.0)
+        };
+        if backside
+        {
+            lon = 180.0 - lon;
+            gamma = 180.0 - gamma;
+        }
+        TmSolution
+        {
+            lat: lat * xisign,
+            lon: (lon * etasign + lon0).norm_degrees(Remainder::InvertedSymmetry),
+            x,
+            y,
+            convergence: (gamma * xisign * etasign).norm_degrees(Remainder::InvertedSymmetry),
+            scale: k * self.k0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::earth::models::{WGS84, Clarke1866, Sphere};
+    use float_cmp::assert_approx_eq;
+    use rstest::*;
+
+    #[test]
+    fn test_snyder()
+    {
+        // Snyder (1987), Map Projections: A Working Manual, p. 269.
+        let tm = TransverseMercator::utm::<Clarke1866>();
+        let sol = tm.forward(-75.0, 40.5, -73.5);
+        assert_approx_eq!(f64, sol.x, 127106.5, epsilon = 0.05);
+        assert_approx_eq!(f64, sol.y, 4484124.4, epsilon = 0.05);
+        assert_approx_eq!(f64, sol.scale, 0.9997989, epsilon = 1e-7);
+    }
+
+    #[rstest]
+    #[case(0.0, 0.0)]
+    #[case(33.3, 3.0)]
+    #[case(-45.0, -2.9)]
+    #[case(60.0, 30.0)]
+    #[case(80.0, -70.0)]
+    #[case(89.99, 10.0)]
+    fn test_round_trip(#[case] lat: f64, #[case] lon: f64)
+    {
+        let tm = TransverseMercator::utm::<WGS84>();
+        let fwd = tm.forward(0.0, lat, lon);
+        let rev = tm.reverse(0.0, fwd.x, fwd.y);
+        assert_approx_eq!(f64, rev.lat, lat, epsilon = 1e-12);
+        assert_approx_eq!(f64, rev.lon, lon, epsilon = 1e-11);
+        assert_approx_eq!(f64, rev.convergence, fwd.convergence, epsilon = 1e-11);
+        assert_approx_eq!(f64, rev.scale, fwd.scale, epsilon = 1e-13);
+        assert_eq!(fwd.x.is_sign_negative(), lon < 0.0);
+    }
+
+    #[test]
+    fn test_central_meridian()
+    {
+        // The projection is true to scale along the central meridian.
+        let tm = TransverseMercator::model::<WGS84>(1.0);
+        let sol = tm.forward(45.0, 90.0, 45.0);
+        assert_approx_eq!(f64, sol.y, 10001965.729313, epsilon = 1e-6);
+        assert_eq!(sol.x, 0.0);
+        let sol = tm.forward(45.0, 30.0, 45.0);
+        assert_approx_eq!(f64, sol.scale, 1.0, epsilon = 1e-15);
+        assert_approx_eq!(f64, sol.convergence, 0.0, epsilon = 1e-15);
+    }
+
+    #[test]
+    fn test_sphere()
+    {
+        // On a sphere x = R atanh(cos φ sin λ).
+        let tm = TransverseMercator::model::<Sphere>(1.0);
+        let sol = tm.forward(0.0, 20.0, 10.0);
+        let (phi, lam) = (20f64.to_radians(), 10f64.to_radians());
+        assert_approx_eq!(f64, sol.x, Sphere::A * (phi.cos() * lam.sin()).atanh(), epsilon = 1e-6);
+        assert_approx_eq!(f64, sol.scale, 1.0 / (1.0 - (phi.cos() * lam.sin()).sq()).sqrt(), epsilon = 1e-14);
+    }
+}
@@ -0,0 +1,270 @@
+use crate::coords::latlon::LatLon;
+use crate::earth::ellipsoid::Ellipsoid;
+use crate::earth::models::Model;
+use crate::projection::ps::{PolarStereographic, PsSolution};
+use crate::projection::tm::TransverseMercator;
+use crate::projection::utm::{Utm, UtmError};
+
+/// The false easting and northing of UPS (meters).
+const FALSE_ORIGIN: f64 = 2000e3;
+
+/// The position of a point in the Universal Polar Stereographic (UPS) system.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Ups
+{
+    /// true for the north polar region, false for the south polar region.
+    pub north: bool,
+
+    /// easting including the false easting (meters).
+    pub easting: f64,
+
+    /// northing including the false northing (meters).
+    pub northing: f64,
+
+    /// meridian convergence, the bearing of the grid north clockwise from the true north (degrees).
+    pub convergence: f64,
+
+    /// scale of the projection at the point.
+    pub scale: f64,
+}
+
+impl Ups
+{
+    /// The minimum latitude of the north polar region, which overlaps UTM by 0.5° (degrees).
+    pub const MIN_NORTH_LATITUDE: f64 = 83.5;
+
+    /// The maximum latitude of the south polar region, which overlaps UTM by 0.5° (degrees).
+    pub const MAX_SOUTH_LATITUDE: f64 = -79.5;
+
+    /// Convert the point to UPS, in the polar region of its hemisphere.
+    pub fn from_latlon(ps: &PolarStereographic, p: LatLon) -> Result<Self, UtmError>
+    {
+        let lat = p.latitude();
+        let north = lat >= 0.0;
+        if lat < Self::MIN_NORTH_LATITUDE && lat > Self::MAX_SOUTH_LATITUDE
+        {
+            return Err(UtmError::LatitudeOutOfRange(lat));
+        }
+        let sol = ps.forward(north, lat, p.longitude());
+        let (easting, northing) = (sol.x + FALSE_ORIGIN, sol.y + FALSE_ORIGIN);
+        Self::check_coords(easting, northing)?;
+        Ok(Self { north, easting, northing, convergence: sol.convergence, scale: sol.scale })
+    }
+
+    /// Create the UPS position from its polar region, easting and northing (meters).
+    pub fn new(ps: &PolarStereographic, north: bool, easting: f64, northing: f64) -> Result<Self, UtmError>
+    {
+        Self::check_coords(easting, northing)?;
+        let sol = Self::reverse(ps, north, easting, northing);
+        Ok(Self { north, easting, northing, convergence: sol.convergence, scale: sol.scale })
+    }
+
+    /// Convert the UPS position back to latitude and longitude.
+    pub fn to_latlon(&self, ps: &PolarStereographic) -> LatLon
+    {
+        let sol = Self::reverse(ps, self.north, self.easting, self.northing);
+        LatLon::new(sol.lat, sol.lon)
+    }
+
+    fn reverse(ps: &PolarStereographic, north: bool, easting: f64, northing: f64) -> PsSolution
+    {
+        ps.reverse(north, easting - FALSE_ORIGIN, northing - FALSE_ORIGIN)
+    }
+
+    fn check_coords(easting: f64, northing: f64) -> Result<(), UtmError>
+    {
+        if !(0.0..=2.0 * FALSE_ORIGIN).contains(&easting)
+        {
+            return Err(UtmError::EastingOutOfRange(easting));
+        }
+        if !(0.0..=2.0 * FALSE_ORIGIN).contains(&northing)
+        {
+            return Err(UtmError::NorthingOutOfRange(northing));
+        }
+        Ok(())
+    }
+}
+
+/// The position of a point in UTM or UPS.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum UtmUpsPosition
+{
+    Utm(Utm),
+    Ups(Ups),
+}
+
+impl UtmUpsPosition
+{
+    /// Get the UTM zone, 0 for UPS.
+    pub fn zone(&self) -> u8
+    {
+        match self
+        {
+            UtmUpsPosition::Utm(utm) => utm.zone,
+            UtmUpsPosition::Ups(_) => 0,
+        }
+    }
+
+    /// Get the hemisphere, true for the north.
+    pub fn north(&self) -> bool
+    {
+        match self
+        {
+            UtmUpsPosition::Utm(utm) => utm.north,
+            UtmUpsPosition::Ups(ups) => ups.north,
+        }
+    }
+
+    /// Get the easting (meters).
+    pub fn easting(&self) -> f64
+    {
+        match self
+        {
+            UtmUpsPosition::Utm(utm) => utm.easting,
+            UtmUpsPosition::Ups(ups) => ups.easting,
+        }
+    }
+
+    /// Get the northing (meters).
+    pub fn northing(&self) -> f64
+    {
+        match self
+        {
+            UtmUpsPosition::Utm(utm) => utm.northing,
+            UtmUpsPosition::Ups(ups) => ups.northing,
+        }
+    }
+}
+
+/// Convert the points between latitude/longitude and the UTM/UPS grid of a model.
+///
+/// The standard choice is UTM between 80°S and 84°N and UPS in the polar regions beyond.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct UtmUps
+{
+    pub tm: TransverseMercator,
+    pub ps: PolarStereographic,
+}
+
+impl UtmUps
+{
+    pub fn model<T>() -> Self where T: Model
+    {
+        Self::with(T::elps())
+    }
+
+    pub fn with(elps: Ellipsoid) -> Self
+    {
+        Self
+        {
+            tm: TransverseMercator::with(elps, TransverseMercator::UTM_K0),
+            ps: PolarStereographic::with(elps, PolarStereographic::UPS_K0),
+        }
+    }
+
+    /// Convert the point to UTM or UPS, according to its latitude.
+    pub fn forward(&self, p: LatLon) -> Result<UtmUpsPosition, UtmError>
+    {
+        let lat = p.latitude();
+        if (Utm::MIN_LATITUDE..Utm::MAX_LATITUDE).contains(&lat)
+        {
+            Utm::from_latlon(&self.tm, p).map(UtmUpsPosition::Utm)
+        }
+        else
+        {
+            Ups::from_latlon(&self.ps, p).map(UtmUpsPosition::Ups)
+        }
+    }
+
+    /// Convert the point to the given UTM zone, or to UPS if `zone` is 0.
+    pub fn forward_in_zone(&self, p: LatLon, zone: u8) -> Result<UtmUpsPosition, UtmError>
+    {
+        if zone == 0
+        {
+            Ups::from_latlon(&self.ps, p).map(UtmUpsPosition::Ups)
+        }
+        else
+        {
+            Utm::from_latlon_in_zone(&self.tm, p, zone).map(UtmUpsPosition::Utm)
+        }
+    }
+
+    /// Create the position from its zone (0 for UPS), hemisphere, easting and northing (meters).
+    pub fn position(&self, zone: u8, north: bool, easting: f64, northing: f64) -> Result<UtmUpsPosition, UtmError>
+    {
+        if zone == 0
+        {
+            Ups::new(&self.ps, north, easting, northing).map(UtmUpsPosition::Ups)
+        }
+        else
+        {
+            Utm::new(&self.tm, zone, north, easting, northing).map(UtmUpsPosition::Utm)
+        }
+    }
+
+    /// Convert the position back to latitude and longitude.
+    pub fn reverse(&self, pos: &UtmUpsPosition) -> LatLon
+    {
+        match pos
+        {
+            UtmUpsPosition::Utm(utm) => utm.to_latlon(&self.tm),
+            UtmUpsPosition::Ups(ups) => ups.to_latlon(&self.ps),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::earth::models::WGS84;
+    use float_cmp::assert_approx_eq;
+    use rstest::*;
+
+    #[rstest]
+    #[case(33.3, 44.4, 38, true)]
+    #[case(83.9, 10.0, 33, true)]
+    #[case(84.0, 10.0, 0, true)]
+    #[case(-80.0, -70.0, 19, false)]
+    #[case(-80.1, -70.0, 0, false)]
+    #[case(90.0, 0.0, 0, true)]
+    #[case(-90.0, 0.0, 0, false)]
+    fn test_utmups(#[case] lat: f64, #[case] lon: f64, #[case] zone: u8, #[case] north: bool)
+    {
+        let grid = UtmUps::model::<WGS84>();
+        let pos = grid.forward(LatLon::new(lat, lon)).unwrap();
+        assert_eq!(pos.zone(), zone);
+        assert_eq!(pos.north(), north);
+        let back = grid.position(zone, north, pos.easting(), pos.northing()).unwrap();
+        let p = grid.reverse(&back);
+        assert_approx_eq!(f64, p.latitude(), lat, epsilon = 1e-12);
+        if lat.abs() != 90.0
+        {
+            assert_approx_eq!(f64, p.longitude(), lon, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_ups()
+    {
+        let grid = UtmUps::model::<WGS84>();
+        let pos = grid.forward(LatLon::new(85.0, 30.0)).unwrap();
+        assert_approx_eq!(f64, pos.easting(), 2277728.69569134, epsilon = 1e-6);
+        assert_approx_eq!(f64, pos.northing(), 1518959.78834277, epsilon = 1e-6);
+        let pos = grid.forward(LatLon::new(90.0, 0.0)).unwrap();
+        assert_eq!((pos.easting(), pos.northing()), (2000e3, 2000e3));
+    }
+
+    #[test]
+    fn test_forward_in_zone()
+    {
+        let grid = UtmUps::model::<WGS84>();
+        let pos = grid.forward_in_zone(LatLon::new(83.7, 10.0), 0).unwrap();
+        assert!(matches!(pos, UtmUpsPosition::Ups(Ups { north: true, .. })));
+        let pos = grid.forward_in_zone(LatLon::new(83.7, 10.0), 32).unwrap();
+        assert!(matches!(pos, UtmUpsPosition::Utm(Utm { zone: 32, .. })));
+        assert_eq!(grid.forward_in_zone(LatLon::new(60.0, 10.0), 0), Err(UtmError::LatitudeOutOfRange(60.0)));
+        assert_eq!(grid.forward_in_zone(LatLon::new(86.0, 10.0), 32), Err(UtmError::LatitudeOutOfRange(86.0)));
+        assert_eq!(grid.position(0, true, 5000e3, 2000e3), Err(UtmError::EastingOutOfRange(5000e3)));
+    }
+}
@@ -0,0 +1,249 @@
+use crate::coords::latlon::LatLon;
+use crate::projection::tm::{TransverseMercator, TmSolution};
+
+/// The false easting of UTM (meters).
+const FALSE_EASTING: f64 = 500e3;
+
+/// The false northing of UTM in the southern hemisphere (meters).
+const FALSE_NORTHING: f64 = 10000e3;
+
+/// Errors of the UTM and UPS conversions.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum UtmError
+{
+    /// The zone is not in the range [1, 60].
+    InvalidZone(u8),
+
+    /// The latitude is out of the range of the grid, [-80°, 84°] for UTM.
+    LatitudeOutOfRange(f64),
+
+    /// The easting is out of the range of the grid, [0, 1000 km] for UTM.
+    EastingOutOfRange(f64),
+
+    /// The northing is out of the range of the grid, [0, 9600 km] for the north and [1000 km, 10000 km] for the south of UTM.
+    NorthingOutOfRange(f64),
+}
+
+impl std::fmt::Display for UtmError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self
+        {
+            UtmError::InvalidZone(zone) => write!(f, "UTM zone {zone} is not in [1, 60]"),
+            UtmError::LatitudeOutOfRange(lat) => write!(f, "latitude {lat} is out of the range of the grid"),
+            UtmError::EastingOutOfRange(easting) => write!(f, "easting {easting} is out of the range of the grid"),
+            UtmError::NorthingOutOfRange(northing) => write!(f, "northing {northing} is out of the range of the grid"),
+        }
+    }
+}
+
+impl std::error::Error for UtmError {}
+
+/// The position of a point in the Universal Transverse Mercator (UTM) system.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Utm
+{
+    /// UTM zone in [1, 60].
+    pub zone: u8,
+
+    /// true for the northern hemisphere, false for the southern hemisphere.
+    pub north: bool,
+
+    /// easting including the false easting (meters).
+    pub easting: f64,
+
+    /// northing including the false northing in the southern hemisphere (meters).
+    pub northing: f64,
+
+    /// meridian convergence, the bearing of the grid north clockwise from the true north (degrees).
+    pub convergence: f64,
+
+    /// scale of the projection at the point.
+    pub scale: f64,
+}
+
+impl Utm
+{
+    /// The minimum latitude covered by UTM (degrees).
+    pub const MIN_LATITUDE: f64 = -80.0;
+
+    /// The maximum latitude covered by UTM (degrees).
+    pub const MAX_LATITUDE: f64 = 84.0;
+
+    /// Get the standard UTM zone of the point, with the exceptions of Norway and Svalbard.
+    ///
+    /// The latitude is expected in the UTM range [-80°, 84°].
+    pub fn standard_zone(lat: f64, lon: f64) -> u8
+    {
+        let lon = LatLon::new(lat, lon).longitude();
+        let ilon = if lon.floor() >= 180.0 { -180 } else { lon.floor() as i32 };
+        let zone = (ilon + 186) / 6;
+        let band = ((lat / 8.0).floor() as i32).clamp(-10, 9);
+        if band == 7 && zone == 31 && ilon >= 3
+        {
+            // The south-west coast of Norway is in zone 32.
+            32
+        }
+        else if band == 9 && (0..42).contains(&ilon)
+        {
+            // Svalbard is covered by the zones 31, 33, 35 and 37.
+            (2 * ((ilon + 183) / 12) + 1) as u8
+        }
+        else
+        {
+            zone as u8
+        }
+    }
+
+    /// Get the longitude of the central meridian of the zone (degrees).
+    pub fn central_meridian(zone: u8) -> f64
+    {
+        6.0 * zone as f64 - 183.0
+    }
+
+    /// Convert the point to UTM in its standard zone.
+    pub fn from_latlon(tm: &TransverseMercator, p: LatLon) -> Result<Self, UtmError>
+    {
+        Self::from_latlon_in_zone(tm, p, Self::standard_zone(p.latitude(), p.longitude()))
+    }
+
+    /// Convert the point to UTM in the given zone, e.g. to keep the points of a survey in a single zone.
+    pub fn from_latlon_in_zone(tm: &TransverseMercator, p: LatLon, zone: u8) -> Result<Self, UtmError>
+    {
+        Self::check_zone(zone)?;
+        let lat = p.latitude();
+        if !(Self::MIN_LATITUDE..=Self::MAX_LATITUDE).contains(&lat)
+        {
+            return Err(UtmError::LatitudeOutOfRange(lat));
+        }
+        let north = lat >= 0.0;
+        let sol = tm.forward(Self::central_meridian(zone), lat, p.longitude());
+        let easting = sol.x + FALSE_EASTING;
+        let northing = if north { sol.y } else { sol.y + FALSE_NORTHING };
+        Self::check_coords(north, easting, northing)?;
+        Ok(Self { zone, north, easting, northing, convergence: sol.convergence, scale: sol.scale })
+    }
+
+    /// Create the UTM position from its zone, hemisphere, easting and northing (meters).
+    pub fn new(tm: &TransverseMercator, zone: u8, north: bool, easting: f64, northing: f64) -> Result<Self, UtmError>
+    {
+        Self::check_zone(zone)?;
+        Self::check_coords(north, easting, northing)?;
+        let sol = Self::reverse(tm, zone, north, easting, northing);
+        Ok(Self { zone, north, easting, northing, convergence: sol.convergence, scale: sol.scale })
+    }
+
+    /// Convert the UTM position back to latitude and longitude.
+    pub fn to_latlon(&self, tm: &TransverseMercator) -> LatLon
+    {
+        let sol = Self::reverse(tm, self.zone, self.north, self.easting, self.northing);
+        LatLon::new(sol.lat, sol.lon)
+    }
+
+    fn reverse(tm: &TransverseMercator, zone: u8, north: bool, easting: f64, northing: f64) -> TmSolution
+    {
+        let y = if north { northing } else { northing - FALSE_NORTHING };
+        tm.reverse(Self::central_meridian(zone), easting - FALSE_EASTING, y)
+    }
+
+    fn check_zone(zone: u8) -> Result<(), UtmError>
+    {
+        if (1..=60).contains(&zone) { Ok(()) } else { Err(UtmError::InvalidZone(zone)) }
+    }
+
+    fn check_coords(north: bool, easting: f64, northing: f64) -> Result<(), UtmError>
+    {
+        if !(0.0..=1000e3).contains(&easting)
+        {
+            return Err(UtmError::EastingOutOfRange(easting));
+        }
+        let range = if north { 0.0..=9600e3 } else { 1000e3..=FALSE_NORTHING };
+        if !range.contains(&northing)
+        {
+            return Err(UtmError::NorthingOutOfRange(northing));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::earth::models::WGS84;
+    use float_cmp::assert_approx_eq;
+    use rstest::*;
+
+    #[rstest]
+    #[case(33.3, 44.4, 38, true, 444140.54, 3684706.36)]
+    #[case(-33.3, 44.4, 38, false, 444140.54, 6315293.64)]
+    #[case(0.0, -3.0, 30, true, 500000.0, 0.0)]
+    fn test_utm(#[case] lat: f64, #[case] lon: f64, #[case] zone: u8, #[case] north: bool, #[case] easting: f64, #[case] northing: f64)
+    {
+        let tm = TransverseMercator::utm::<WGS84>();
+        let utm = Utm::from_latlon(&tm, LatLon::new(lat, lon)).unwrap();
+        assert_eq!(utm.zone, zone);
+        assert_eq!(utm.north, north);
+        assert_approx_eq!(f64, utm.easting, easting, epsilon = 5e-3);
+        assert_approx_eq!(f64, utm.northing, northing, epsilon = 5e-3);
+
+        let rev = Utm::new(&tm, zone, north, utm.easting, utm.northing).unwrap();
+        assert_approx_eq!(f64, rev.convergence, utm.convergence, epsilon = 1e-12);
+        assert_approx_eq!(f64, rev.scale, utm.scale, epsilon = 1e-15);
+        let p = rev.to_latlon(&tm);
+        assert_approx_eq!(f64, p.latitude(), lat, epsilon = 1e-12);
+        assert_approx_eq!(f64, p.longitude(), lon, epsilon = 1e-12);
+    }
+
+    #[rstest]
+    #[case(50.0, 3.5, 31)]
+    #[case(56.0, 2.9, 31)]
+    #[case(56.0, 3.0, 32)]
+    #[case(63.9, 5.0, 32)]
+    #[case(64.0, 5.0, 31)]
+    #[case(71.9, 8.0, 32)]
+    #[case(72.0, 8.0, 31)]
+    #[case(78.0, 9.0, 33)]
+    #[case(78.0, 20.9, 33)]
+    #[case(78.0, 21.0, 35)]
+    #[case(78.0, 33.0, 37)]
+    #[case(78.0, 42.0, 38)]
+    #[case(0.0, -180.0, 1)]
+    #[case(0.0, 180.0, 1)]
+    #[case(0.0, 179.9, 60)]
+    fn test_standard_zone(#[case] lat: f64, #[case] lon: f64, #[case] zone: u8)
+    {
+        assert_eq!(Utm::standard_zone(lat, lon), zone);
+    }
+
+    #[test]
+    fn test_forced_zone()
+    {
+        let tm = TransverseMercator::utm::<WGS84>();
+        let p = LatLon::new(45.0, 11.9);
+        let standard = Utm::from_latlon(&tm, p).unwrap();
+        let forced = Utm::from_latlon_in_zone(&tm, p, 33).unwrap();
+        assert_eq!((standard.zone, forced.zone), (32, 33));
+        assert!(forced.easting < 500e3 && standard.easting > 500e3);
+        assert!(forced.convergence < 0.0 && standard.convergence > 0.0);
+        let q = forced.to_latlon(&tm);
+        assert_approx_eq!(f64, q.latitude(), 45.0, epsilon = 1e-12);
+        assert_approx_eq!(f64, q.longitude(), 11.9, epsilon = 1e-12);
+
+        assert_eq!(Utm::from_latlon_in_zone(&tm, p, 0), Err(UtmError::InvalidZone(0)));
+        assert_eq!(Utm::from_latlon_in_zone(&tm, p, 61), Err(UtmError::InvalidZone(61)));
+        assert!(matches!(Utm::from_latlon_in_zone(&tm, p, 45), Err(UtmError::EastingOutOfRange(_))));
+    }
+
+    #[test]
+    fn test_errors()
+    {
+        let tm = TransverseMercator::utm::<WGS84>();
+        assert_eq!(Utm::from_latlon(&tm, LatLon::new(84.5, 0.0)), Err(UtmError::LatitudeOutOfRange(84.5)));
+        assert_eq!(Utm::from_latlon(&tm, LatLon::new(-80.5, 0.0)), Err(UtmError::LatitudeOutOfRange(-80.5)));
+        assert_eq!(Utm::new(&tm, 31, true, -1.0, 0.0), Err(UtmError::EastingOutOfRange(-1.0)));
+        assert_eq!(Utm::new(&tm, 31, false, 500e3, 500e3), Err(UtmError::NorthingOutOfRange(500e3)));
+        assert_eq!(UtmError::InvalidZone(0).to_string(), "UTM zone 0 is not in [1, 60]");
+    }
+}
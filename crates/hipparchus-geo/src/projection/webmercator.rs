@@ -0,0 +1,414 @@
+use std::f64::consts::PI;
+use crate::coords::bbox::BoundingBox;
+use crate::coords::latlon::LatLon;
+use crate::earth::ellipsoid::Ellipsoid;
+use crate::earth::models::{Model, SpherePopular};
+use crate::projection::generic::{Projection, ProjectionSolution};
+
+/// The position of a point on the Web Mercator projection.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct WebMercatorSolution
+{
+    /// latitude of the point (degrees).
+    pub lat: f64,
+
+    /// longitude of the point (degrees).
+    pub lon: f64,
+
+    /// easting of the point (meters).
+    pub x: f64,
+
+    /// northing of the point (meters).
+    pub y: f64,
+
+    /// scale of the projection at the point.
+    pub scale: f64,
+}
+
+/// Web Mercator projection (EPSG:3857), the spherical Mercator projection of the slippy maps.
+///
+/// The geodetic latitudes and longitudes are projected as if they were on the sphere of the
+/// equatorial radius of WGS-84, i.e. `SpherePopular`, so the projection is neither conformal
+/// nor equal-area on the ellipsoid.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct WebMercator
+{
+    pub elps: Ellipsoid,
+}
+
+impl Default for WebMercator
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl WebMercator
+{
+    /// The maximum latitude of the square world map (degrees), i.e. atan(sinh(π)).
+    pub const MAX_LATITUDE: f64 = 85.051_128_779_806_6;
+
+    /// The size of the tiles in pixels.
+    pub const TILE_SIZE: u32 = 256;
+
+    /// Create the projection on `SpherePopular`.
+    pub fn new() -> Self
+    {
+        Self { elps: SpherePopular::elps() }
+    }
+
+    /// Get the half of the width and height of the world map (meters).
+    pub fn extent(&self) -> f64
+    {
+        PI * self.elps.a
+    }
+
+    /// Project the point (lat, lon) in degrees.
+    pub fn forward(&self, lat: f64, lon: f64) -> WebMercatorSolution
+    {
+        let phi = lat.to_radians();
+        WebMercatorSolution
+        {
+            lat,
+            lon,
+            x: self.elps.a * lon.to_radians(),
+            y: self.elps.a * phi.tan().asinh(),
+            scale: 1.0 / phi.cos(),
+        }
+    }
+
+    /// Unproject the point (x, y) in meters.
+    pub fn reverse(&self, x: f64, y: f64) -> WebMercatorSolution
+    {
+        let phi = (y / self.elps.a).sinh().atan();
+        WebMercatorSolution
+        {
+            lat: phi.to_degrees(),
+            lon: (x / self.elps.a).to_degrees(),
+            x,
+            y,
+            scale: 1.0 / phi.cos(),
+        }
+    }
+
+    /// Get the size of a pixel on the ground at the latitude (meters), with the zoom and the tile size in pixels.
+    pub fn resolution(&self, lat: f64, zoom: u8, tile_size: u32) -> Result<f64, TileError>
+    {
+        Ok(2.0 * self.extent() * lat.to_radians().cos() / world_size(zoom, tile_size)?)
+    }
+
+    /// Get the global pixel coordinates of the point from the north-west corner of the world map.
+    ///
+    /// The latitude is clamped to `MAX_LATITUDE`. Fails if the zoom is above `Tile::MAX_ZOOM`.
+    pub fn latlon_to_pixel(&self, p: LatLon, zoom: u8, tile_size: u32) -> Result<(f64, f64), TileError>
+    {
+        let (x, y) = normalized(p);
+        let size = world_size(zoom, tile_size)?;
+        Ok((x * size, y * size))
+    }
+
+    /// Get the point of the global pixel coordinates from the north-west corner of the world map.
+    pub fn pixel_to_latlon(&self, px: f64, py: f64, zoom: u8, tile_size: u32) -> Result<LatLon, TileError>
+    {
+        let size = world_size(zoom, tile_size)?;
+        Ok(LatLon::new(latitude(py / size), px / size * 360.0 - 180.0))
+    }
+
+    /// Get the bounds of the tile in the projection (meters), the west, south, east and north.
+    pub fn tile_bounds(&self, tile: &Tile) -> (f64, f64, f64, f64)
+    {
+        let size = 2.0 * self.extent() / tile.count() as f64;
+        let (x, y) = (tile.x as f64 * size - self.extent(), self.extent() - tile.y as f64 * size);
+        (x, y - size, x + size, y)
+    }
+}
+
+impl Projection for WebMercator
+{
+    fn forward(&self, lat: f64, lon: f64) -> ProjectionSolution
+    {
+        let s = WebMercator::forward(self, lat, lon);
+        ProjectionSolution { lat, lon, x: s.x, y: s.y, convergence: 0.0, scale: s.scale }
+    }
+
+    fn reverse(&self, x: f64, y: f64) -> ProjectionSolution
+    {
+        let s = WebMercator::reverse(self, x, y);
+        ProjectionSolution { lat: s.lat, lon: s.lon, x, y, convergence: 0.0, scale: s.scale }
+    }
+}
+
+/// Errors of the map tiles.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TileError
+{
+    /// The zoom is above `Tile::MAX_ZOOM`.
+    InvalidZoom(u8),
+
+    /// The column or the row is out of the tiles at the zoom.
+    InvalidTile { z: u8, x: u32, y: u32 },
+}
+
+impl std::fmt::Display for TileError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self
+        {
+            TileError::InvalidZoom(z) => write!(f, "tile zoom {z} is above {}", Tile::MAX_ZOOM),
+            TileError::InvalidTile { z, x, y } => write!(f, "tile {z}/{x}/{y} is out of the map"),
+        }
+    }
+}
+
+impl std::error::Error for TileError {}
+
+/// A tile of the XYZ scheme of the slippy maps, with the rows from the north to the south.
+///
+/// The TMS scheme numbers the rows from the south to the north instead, see `tms_y` and `from_tms`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Tile
+{
+    /// The zoom level.
+    pub z: u8,
+
+    /// The column from the west.
+    pub x: u32,
+
+    /// The row from the north.
+    pub y: u32,
+}
+
+impl Tile
+{
+    /// The maximum zoom level.
+    pub const MAX_ZOOM: u8 = 30;
+
+    /// Create the tile of the XYZ scheme.
+    pub fn new(z: u8, x: u32, y: u32) -> Result<Self, TileError>
+    {
+        if z > Self::MAX_ZOOM
+        {
+            return Err(TileError::InvalidZoom(z));
+        }
+        if x >= 1 << z || y >= 1 << z
+        {
+            return Err(TileError::InvalidTile { z, x, y });
+        }
+        Ok(Self { z, x, y })
+    }
+
+    /// Create the tile of the TMS scheme, with the rows from the south.
+    pub fn from_tms(z: u8, x: u32, y: u32) -> Result<Self, TileError>
+    {
+        if z <= Self::MAX_ZOOM && y < 1 << z
+        {
+            Self::new(z, x, (1 << z) - 1 - y)
+        }
+        else
+        {
+            Self::new(z, x, y)
+        }
+    }
+
+    /// Get the tile at the zoom containing the point, with the latitude clamped to `WebMercator::MAX_LATITUDE`.
+    pub fn from_latlon(p: LatLon, z: u8) -> Result<Self, TileError>
+    {
+        if z > Self::MAX_ZOOM
+        {
+            return Err(TileError::InvalidZoom(z));
+        }
+        let (x, y) = normalized(p);
+        let n = (1u64 << z) as f64;
+        let index = |v: f64| ((v * n).floor() as i64).clamp(0, n as i64 - 1) as u32;
+        Ok(Self { z, x: index(x), y: index(y) })
+    }
+
+    /// Get the row in the TMS scheme, counted from the south.
+    pub fn tms_y(&self) -> u32
+    {
+        self.count() - 1 - self.y
+    }
+
+    /// Get the bounds of the tile.
+    pub fn bounds(&self) -> BoundingBox
+    {
+        let n = self.count() as f64;
+        let lon = |x: u32| x as f64 / n * 360.0 - 180.0;
+        let lat = |y: u32| latitude(y as f64 / n);
+        BoundingBox::new(lat(self.y + 1), lon(self.x), lat(self.y), lon(self.x + 1))
+    }
+
+    /// Get the centre of the tile on the map, which is not the middle of the latitudes.
+    pub fn center(&self) -> LatLon
+    {
+        let n = self.count() as f64;
+        LatLon::new(latitude((self.y as f64 + 0.5) / n), (self.x as f64 + 0.5) / n * 360.0 - 180.0)
+    }
+
+    /// Get the tile containing this one at the zoom above, or `None` at the zoom 0.
+    pub fn parent(&self) -> Option<Tile>
+    {
+        self.z.checked_sub(1).map(|z| Self { z, x: self.x / 2, y: self.y / 2 })
+    }
+
+    /// Get the 4 tiles at the zoom below, in the order of NW, NE, SW and SE.
+    pub fn children(&self) -> Result<[Tile; 4], TileError>
+    {
+        if self.z >= Self::MAX_ZOOM
+        {
+            return Err(TileError::InvalidZoom(self.z + 1));
+        }
+        let (z, x, y) = (self.z + 1, self.x * 2, self.y * 2);
+        Ok([(0, 0), (1, 0), (0, 1), (1, 1)].map(|(dx, dy)| Self { z, x: x + dx, y: y + dy }))
+    }
+
+    // The number of the tiles along the width or the height of the map.
+    fn count(&self) -> u32
+    {
+        1 << self.z
+    }
+}
+
+impl std::fmt::Display for Tile
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        write!(f, "{}/{}/{}", self.z, self.x, self.y)
+    }
+}
+
+// The size of the world map in pixels.
+fn world_size(zoom: u8, tile_size: u32) -> Result<f64, TileError>
+{
+    if zoom > Tile::MAX_ZOOM
+    {
+        return Err(TileError::InvalidZoom(zoom));
+    }
+    Ok(tile_size as f64 * (1u32 << zoom) as f64)
+}
+
+// The position of the point on the world map in [0, 1], from the north-west corner.
+fn normalized(p: LatLon) -> (f64, f64)
+{
+    let phi = p.latitude().clamp(-WebMercator::MAX_LATITUDE, WebMercator::MAX_LATITUDE).to_radians();
+    ((p.longitude() + 180.0) / 360.0, (1.0 - phi.tan().asinh() / PI) / 2.0)
+}
+
+// The latitude of the position on the world map in [0, 1], from the north.
+fn latitude(y: f64) -> f64
+{
+    (PI * (1.0 - 2.0 * y)).sinh().atan().to_degrees()
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use rstest::*;
+    use float_cmp::assert_approx_eq;
+
+    #[rstest]
+    #[case(51.5074, -0.1278, -14226.630923380362, 6711542.475587634, 1.6066488161666914)]
+    #[case(-33.8568, 151.2153, 16833210.196152102, -4009589.9342226647, 1.2041916770213745)]
+    #[case(0.0, 180.0, 20037508.342789244, 0.0, 1.0)]
+    #[case(WebMercator::MAX_LATITUDE, 0.0, 0.0, 20037508.342789244, 11.591953275521531)]
+    fn test_forward(#[case] lat: f64, #[case] lon: f64, #[case] x: f64, #[case] y: f64, #[case] scale: f64)
+    {
+        let merc = WebMercator::new();
+        let s = merc.forward(lat, lon);
+        assert_approx_eq!(f64, s.x, x, epsilon = 1e-6);
+        assert_approx_eq!(f64, s.y, y, epsilon = 1e-6);
+        assert_approx_eq!(f64, s.scale, scale, epsilon = 1e-9);
+        let r = merc.reverse(x, y);
+        assert_approx_eq!(f64, r.lat, lat, epsilon = 1e-12);
+        assert_approx_eq!(f64, r.lon, lon, epsilon = 1e-12);
+        assert_approx_eq!(f64, r.scale, s.scale, epsilon = 1e-9);
+        let p = Projection::forward(&merc, lat, lon);
+        assert_eq!((p.x, p.y, p.scale, p.convergence), (s.x, s.y, s.scale, 0.0));
+    }
+
+    #[rstest]
+    #[case(51.5074, -0.1278, 10, 511, 340, 130978.93888, 87169.57060054864)]
+    #[case(-33.8568, 151.2153, 15, 30147, 19662, 7717875.87584, 5033601.921266834)]
+    #[case(48.85, 2.35, 0, 0, 0, 129.67111111111112, 88.0784040274574)]
+    #[case(89.0, -180.0, 4, 0, 0, 0.0, 0.0)]
+    #[case(-89.0, 179.9999999, 4, 15, 15, 4096.0, 4096.0)]
+    fn test_tile(#[case] lat: f64, #[case] lon: f64, #[case] z: u8, #[case] x: u32, #[case] y: u32, #[case] px: f64, #[case] py: f64)
+    {
+        let merc = WebMercator::new();
+        let p = LatLon::new(lat, lon);
+        let tile = Tile::from_latlon(p, z).unwrap();
+        assert_eq!(tile, Tile::new(z, x, y).unwrap());
+        let (qx, qy) = merc.latlon_to_pixel(p, z, WebMercator::TILE_SIZE).unwrap();
+        assert_approx_eq!(f64, qx, px, epsilon = 1e-3);
+        assert_approx_eq!(f64, qy, py, epsilon = 1e-3);
+        if lat.abs() < WebMercator::MAX_LATITUDE
+        {
+            assert!(tile.bounds().contains(p));
+            let q = merc.pixel_to_latlon(px, py, z, WebMercator::TILE_SIZE).unwrap();
+            assert_approx_eq!(f64, q.latitude(), lat, epsilon = 1e-9);
+            assert_approx_eq!(f64, q.longitude(), lon, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_bounds()
+    {
+        let merc = WebMercator::new();
+        let tile = Tile::new(10, 511, 340).unwrap();
+        let b = tile.bounds();
+        assert_approx_eq!(f64, b.south, 51.39920565355377, epsilon = 1e-12);
+        assert_approx_eq!(f64, b.west, -0.3515625, epsilon = 1e-12);
+        assert_approx_eq!(f64, b.north, 51.6180165487737, epsilon = 1e-12);
+        assert_approx_eq!(f64, b.east, 0.0, epsilon = 1e-12);
+        let (west, south, east, north) = merc.tile_bounds(&tile);
+        assert_approx_eq!(f64, east - west, 2.0 * merc.extent() / 1024.0, epsilon = 1e-6);
+        assert_approx_eq!(f64, merc.forward(b.south, b.west).x, west, epsilon = 1e-6);
+        assert_approx_eq!(f64, merc.forward(b.south, b.west).y, south, epsilon = 1e-6);
+        assert_approx_eq!(f64, merc.forward(b.north, b.east).y, north, epsilon = 1e-6);
+        assert_eq!(Tile::from_latlon(tile.center(), 10), Ok(tile));
+
+        let world = Tile::new(0, 0, 0).unwrap().bounds();
+        assert_approx_eq!(f64, world.north, WebMercator::MAX_LATITUDE, epsilon = 1e-12);
+        assert_approx_eq!(f64, world.south, -WebMercator::MAX_LATITUDE, epsilon = 1e-12);
+        assert_eq!(merc.tile_bounds(&Tile::new(0, 0, 0).unwrap()), (-merc.extent(), -merc.extent(), merc.extent(), merc.extent()));
+
+        assert_approx_eq!(f64, merc.resolution(0.0, 0, 256).unwrap(), 156543.03392804097, epsilon = 1e-6);
+        assert_approx_eq!(f64, merc.resolution(51.5074, 10, 256).unwrap(), 95.1508848928761, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_hierarchy()
+    {
+        let tile = Tile::new(10, 511, 340).unwrap();
+        assert_eq!(tile.to_string(), "10/511/340");
+        assert_eq!(tile.tms_y(), 683);
+        assert_eq!(Tile::from_tms(10, 511, 683), Ok(tile));
+        assert_eq!(tile.parent(), Some(Tile { z: 9, x: 255, y: 170 }));
+        assert_eq!(Tile::new(0, 0, 0).unwrap().parent(), None);
+        let children = tile.children().unwrap();
+        assert_eq!(children[3], Tile { z: 11, x: 1023, y: 681 });
+        assert!(children.iter().all(|c| c.parent() == Some(tile)));
+        let b = tile.bounds();
+        assert_eq!(children[0].bounds().north, b.north);
+        assert_eq!(children[3].bounds().east, b.east);
+    }
+
+    #[test]
+    fn test_errors()
+    {
+        assert_eq!(Tile::new(31, 0, 0), Err(TileError::InvalidZoom(31)));
+        assert_eq!(Tile::new(2, 4, 0), Err(TileError::InvalidTile { z: 2, x: 4, y: 0 }));
+        assert_eq!(Tile::from_tms(2, 0, 4), Err(TileError::InvalidTile { z: 2, x: 0, y: 4 }));
+        assert_eq!(Tile::from_latlon(LatLon::new(0.0, 0.0), 31), Err(TileError::InvalidZoom(31)));
+        assert_eq!(Tile::new(30, 0, 0).unwrap().children(), Err(TileError::InvalidZoom(31)));
+
+        let merc = WebMercator::new();
+        assert!(merc.latlon_to_pixel(LatLon::new(0.0, 0.0), 30, 256).is_ok());
+        assert_eq!(merc.latlon_to_pixel(LatLon::new(0.0, 0.0), 31, 256), Err(TileError::InvalidZoom(31)));
+        assert_eq!(merc.pixel_to_latlon(0.0, 0.0, 64, 256), Err(TileError::InvalidZoom(64)));
+        assert_eq!(merc.resolution(0.0, 255, 256), Err(TileError::InvalidZoom(255)));
+    }
+}
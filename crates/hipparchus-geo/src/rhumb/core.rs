@@ -0,0 +1,343 @@
+#![allow(non_snake_case)]
+use hipparchus_mean::Power;
+use hipparchus_az::{Degrees, Remainder};
+use once_cell::sync::Lazy;
+use crate::Coord;
+use crate::earth::auxiliary::taupf;
+use crate::earth::ellipsoid::Ellipsoid;
+use crate::earth::models::Model;
+use crate::geodesic::trig;
+use crate::geodesic::Caps;
+
+// The order of the Gauss-Legendre quadrature used to integrate along the rhumb lines.
+const QUADRATURE_ORDER: usize = 16;
+
+// Below this difference of isometric latitudes, the ratios along the rhumb line are integrated.
+const PSI_THRESHOLD: f64 = 0.1;
+
+// The nodes and weights of the Gauss-Legendre quadrature on [-1, 1].
+static QUADRATURE: Lazy<[(f64, f64); QUADRATURE_ORDER]> = Lazy::new(||
+{
+    let n = QUADRATURE_ORDER;
+    let mut quadrature = [(0.0, 0.0); QUADRATURE_ORDER];
+    for (i, q) in quadrature.iter_mut().enumerate()
+    {
+        let mut x = (std::f64::consts::PI * (i as f64 + 0.75) / (n as f64 + 0.5)).cos();
+        loop
+        {
+            let (mut p0, mut p1) = (1.0, x);
+            for k in 2..=n
+            {
+                let p2 = ((2 * k - 1) as f64 * x * p1 - (k - 1) as f64 * p0) / k as f64;
+                p0 = p1;
+                p1 = p2;
+            }
+            let dp = n as f64 * (x * p1 - p0) / (x.sq() - 1.0);
+            let dx = p1 / dp;
+            x -= dx;
+            if dx.abs() <= 4.0 * f64::EPSILON
+            {
+                *q = (x, 2.0 / ((1.0 - x.sq()) * dp.sq()));
+                break;
+            }
+        }
+    }
+    quadrature
+});
+
+/// The solution of the direct or inverse rhumb line problem.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RhumbSolution
+{
+    /// latitude of point 2 (degrees).
+    pub lat2: f64,
+
+    /// longitude of point 2 (degrees).
+    pub lon2: f64,
+
+    /// azimuth of the rhumb line (degrees).
+    pub azi12: f64,
+
+    /// distance between point 1 and point 2 (meters).
+    pub s12: f64,
+
+    /// area under the rhumb line (meters<sup>2</sup>).
+    pub S12: f64,
+}
+
+/// Solve the direct and inverse problems of rhumb lines (loxodromes) on an ellipsoid.
+///
+/// A rhumb line crosses all the meridians at the same azimuth. The distances are computed with
+/// the rectifying latitude μ and the longitudes with the isometric latitude ψ derived from the
+/// conformal latitude χ. The areas are computed with the authalic latitude ξ, with the same
+/// convention as the geodesic: S12 is the area between the rhumb line from point 1 to point 2 and
+/// the equator, positive if the rhumb line goes eastward in the northern hemisphere.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Rhumb
+{
+    pub elps: Ellipsoid,
+
+    // signed eccentricity
+    es: f64,
+
+    // rectifying radius
+    rm: f64,
+
+    // authalic radius squared
+    c2: f64,
+}
+
+// The quantities accumulated along a rhumb line between two latitudes.
+struct Span
+{
+    // difference of isometric latitudes (radians)
+    psi12: f64,
+
+    // ratio between the meridian distance and the isometric latitude (meters)
+    ratio: f64,
+
+    // mean of the sine of authalic latitude with respect to the isometric latitude
+    sxi: f64,
+}
+
+impl Rhumb
+{
+    pub fn new(a: f64, f: f64) -> Self
+    {
+        Self::with(Ellipsoid::new(a, 1.0 / f))
+    }
+
+    pub fn model<T>() -> Self where T: Model
+    {
+        Self::with(T::elps())
+    }
+
+    pub fn with(elps: Ellipsoid) -> Self
+    {
+        let es = elps.signed_eccentricity();
+        let rm = elps.rectifying_radius();
+        let c2 = elps.authalic_radius().sq();
+        Self { elps, es, rm, c2 }
+    }
+
+    /// Get the total area of the ellipsoid in square meters.
+    pub fn area(&self) -> f64
+    {
+        self.c2 * 4.0 * std::f64::consts::PI
+    }
+
+    /// Solve the inverse problem: find the azimuth and the distance of the rhumb line from point 1 to point 2.
+    ///
+    /// The rhumb line takes the shorter way in longitude, so |lon12| ≤ 180°. The returned `lat2`
+    /// and `lon2` are the given ones.
+    pub fn inverse(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> RhumbSolution
+    {
+        let lat1 = Coord::Latitude.nan(lat1);
+        let lat2 = Coord::Latitude.nan(lat2);
+        let lam12 = trig::ang_diff(lon1, lon2).0.to_radians();
+        let span = self.span(lat1, lat2);
+        let azi12 = trig::atan2d(lam12, span.psi12);
+        let (s12, S12) = if span.psi12.is_infinite()
+        {
+            // One of the points is a pole, the rhumb line is a meridian.
+            (self.meridian_distance(lat1, lat2).abs(), 0.0)
+        }
+        else
+        {
+            (lam12.hypot(span.psi12) * span.ratio, self.c2 * lam12 * span.sxi)
+        };
+        RhumbSolution { lat2, lon2, azi12, s12, S12 }
+    }
+
+    /// Solve the direct problem: place point 2 at distance s12 (meters) from point 1 along the rhumb line with azimuth azi12.
+    ///
+    /// Pass `Caps::LONG_UNROLL` in `outmask` to get the unrolled longitude of point 2 instead of
+    /// reducing it to [-180°, 180°]. The latitude and longitude are NaN if the rhumb line passes a pole.
+    pub fn direct(&self, lat1: f64, lon1: f64, azi12: f64, s12: f64, outmask: Caps) -> RhumbSolution
+    {
+        let lat1 = Coord::Latitude.nan(lat1);
+        let (salp, calp) = trig::sincosd(trig::ang_round(azi12));
+        let mu2 = self.elps.rectifying_latitude(lat1.to_radians()) + s12 * calp / self.rm;
+        if mu2.abs() > std::f64::consts::FRAC_PI_2
+        {
+            return RhumbSolution { lat2: f64::NAN, lon2: f64::NAN, azi12, s12, S12: f64::NAN };
+        }
+
+        let lat2 = self.elps.rectifying_latitude_inverse(mu2).to_degrees();
+        let span = self.span(lat1, lat2);
+        let lam12 = if salp == 0.0 { 0.0 } else { s12 * salp / span.ratio };
+        let lon2 = lon1 + lam12.to_degrees();
+        let lon2 = if outmask.contains(Caps::LONG_UNROLL) { lon2 } else { lon2.norm_degrees(Remainder::InvertedSymmetry) };
+        let S12 = if span.psi12.is_infinite() { 0.0 } else { self.c2 * lam12 * span.sxi };
+        RhumbSolution { lat2, lon2, azi12, s12, S12 }
+    }
+
+    // Get the isometric latitude ψ (radians) of the latitude (degrees).
+    fn isometric(&self, lat: f64) -> f64
+    {
+        let (sphi, cphi) = trig::sincosd(lat);
+        taupf(sphi / cphi, self.es).asinh()
+    }
+
+    // Get the signed distance along the meridian between two latitudes (degrees).
+    fn meridian_distance(&self, lat1: f64, lat2: f64) -> f64
+    {
+        let mu1 = self.elps.rectifying_latitude(lat1.to_radians());
+        let mu2 = self.elps.rectifying_latitude(lat2.to_radians());
+        self.rm * (mu2 - mu1)
+    }
+
+    // Get the meridian radius of curvature, the derivative of ψ and the sine of ξ at φ (radians).
+    fn derivatives(&self, phi: f64) -> (f64, f64, f64)
+    {
+        let (sphi, cphi) = phi.sin_cos();
+        let w2 = 1.0 - self.elps.e1sq * sphi.sq();
+        let rho = self.elps.a * (1.0 - self.elps.e1sq) / (w2 * w2.sqrt());
+        let dpsi = (1.0 - self.elps.e1sq) / (w2 * cphi);
+        (rho, dpsi, self.elps.authalic_sine(sphi))
+    }
+
+    // Integrate the derivatives between two latitudes (degrees) and return the means over [φ1, φ2].
+    fn means(&self, lat1: f64, lat2: f64) -> (f64, f64, f64, f64)
+    {
+        let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+        let (mid, half) = ((phi1 + phi2) / 2.0, (phi2 - phi1) / 2.0);
+        QUADRATURE.iter().fold((0.0, 0.0, 0.0, 0.0), |(m, p, a, d), &(x, w)|
+        {
+            let phi = mid + half * x;
+            let (rho, dpsi, sxi) = self.derivatives(phi);
+            let w = w / 2.0;
+            (m + w * rho, p + w * dpsi, a + w * sxi * dpsi, d + w * (sxi - phi.sin()) * dpsi)
+        })
+    }
+
+    // Compute the quantities along the rhumb line between two latitudes (degrees).
+    fn span(&self, lat1: f64, lat2: f64) -> Span
+    {
+        let psi12 = self.isometric(lat2) - self.isometric(lat1);
+        if psi12.is_infinite() || psi12.is_nan()
+        {
+            Span { psi12, ratio: 0.0, sxi: f64::NAN }
+        }
+        else if psi12.abs() < PSI_THRESHOLD
+        {
+            // Short spans in isometric latitude, the differences would lose precision.
+            let (rho, dpsi, sxi, _) = self.means(lat1, lat2);
+            Span { psi12, ratio: rho / dpsi, sxi: sxi / dpsi }
+        }
+        else
+        {
+            // The integral of sin(φ)ψ'(φ) is ln(1 - e² sin²φ) / 2 - ln(cos φ).
+            let f = |lat: f64|
+            {
+                let (sphi, cphi) = trig::sincosd(lat);
+                (1.0 - self.elps.e1sq * sphi.sq()).ln() / 2.0 - cphi.ln()
+            };
+            let (_, _, _, d) = self.means(lat1, lat2);
+            let sxi = f(lat2) - f(lat1) + (lat2 - lat1).to_radians() * d;
+            Span { psi12, ratio: self.meridian_distance(lat1, lat2) / psi12, sxi: sxi / psi12 }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::earth::models::{WGS84, Sphere};
+    use float_cmp::assert_approx_eq;
+    use rstest::*;
+
+    #[test]
+    fn test_quadrature()
+    {
+        let sum: f64 = QUADRATURE.iter().map(|&(_, w)| w).sum();
+        assert_approx_eq!(f64, sum, 2.0, epsilon = 1e-14);
+        let x8: f64 = QUADRATURE.iter().map(|&(x, w)| w * x.powi(8)).sum();
+        assert_approx_eq!(f64, x8, 2.0 / 9.0, epsilon = 1e-14);
+    }
+
+    #[rstest]
+    // Reference values of RhumbSolve of GeographicLib and numerical integration.
+    #[case(40.6, -73.8, 51.6, -0.5, 77.768389710256, 5771083.383328, 37395209100030.38)]
+    #[case(40.6, -73.8, 49.01666667, 2.55, 81.173949660916, 6095917.162538, 38067359307073.17)]
+    #[case(-30.0, 170.0, 20.0, -160.0, 30.188579522850, 6400554.547750, -1910212371157.30)]
+    fn test_inverse(#[case] lat1: f64, #[case] lon1: f64, #[case] lat2: f64, #[case] lon2: f64, #[case] azi12: f64, #[case] s12: f64, #[case] S12: f64)
+    {
+        let rhumb = Rhumb::model::<WGS84>();
+        let sol = rhumb.inverse(lat1, lon1, lat2, lon2);
+        assert_approx_eq!(f64, sol.azi12, azi12, epsilon = 1e-11);
+        assert_approx_eq!(f64, sol.s12, s12, epsilon = 1e-6);
+        assert_approx_eq!(f64, sol.S12, S12, epsilon = 0.5);
+
+        let sol = rhumb.direct(lat1, lon1, azi12, s12, Caps::empty());
+        assert_approx_eq!(f64, sol.lat2, lat2, epsilon = 1e-11);
+        assert_approx_eq!(f64, sol.lon2, lon2, epsilon = 1e-11);
+        assert_approx_eq!(f64, sol.S12, S12, epsilon = 10.0);
+    }
+
+    #[test]
+    fn test_parallel()
+    {
+        // Along a parallel, the rhumb line is the parallel itself.
+        let rhumb = Rhumb::model::<WGS84>();
+        let elps = WGS84::elps();
+        let (sphi, cphi) = trig::sincosd(45.0);
+        let radius = elps.a * cphi / (1.0 - elps.e1sq * sphi.sq()).sqrt();
+        let sol = rhumb.inverse(45.0, 10.0, 45.0, 20.0);
+        assert_eq!(sol.azi12, 90.0);
+        assert_approx_eq!(f64, sol.s12, radius * 10.0f64.to_radians(), epsilon = 1e-8);
+        assert_approx_eq!(f64, sol.S12, rhumb.c2 * 10.0f64.to_radians() * elps.authalic_sine(sphi), epsilon = 1e-2);
+
+        let sol = rhumb.direct(45.0, 10.0, -90.0, radius * 10.0f64.to_radians(), Caps::empty());
+        assert_approx_eq!(f64, sol.lat2, 45.0, epsilon = 1e-12);
+        assert_approx_eq!(f64, sol.lon2, 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_meridian()
+    {
+        let rhumb = Rhumb::model::<WGS84>();
+        let quadrant = 10001965.729313;
+        let sol = rhumb.inverse(0.0, 10.0, 90.0, 50.0);
+        assert_eq!(sol.azi12, 0.0);
+        assert_approx_eq!(f64, sol.s12, quadrant, epsilon = 1e-6);
+        assert_eq!(sol.S12, 0.0);
+
+        let sol = rhumb.inverse(0.0, 10.0, -90.0, 10.0);
+        assert_eq!(sol.azi12, 180.0);
+        assert_approx_eq!(f64, sol.s12, quadrant, epsilon = 1e-6);
+
+        let sol = rhumb.direct(0.0, 10.0, 0.0, rhumb.rm * std::f64::consts::FRAC_PI_2, Caps::empty());
+        assert_approx_eq!(f64, sol.lat2, 90.0, epsilon = 1e-9);
+        assert_eq!(sol.lon2, 10.0);
+
+        // The rhumb line passes the pole.
+        let sol = rhumb.direct(0.0, 10.0, 30.0, 2.0 * quadrant, Caps::empty());
+        assert!(sol.lat2.is_nan());
+        assert!(sol.lon2.is_nan());
+    }
+
+    #[test]
+    fn test_long_unroll()
+    {
+        let rhumb = Rhumb::model::<WGS84>();
+        let sol = rhumb.inverse(10.0, 170.0, 20.0, -170.0);
+        let wrapped = rhumb.direct(10.0, 170.0, sol.azi12, sol.s12, Caps::empty());
+        let unrolled = rhumb.direct(10.0, 170.0, sol.azi12, sol.s12, Caps::LONG_UNROLL);
+        assert_approx_eq!(f64, wrapped.lon2, -170.0, epsilon = 1e-11);
+        assert_approx_eq!(f64, unrolled.lon2, 190.0, epsilon = 1e-11);
+    }
+
+    #[test]
+    fn test_sphere()
+    {
+        // On a sphere, s12 = R Δφ / cos(azi12).
+        let rhumb = Rhumb::model::<Sphere>();
+        let sol = rhumb.inverse(10.0, 20.0, 30.0, 40.0);
+        let psi12 = 30.0f64.to_radians().tan().asinh() - 10.0f64.to_radians().tan().asinh();
+        let azi12 = 20.0f64.to_radians().atan2(psi12);
+        assert_approx_eq!(f64, sol.azi12, azi12.to_degrees(), epsilon = 1e-12);
+        assert_approx_eq!(f64, sol.s12, Sphere::A * 20.0f64.to_radians() / azi12.cos(), epsilon = 1e-6);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::geodesic::{Caps, Spacing, Waypoint};
+use crate::rhumb::core::{Rhumb, RhumbSolution};
+
+/// A rhumb line starting at a given point with a given azimuth.
+///
+/// Lines are usually built with `Rhumb::line`, `Rhumb::inverse_line` or `Rhumb::direct_line`;
+/// the last two also set point 3 of the line, which bounds the waypoints.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RhumbLine
+{
+    rhumb: Rhumb,
+    lat1: f64,
+    lon1: f64,
+    azi12: f64,
+    s13: f64,
+}
+
+impl RhumbLine
+{
+    /// Place a point at distance `s12` (meters) from point 1 along the line.
+    ///
+    /// See `Rhumb::direct` for the meaning of `outmask`.
+    pub fn position(&self, s12: f64, outmask: Caps) -> RhumbSolution
+    {
+        self.rhumb.direct(self.lat1, self.lon1, self.azi12, s12, outmask)
+    }
+
+    /// Specify the position of point 3 on the line by the distance `s13` (meters) from point 1.
+    pub fn set_distance(&mut self, s13: f64)
+    {
+        self.s13 = s13;
+    }
+
+    /// Get the latitude of point 1 (degrees).
+    pub fn latitude(&self) -> f64
+    {
+        self.lat1
+    }
+
+    /// Get the longitude of point 1 (degrees).
+    pub fn longitude(&self) -> f64
+    {
+        self.lon1
+    }
+
+    /// Get the azimuth of the line (degrees).
+    pub fn azimuth(&self) -> f64
+    {
+        self.azi12
+    }
+
+    /// Get the distance between point 1 and point 3 (meters), NaN if point 3 is not set.
+    pub fn distance(&self) -> f64
+    {
+        self.s13
+    }
+
+    /// Iterate the waypoints from point 1 to point 3 of the line every `step` meters.
+    ///
+    /// The last waypoint is placed exactly at point 3, so it may be closer than `step` to the
+    /// previous one. See `Rhumb::direct` for the meaning of `outmask`. No waypoint is returned if
+    /// point 3 of the line is not set, `step` is not a positive number or there would be more
+    /// than `Waypoint::MAX_COUNT` waypoints.
+    pub fn waypoints_by_distance(&self, step: f64, outmask: Caps) -> impl ExactSizeIterator<Item = Waypoint> + '_
+    {
+        self.waypoints(Spacing::by_distance(self.s13, step), outmask)
+    }
+
+    /// Iterate `n` equally spaced waypoints from point 1 to point 3 of the line, both included.
+    ///
+    /// A single waypoint at point 1 is returned if `n` is 1. See `waypoints_by_distance` for the
+    /// requirements on the line.
+    pub fn waypoints_by_count(&self, n: usize, outmask: Caps) -> impl ExactSizeIterator<Item = Waypoint> + '_
+    {
+        self.waypoints(Spacing::by_count(self.s13, n), outmask)
+    }
+
+    fn waypoints(&self, spacing: Spacing, outmask: Caps) -> impl ExactSizeIterator<Item = Waypoint> + '_
+    {
+        (0..spacing.len).map(move |i|
+        {
+            let s = if spacing.is_end(i) { self.s13 } else { spacing.step * i as f64 };
+            let sol = self.position(s, outmask);
+            Waypoint { lat: sol.lat2, lon: sol.lon2, azi: self.azi12, s }
+        })
+    }
+}
+
+impl Rhumb
+{
+    /// Create a rhumb line starting at (lat1, lon1) with azimuth azi12 (degrees).
+    pub fn line(&self, lat1: f64, lon1: f64, azi12: f64) -> RhumbLine
+    {
+        RhumbLine { rhumb: *self, lat1, lon1, azi12, s13: f64::NAN }
+    }
+
+    /// Create a rhumb line from point 1 to point 2, with point 3 set at point 2.
+    pub fn inverse_line(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> RhumbLine
+    {
+        let sol = self.inverse(lat1, lon1, lat2, lon2);
+        let mut line = self.line(lat1, lon1, sol.azi12);
+        line.set_distance(sol.s12);
+        line
+    }
+
+    /// Create a rhumb line starting at (lat1, lon1) with azimuth azi12, with point 3 set at distance s12 (meters).
+    pub fn direct_line(&self, lat1: f64, lon1: f64, azi12: f64, s12: f64) -> RhumbLine
+    {
+        let mut line = self.line(lat1, lon1, azi12);
+        line.set_distance(s12);
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::earth::models::WGS84;
+    use float_cmp::assert_approx_eq;
+    use rstest::*;
+
+    #[rstest]
+    #[case(500e3, 13)]
+    #[case(1e7, 2)]
+    fn test_waypoints_by_distance(#[case] step: f64, #[case] count: usize)
+    {
+        let rhumb = Rhumb::model::<WGS84>();
+        let line = rhumb.inverse_line(40.6, -73.8, 51.6, -0.5);
+        let waypoints: Vec<Waypoint> = line.waypoints_by_distance(step, Caps::empty()).collect();
+        assert_eq!(waypoints.len(), count);
+        for w in waypoints.iter()
+        {
+            let sol = rhumb.inverse(40.6, -73.8, w.lat, w.lon);
+            assert_approx_eq!(f64, sol.s12, w.s, epsilon = 1e-6);
+            assert_eq!(w.azi, line.azimuth());
+            if w.s > 0.0
+            {
+                assert_approx_eq!(f64, sol.azi12, line.azimuth(), epsilon = 1e-10);
+            }
+        }
+        let last = waypoints.last().unwrap();
+        assert_eq!(last.s, line.distance());
+        assert_approx_eq!(f64, last.lat, 51.6, epsilon = 1e-11);
+        assert_approx_eq!(f64, last.lon, -0.5, epsilon = 1e-11);
+    }
+
+    #[test]
+    fn test_waypoints_by_count()
+    {
+        let rhumb = Rhumb::model::<WGS84>();
+        let line = rhumb.direct_line(10.0, 170.0, 80.0, 3000e3);
+        let waypoints: Vec<Waypoint> = line.waypoints_by_count(4, Caps::LONG_UNROLL).collect();
+        let s: Vec<f64> = waypoints.iter().map(|w| w.s).collect();
+        assert_eq!(s, vec![0.0, 1000e3, 2000e3, 3000e3]);
+        assert!(waypoints.windows(2).all(|w| w[1].lon > w[0].lon && w[1].lat > w[0].lat));
+        assert!(waypoints.last().unwrap().lon > 180.0);
+
+        assert_eq!(line.waypoints_by_count(1, Caps::empty()).len(), 1);
+        assert_eq!(rhumb.line(10.0, 170.0, 80.0).waypoints_by_count(4, Caps::empty()).len(), 0);
+        assert_eq!(line.waypoints_by_distance(-1.0, Caps::empty()).len(), 0);
+        assert_eq!(line.waypoints_by_distance(1e-300, Caps::empty()).len(), 0);
+        assert_eq!(line.waypoints_by_distance(3000e3 / (Waypoint::MAX_COUNT - 1) as f64, Caps::empty()).len(), Waypoint::MAX_COUNT);
+    }
+}
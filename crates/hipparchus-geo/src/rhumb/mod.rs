@@ -0,0 +1,7 @@
+pub use core::{Rhumb, RhumbSolution};
+pub use line::RhumbLine;
+pub use polygon::RhumbPolygonArea;
+
+mod core;
+mod line;
+mod polygon;
@@ -0,0 +1,163 @@
+use crate::geodesic::{Caps, PolygonAccumulator, PolygonMeasure, Winding};
+use crate::rhumb::core::Rhumb;
+
+/// Compute the perimeter and area of a polygon whose edges are rhumb lines.
+#[derive(Debug, Clone)]
+pub struct RhumbPolygonArea<'a>
+{
+    rhumb: &'a Rhumb,
+    sums: PolygonAccumulator,
+}
+
+impl<'a> RhumbPolygonArea<'a>
+{
+    /// Create a new RhumbPolygonArea using a Rhumb.
+    pub fn new(rhumb: &'a Rhumb, winding: Winding) -> Self
+    {
+        Self { rhumb, sums: PolygonAccumulator::new(winding) }
+    }
+
+    /// Add a point to the polygon
+    pub fn add_point(&mut self, lat: f64, lon: f64)
+    {
+        self.sums.add_point(lat, lon, |lat1, lon1|
+        {
+            let sol = self.rhumb.inverse(lat1, lon1, lat, lon);
+            (sol.s12, sol.S12)
+        });
+    }
+
+    /// Add an edge to the polygon using an azimuth (in degrees) and a distance (in meters). This can only be called after at least one point has been added.
+    ///
+    /// # Panics
+    /// Panics if no points have been added yet.
+    pub fn add_edge(&mut self, azimuth: f64, distance: f64)
+    {
+        if self.sums.count() == 0
+        {
+            panic!("RhumbPolygonArea::add_edge: No points added yet");
+        }
+
+        self.sums.add_edge(distance, |lat1, lon1|
+        {
+            let sol = self.rhumb.direct(lat1, lon1, azimuth, distance, Caps::LONG_UNROLL);
+            (sol.lat2, sol.lon2, sol.S12)
+        });
+    }
+
+    /// Consumes the RhumbPolygonArea and returns the perimeter (meters), the area (meters²) and
+    /// the number of points added to the polygon.
+    ///
+    /// See `PolygonArea::compute` for the meaning of `sign`.
+    pub fn compute(self, sign: bool) -> PolygonMeasure
+    {
+        let rhumb = self.rhumb;
+        self.sums.compute(rhumb.area(), sign, |lat1, lon1, lat2, lon2|
+        {
+            let sol = rhumb.inverse(lat1, lon1, lat2, lon2);
+            (sol.s12, sol.S12)
+        })
+    }
+
+    /// Check what the perimeter and area would be if this point was added to the polygon without actually adding it
+    pub fn test_point(&self, lat: f64, lon: f64, sign: bool) -> PolygonMeasure
+    {
+        let mut pa = self.clone();
+        pa.add_point(lat, lon);
+        pa.compute(sign)
+    }
+
+    /// Check what the perimeter and area would be if this edge was added to the polygon without actually adding it
+    pub fn test_edge(&self, azimuth: f64, distance: f64, sign: bool) -> PolygonMeasure
+    {
+        let mut pa = self.clone();
+        pa.add_edge(azimuth, distance);
+        pa.compute(sign)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::earth::models::{Model, WGS84};
+    use float_cmp::assert_approx_eq;
+
+    // The area of the quadrangle between two parallels and two meridians.
+    fn quadrangle(lat1: f64, lat2: f64, lon12: f64) -> f64
+    {
+        let elps = WGS84::elps();
+        let c2 = elps.authalic_radius().powi(2);
+        let (s1, s2) = (lat1.to_radians().sin(), lat2.to_radians().sin());
+        c2 * lon12.to_radians() * (elps.authalic_sine(s2) - elps.authalic_sine(s1))
+    }
+
+    #[test]
+    fn test_quadrangle()
+    {
+        // The edges along the parallels and the meridians are rhumb lines.
+        let rhumb = Rhumb::model::<WGS84>();
+        let mut pa = RhumbPolygonArea::new(&rhumb, Winding::CounterClockwise);
+        pa.add_point(0.0, 0.0);
+        pa.add_point(0.0, 1.0);
+        pa.add_point(1.0, 1.0);
+        pa.add_point(1.0, 0.0);
+        let m = pa.clone().compute(true);
+        assert_approx_eq!(f64, m.area, quadrangle(0.0, 1.0, 1.0), epsilon = 1e-2);
+        assert_eq!(m.count, 4);
+
+        let m = pa.test_point(0.5, -0.5, true);
+        assert!(m.area > quadrangle(0.0, 1.0, 1.0));
+        assert_eq!(m.count, 5);
+
+        let mut pa = RhumbPolygonArea::new(&rhumb, Winding::Clockwise);
+        pa.add_point(0.0, 0.0);
+        pa.add_point(0.0, 1.0);
+        pa.add_point(1.0, 1.0);
+        pa.add_point(1.0, 0.0);
+        assert_approx_eq!(f64, pa.compute(true).area, -quadrangle(0.0, 1.0, 1.0), epsilon = 1e-2);
+    }
+
+    #[test]
+    fn test_antimeridian()
+    {
+        let rhumb = Rhumb::model::<WGS84>();
+        let mut pa = RhumbPolygonArea::new(&rhumb, Winding::CounterClockwise);
+        pa.add_point(-10.0, 170.0);
+        pa.add_point(-10.0, -170.0);
+        pa.add_point(10.0, -170.0);
+        pa.add_point(10.0, 170.0);
+        assert_approx_eq!(f64, pa.compute(true).area, quadrangle(-10.0, 10.0, 20.0), epsilon = 1.0);
+    }
+
+    #[test]
+    fn test_pole()
+    {
+        // A ring along the parallel of 80° encloses the polar cap.
+        let rhumb = Rhumb::model::<WGS84>();
+        let cap = quadrangle(80.0, 90.0, 360.0);
+        let mut pa = RhumbPolygonArea::new(&rhumb, Winding::CounterClockwise);
+        pa.add_point(80.0, 0.0);
+        pa.add_point(80.0, 120.0);
+        pa.add_point(80.0, -120.0);
+        let m = pa.compute(true);
+        assert_approx_eq!(f64, m.area, cap, epsilon = 1.0);
+
+        let mut pa = RhumbPolygonArea::new(&rhumb, Winding::CounterClockwise);
+        pa.add_point(80.0, 0.0);
+        pa.add_edge(90.0, m.perimeter / 3.0);
+        pa.add_edge(90.0, m.perimeter / 3.0);
+        let e = pa.compute(true);
+        assert_approx_eq!(f64, e.area, cap, epsilon = 1.0);
+        assert_approx_eq!(f64, e.perimeter, m.perimeter, epsilon = 1e-6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_edge_panic()
+    {
+        let rhumb = Rhumb::model::<WGS84>();
+        let mut pa = RhumbPolygonArea::new(&rhumb, Winding::CounterClockwise);
+        pa.add_edge(90.0, 1000.0);
+    }
+}